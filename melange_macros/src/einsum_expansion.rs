@@ -0,0 +1,227 @@
+use crate::einsum_syntax::*;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Error, LitByteStr, Result};
+
+/// Generates the code of an `einsum!` invocation: an inner function generic
+/// over the types of the operands, whose bounds check the ranks of the
+/// operands and the equality of dimensions sharing a label, called on them.
+pub fn expand_einsum(input: EinsumInput) -> Result<TokenStream> {
+    let subscripts = Subscripts::parse(&input.subscripts)?;
+    let operands: Vec<_> = input.operands.iter().collect();
+    if operands.len() != subscripts.inputs.len() {
+        return Err(Error::new(
+            input.subscripts.span(),
+            format!(
+                "Subscripts describe {} operands but {} were given.",
+                subscripts.inputs.len(),
+                operands.len()
+            ),
+        ));
+    }
+
+    let inputs: Vec<&[u8]> = subscripts.inputs.iter().map(|l| &l[..]).collect();
+    let output = &subscripts.output[..];
+    let variable = input.var.is_some();
+    let n = inputs.len();
+
+    let shapes: Vec<_> = (0..n).map(|k| format_ident!("S{}", k)).collect();
+    let transpose_policies: Vec<_> = (0..n).map(|k| format_ident!("C{}", k)).collect();
+    let layouts: Vec<_> = (0..n).map(|k| format_ident!("L{}", k)).collect();
+    let allocation_policies: Vec<_> = (0..n).map(|k| format_ident!("P{}", k)).collect();
+    let args: Vec<_> = (0..n).map(|k| format_ident!("op{}", k)).collect();
+    let operand_idents: Vec<_> = (0..n).map(|k| format_ident!("operand{}", k)).collect();
+    let p0 = &allocation_policies[0];
+
+    // Type of the dimension of the axis `axis` of the operand `k`.
+    let dim = |k: usize, axis: usize| {
+        let shape = &shapes[k];
+        let axis = format_ident!("U{}", axis);
+        quote! { <#shape as __einsum::At<__einsum::#axis>>::Output }
+    };
+    let first_dim = |label: u8| {
+        inputs
+            .iter()
+            .enumerate()
+            .find_map(|(k, labels)| labels.iter().position(|l| *l == label).map(|a| dim(k, a)))
+            .unwrap()
+    };
+
+    let mut bounds = Vec::new();
+    for (k, labels) in inputs.iter().enumerate() {
+        let shape = &shapes[k];
+        let rank = format_ident!("U{}", labels.len());
+        bounds.push(quote! { #shape: __einsum::Len<Output = __einsum::#rank> });
+        for (axis, label) in labels.iter().enumerate() {
+            let axis_ident = format_ident!("U{}", axis);
+            bounds.push(quote! { #shape: __einsum::At<__einsum::#axis_ident> });
+
+            let first = first_dim(*label);
+            let current = dim(k, axis);
+            if first.to_string() != current.to_string() {
+                bounds.push(quote! { #first: __einsum::IsEqual<#current> });
+                bounds.push(quote! { __einsum::Eq<#first, #current>: __einsum::TRUE });
+            }
+        }
+    }
+
+    // Shape aliases nest their last axis outermost.
+    let out_shape = output
+        .iter()
+        .fold(quote! { __einsum::ATerm }, |acc, label| {
+            let dim = first_dim(*label);
+            quote! { __einsum::TArr<#dim, #acc> }
+        });
+
+    let byte_str = |labels: &[u8]| {
+        let lit = LitByteStr::new(labels, Span::call_site());
+        quote! { &#lit[..] }
+    };
+    let contraction = |labels: &[&[u8]], operands: &[TokenStream], out_labels: &[u8], out| {
+        let label_strs: Vec<_> = labels.iter().map(|l| byte_str(l)).collect();
+        let out_str = byte_str(out_labels);
+        if is_dot(labels, out_labels) {
+            quote! {
+                __einsum::contract_dot([#(#label_strs),*], [#(#operands),*], #out_str, #out);
+            }
+        } else {
+            quote! {
+                __einsum::contract(
+                    &[#(#label_strs),*],
+                    &[#(#operands),*],
+                    #out_str,
+                    &<#out_shape as __einsum::StaticShape>::to_vec(),
+                    #out,
+                );
+            }
+        }
+    };
+    let operand_refs: Vec<_> = operand_idents.iter().map(|o| quote! { &#o }).collect();
+    let forward = contraction(&inputs, &operand_refs, output, quote! { &mut out });
+    let out_layout = quote! { <#p0 as __einsum::StaticAllocationPolicy<T, #out_shape>>::Layout };
+
+    let function = if variable {
+        // The gradient of the operand `k` is the summation of the gradient of
+        // the result and of the other operands into the subscripts of `k`.
+        let backward: Vec<_> = (0..n)
+            .map(|k| {
+                let mut labels = vec![output];
+                let mut grad_operands = vec![quote! { &grad }];
+                for (j, operand) in operand_idents.iter().enumerate() {
+                    if j != k {
+                        labels.push(inputs[j]);
+                        grad_operands.push(quote! { &#operand });
+                    }
+                }
+                let label_strs: Vec<_> = labels.iter().map(|l| byte_str(l)).collect();
+                let self_labels = byte_str(inputs[k]);
+                let dot = is_dot(&labels, inputs[k]);
+                let arg = &args[k];
+
+                quote! {
+                    #arg.einsum_backward(
+                        &[#(#label_strs),*],
+                        &[#(#grad_operands),*],
+                        #self_labels,
+                        #dot,
+                    );
+                }
+            })
+            .collect();
+
+        quote! {
+            #[allow(clippy::type_complexity, clippy::too_many_arguments)]
+            fn __einsum_fn<T, #(#shapes, #transpose_policies, #layouts, #allocation_policies,)* Cback, Lback, Pback>(
+                #(
+                    #args: __einsum::Variable<
+                        T,
+                        #shapes,
+                        #transpose_policies,
+                        #layouts,
+                        #allocation_policies,
+                        <#allocation_policies as __einsum::StaticAllocationPolicy<T, #shapes>>::Layout,
+                        __einsum::Contiguous,
+                        <Pback as __einsum::StaticAllocationPolicy<T, #shapes>>::Layout,
+                        Pback,
+                    >,
+                )*
+            ) -> __einsum::Variable<
+                T,
+                #out_shape,
+                __einsum::Contiguous,
+                #out_layout,
+                #p0,
+                #out_layout,
+                Cback,
+                Lback,
+                Pback,
+            >
+            where
+                T: __einsum::Contraction + 'static,
+                #(
+                    #shapes: __einsum::StaticShape + 'static,
+                    #transpose_policies: 'static,
+                    #layouts: for<'a> __einsum::Layout<'a, T> + 'static,
+                    #allocation_policies: __einsum::StaticAllocationPolicy<T, #shapes> + 'static,
+                    <#allocation_policies as __einsum::StaticAllocationPolicy<T, #shapes>>::Layout:
+                        for<'a> __einsum::Layout<'a, T> + 'static,
+                    Pback: __einsum::StaticAllocationPolicy<T, #shapes> + 'static,
+                    <Pback as __einsum::StaticAllocationPolicy<T, #shapes>>::Layout:
+                        for<'a> __einsum::Layout<'a, T> + for<'a> __einsum::LayoutMut<'a, T> + 'static,
+                )*
+                #(#bounds,)*
+                #out_shape: __einsum::StaticShape,
+                #p0: __einsum::StaticAllocationPolicy<T, #out_shape>,
+                #out_layout: for<'a> __einsum::Layout<'a, T>,
+                Cback: 'static,
+                Lback: for<'a> __einsum::Layout<'a, T> + 'static,
+            {
+                #(let #operand_idents = #args.einsum_operand();)*
+                let mut out: __einsum::Tensor<T, #out_shape, __einsum::Contiguous, #out_layout, #p0> =
+                    __einsum::Tensor::default();
+                #forward
+                let require_grad = [#(#args.einsum_requires_grad()),*].iter().any(|r| *r);
+
+                __einsum::Variable::einsum_result(
+                    out,
+                    require_grad,
+                    Box::new(move |grad: __einsum::Tensor<T, #out_shape, Cback, Lback, Pback>| {
+                        let grad = __einsum::Operand::new(&grad);
+                        #(#backward)*
+                    }),
+                )
+            }
+        }
+    } else {
+        quote! {
+            #[allow(clippy::type_complexity, clippy::too_many_arguments)]
+            fn __einsum_fn<T, #(#shapes, #transpose_policies, #layouts, #allocation_policies),*>(
+                #(#args: &__einsum::Tensor<T, #shapes, #transpose_policies, #layouts, #allocation_policies>),*
+            ) -> __einsum::Tensor<T, #out_shape, __einsum::Contiguous, #out_layout, #p0>
+            where
+                T: __einsum::Contraction,
+                #(#layouts: for<'a> __einsum::Layout<'a, T>,)*
+                #(#bounds,)*
+                #out_shape: __einsum::StaticShape,
+                #p0: __einsum::StaticAllocationPolicy<T, #out_shape>,
+            {
+                #(let #operand_idents = __einsum::Operand::new(#args);)*
+                let mut out: __einsum::Tensor<T, #out_shape, __einsum::Contiguous, #out_layout, #p0> =
+                    __einsum::Tensor::default();
+                #forward
+
+                out
+            }
+        }
+    };
+
+    Ok(quote! {
+        {
+            use melange::tensor::einsum::macro_support as __einsum;
+
+            #function
+
+            __einsum_fn(#(#operands),*)
+        }
+    })
+}
@@ -0,0 +1,113 @@
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::Ident;
+use syn::{Error, Expr, LitStr, Result};
+
+pub struct EinsumInput {
+    pub var: Option<Ident>,
+    pub subscripts: LitStr,
+    pub operands: Punctuated<Expr, Comma>,
+}
+
+impl Parse for EinsumInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let var = if input.peek(Ident) {
+            let ident: Ident = input.parse()?;
+            if ident != "var" {
+                return Err(Error::new(ident.span(), "Expected `var` or subscripts."));
+            }
+            Some(ident)
+        } else {
+            None
+        };
+
+        let subscripts = input.parse()?;
+        input.parse::<Comma>()?;
+
+        Ok(EinsumInput {
+            var,
+            subscripts,
+            operands: Punctuated::parse_separated_nonempty(input)?,
+        })
+    }
+}
+
+/// Labels of the axes of the operands and of the output.
+pub struct Subscripts {
+    pub inputs: Vec<Vec<u8>>,
+    pub output: Vec<u8>,
+}
+
+impl Subscripts {
+    /// Parses subscripts such as `"bij,bjk->bik"`. Without `->`, the output
+    /// contains the labels that appear once in the inputs in alphabetical order.
+    pub fn parse(lit: &LitStr) -> Result<Self> {
+        let value: String = lit.value().chars().filter(|c| !c.is_whitespace()).collect();
+        let error = |message: &str| Error::new(lit.span(), message);
+
+        let (inputs, output) = match value.find("->") {
+            Some(position) => (&value[..position], Some(&value[position + 2..])),
+            None => (&value[..], None),
+        };
+        let inputs: Vec<Vec<u8>> = inputs.split(',').map(|s| s.bytes().collect()).collect();
+        let all_labels = || inputs.iter().flatten();
+        if !all_labels().all(u8::is_ascii_alphabetic) {
+            return Err(error("Subscripts must be ASCII letters."));
+        }
+
+        let output: Vec<u8> = match output {
+            Some(output) => {
+                let output: Vec<u8> = output.bytes().collect();
+                for (i, label) in output.iter().enumerate() {
+                    if !label.is_ascii_alphabetic() {
+                        return Err(error("Subscripts must be ASCII letters."));
+                    }
+                    if output[..i].contains(label) {
+                        return Err(error("Output subscripts cannot be repeated."));
+                    }
+                    if !all_labels().any(|l| l == label) {
+                        return Err(error("Output subscripts must appear in the inputs."));
+                    }
+                }
+
+                output
+            }
+            None => {
+                let mut output: Vec<u8> = all_labels()
+                    .copied()
+                    .filter(|label| all_labels().filter(|l| *l == label).count() == 1)
+                    .collect();
+                output.sort_unstable();
+
+                output
+            }
+        };
+
+        Ok(Subscripts { inputs, output })
+    }
+}
+
+/// Returns true if the summation of the operands with subscripts `inputs`
+/// into `output` is a batched matrix product of two operands.
+pub fn is_dot(inputs: &[&[u8]], output: &[u8]) -> bool {
+    if inputs.len() != 2 {
+        return false;
+    }
+    let repeated = |labels: &[u8]| {
+        labels
+            .iter()
+            .enumerate()
+            .any(|(i, label)| labels[..i].contains(label))
+    };
+    let shared = |labels: &[u8], other: &[u8]| {
+        labels
+            .iter()
+            .all(|label| other.contains(label) || output.contains(label))
+    };
+
+    !repeated(inputs[0])
+        && !repeated(inputs[1])
+        && shared(inputs[0], inputs[1])
+        && shared(inputs[1], inputs[0])
+}
@@ -17,6 +17,12 @@ use closure_definition_syntax::*;
 mod search_replace;
 use search_replace::*;
 
+mod einsum_syntax;
+use einsum_syntax::*;
+
+mod einsum_expansion;
+use einsum_expansion::*;
+
 #[proc_macro_attribute]
 pub fn expand_operations(attr: TokenStream, item: TokenStream) -> TokenStream {
     // Parse input and impl block.
@@ -175,3 +181,18 @@ pub fn define_closure(attr: TokenStream, item: TokenStream) -> TokenStream {
     };
     result.into()
 }
+
+/// Computes the Einstein summation of tensors described by subscripts,
+/// e.g. `einsum!("bij,bjk->bik", &a, &b)`, or of variables when the
+/// subscripts are preceded by `var`, e.g. `einsum!(var "ij,jk->ik", x, y)`.
+/// Ranks of the operands and dimensions sharing a label are checked at
+/// compile time.
+#[proc_macro]
+pub fn einsum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as EinsumInput);
+
+    match expand_einsum(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
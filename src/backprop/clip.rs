@@ -0,0 +1,81 @@
+//! `clip` contains gradient clipping utilities that prevent exploding
+//! gradients, typically when training recurrent networks.
+//!
+//! Parameters of a model are variables of different shapes and types.
+//! The `Gradient` trait erases these differences so that `clip_grad_norm`
+//! and `clip_grad_value` can operate on a slice of trait objects.
+//! Only retained gradients are clipped, variables created without
+//! gradient retention are ignored.
+//!
+//! Gradients should be clipped after the backward pass and before
+//! the optimizer step.
+
+use super::variable::Variable;
+use crate::tensor::layout::LayoutMut;
+use num_traits::Float;
+use std::iter::Sum;
+
+/// Variables whose retained gradient can be inspected and modified in place.
+pub trait Gradient<T> {
+    /// Returns the sum of the squares of the elements of the gradient,
+    /// zero if the gradient is not retained.
+    fn grad_squared_norm(&self) -> T;
+
+    /// Multiplies the gradient by `factor`.
+    fn grad_scal_mul_(&self, factor: T);
+
+    /// Restricts the elements of the gradient to `[min, max]`.
+    fn grad_clamp_(&self, min: T, max: T);
+}
+
+impl<T, S, C, L, P, Lgrad, Cback, Lback, Pback> Gradient<T>
+    for Variable<T, S, C, L, P, Lgrad, Cback, Lback, Pback>
+where
+    T: Float + Sum,
+    Lgrad: for<'a> LayoutMut<'a, T>,
+{
+    fn grad_squared_norm(&self) -> T {
+        match &self.borrow().grad {
+            Some(grad) => grad.iter().map(|x| *x * *x).sum(),
+            None => T::zero(),
+        }
+    }
+
+    fn grad_scal_mul_(&self, factor: T) {
+        if let Some(grad) = &mut self.borrow_mut().grad {
+            grad.iter_mut().for_each(|x| *x = *x * factor);
+        }
+    }
+
+    fn grad_clamp_(&self, min: T, max: T) {
+        if let Some(grad) = &mut self.borrow_mut().grad {
+            grad.iter_mut().for_each(|x| *x = x.max(min).min(max));
+        }
+    }
+}
+
+/// Rescales the gradients of `params` so that their global L2 norm,
+/// computed as if all gradients were concatenated, is at most `max_norm`.
+///
+/// Returns the global norm before clipping.
+pub fn clip_grad_norm<T: Float>(params: &[&dyn Gradient<T>], max_norm: T) -> T {
+    let total_norm = params
+        .iter()
+        .fold(T::zero(), |acc, param| acc + param.grad_squared_norm())
+        .sqrt();
+
+    // Small constant from PyTorch that avoids divisions by zero.
+    let factor = max_norm / (total_norm + T::from(1e-6).unwrap());
+    if factor < T::one() {
+        params.iter().for_each(|param| param.grad_scal_mul_(factor));
+    }
+
+    total_norm
+}
+
+/// Clamps each element of the gradients of `params` to `[-clip_value, clip_value]`.
+pub fn clip_grad_value<T: Float>(params: &[&dyn Gradient<T>], clip_value: T) {
+    params
+        .iter()
+        .for_each(|param| param.grad_clamp_(-clip_value, clip_value));
+}
@@ -712,3 +712,333 @@ where
         })))
     }
 }
+
+#[expand_operations(
+    where_<T: Send + Sync + Copy + AddAssign + 'static>,
+)]
+#[define_closure(
+    where_: move |grad| {
+        let grad = grad.as_contiguous();
+        let zeros: Tensor<T, Z, Contiguous, <Pback as StaticAllocationPolicy<T, Z>>::Layout, Pback> =
+            Tensor::default();
+        let (mut self_grad, mut other_grad) = (zeros.as_contiguous(), zeros.as_contiguous());
+        grad.where_unchecked(&mask, &zeros, &mut self_grad);
+        zeros.where_unchecked(&mask, &grad, &mut other_grad);
+
+        self.backward(self_grad.sum_to::<S>());
+        other.backward(other_grad.sum_to::<Srhs>());
+    }
+)]
+impl<T, S, C, L, P, Pback> Variable<T, S, C, L, P, P::Layout, Contiguous, Pback::Layout, Pback>
+where
+    S: StaticShape + 'static,
+    C: 'static,
+    L: for<'a> Layout<'a, T> + 'static,
+    P: StaticAllocationPolicy<T, S> + 'static,
+    P::Layout: for<'a> Layout<'a, T> + 'static,
+    Pback: StaticAllocationPolicy<T, S> + 'static,
+    Pback::Layout: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T> + 'static,
+{
+    /// Selects the values of `self` where `mask` is true and those of `other`
+    /// elsewhere, the three operands being broadcast to the shape `Z` they
+    /// all broadcast to. The gradient is routed to `self` or `other`
+    /// according to `mask` and summed over the broadcast axes.
+    #[allow(clippy::type_complexity)]
+    pub fn operation<Z, Zm, Sm, Cm, Lm, Pm, Srhs, Crhs, Lrhs, Prhs, Cback, Lback>(
+        self,
+        mask: &Tensor<bool, Sm, Cm, Lm, Pm>,
+        other: Variable<
+            T,
+            Srhs,
+            Crhs,
+            Lrhs,
+            Prhs,
+            <Prhs as StaticAllocationPolicy<T, Srhs>>::Layout,
+            Contiguous,
+            <Pback as StaticAllocationPolicy<T, Srhs>>::Layout,
+            Pback,
+        >,
+    ) -> Variable<
+        T,
+        Z,
+        Contiguous,
+        <P as StaticAllocationPolicy<T, Z>>::Layout,
+        P,
+        <P as StaticAllocationPolicy<T, Z>>::Layout,
+        Cback,
+        Lback,
+        Pback,
+    >
+    where
+        S: BroadcastShape<Sm, Output = Zm> + Broadcast<Z> + BroadcastTo<Z>,
+        <S as Broadcast<Z>>::Output: TRUE,
+        <S as BroadcastTo<Z>>::Output: TRUE,
+        Zm: BroadcastShape<Srhs, Output = Z>,
+        Sm: StaticShape + Broadcast<Z>,
+        <Sm as Broadcast<Z>>::Output: TRUE,
+        Lm: for<'a> Layout<'a, bool>,
+        Pm: StaticAllocationPolicy<bool, Z> + 'static,
+        <Pm as StaticAllocationPolicy<bool, Z>>::Layout: for<'a> Layout<'a, bool> + 'static,
+        Srhs: StaticShape + Broadcast<Z> + BroadcastTo<Z> + 'static,
+        <Srhs as Broadcast<Z>>::Output: TRUE,
+        <Srhs as BroadcastTo<Z>>::Output: TRUE,
+        Z: StaticShape + 'static,
+        Crhs: 'static,
+        Lrhs: for<'a> Layout<'a, T> + 'static,
+        Prhs: StaticAllocationPolicy<T, Srhs> + 'static,
+        <Prhs as StaticAllocationPolicy<T, Srhs>>::Layout: for<'a> Layout<'a, T> + 'static,
+        P: StaticAllocationPolicy<T, Z>,
+        <P as StaticAllocationPolicy<T, Z>>::Layout:
+            for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>,
+        Pback: StaticAllocationPolicy<T, Srhs> + StaticAllocationPolicy<T, Z>,
+        <Pback as StaticAllocationPolicy<T, Srhs>>::Layout:
+            for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T> + 'static,
+        <Pback as StaticAllocationPolicy<T, Z>>::Layout:
+            for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>,
+        Cback: 'static,
+        Lback: for<'a> Layout<'a, T> + 'static,
+    {
+        let mask = mask.broadcast::<Z>().as_contiguous();
+        let (value, grad) = {
+            let self_ref = self.borrow();
+            let other_ref = other.borrow();
+            let mut value = Tensor::default();
+            self_ref.value.broadcast::<Z>().where_unchecked(
+                &mask,
+                &other_ref.value.broadcast(),
+                &mut value,
+            );
+
+            (
+                value,
+                if self_ref.grad.is_some() || other_ref.grad.is_some() {
+                    Some(Tensor::default())
+                } else {
+                    None
+                },
+            )
+        };
+
+        Variable(Rc::new(RefCell::new(BackpropNode {
+            value,
+            grad,
+            backward_op_name: "where_back",
+            backward_closure: Box::new(|| ()),
+        })))
+    }
+}
+
+#[expand_operations(
+    clamp<T=f64>(f64, f64),
+    clamp<T=f32>(f32, f32),
+    clamp<T=f64>(f64, f64) as clamp_straight_through,
+    clamp<T=f32>(f32, f32) as clamp_straight_through,
+)]
+#[define_closure(
+    clamp: move |grad| {
+        let inside = {
+            let self_ref = self.borrow();
+            self_ref.value.scal_ge(param0).and(&self_ref.value.scal_le(param1))
+        };
+        let zeros: Tensor<T, S, Contiguous, Pback::Layout, Pback> = Tensor::default();
+
+        let mut self_grad = Tensor::default();
+        grad.as_contiguous().where_unchecked(&inside, &zeros, &mut self_grad);
+
+        self.backward(self_grad);
+    }
+)]
+#[define_closure(
+    clamp_straight_through: move |grad| {
+        self.backward(grad.as_contiguous());
+    }
+)]
+impl<T, S, C, L, P, Pback> Variable<T, S, C, L, P, P::Layout, Contiguous, Pback::Layout, Pback>
+where
+    S: StaticShape + 'static,
+    C: 'static,
+    L: for<'a> Layout<'a, T> + 'static,
+    P: StaticAllocationPolicy<T, S> + 'static,
+    P::Layout: for<'a> Layout<'a, T> + 'static,
+    Pback: StaticAllocationPolicy<T, S> + 'static,
+    Pback::Layout: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T> + 'static,
+{
+    /// Restricts the values of `self` to `[param0, param1]`. The gradient is
+    /// zero outside of the range with `clamp` and passed through unchanged
+    /// with `clamp_straight_through`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `param0 > param1` or if either bound is NaN.
+    #[allow(clippy::type_complexity)]
+    pub fn operation<Cback, Lback>(self, param0: type0, param1: type1) -> Variable<T, S, Contiguous, <P as StaticAllocationPolicy<T, S>>::Layout, P, <P as StaticAllocationPolicy<T, S>>::Layout, Cback, Lback, Pback>
+    where
+        S: Broadcast<S>,
+        <S as Broadcast<S>>::Output: TRUE,
+        P: StaticAllocationPolicy<bool, S>,
+        <P as StaticAllocationPolicy<bool, S>>::Layout: for<'a> Layout<'a, bool>,
+        Cback: 'static,
+        Lback: for<'a> Layout<'a, T> + 'static,
+    {
+        let (value, grad) = {
+            let self_ref = self.borrow();
+            (
+                self_ref.value.placeholder(param0, param1),
+                if self_ref.grad.is_some() {
+                    Some(Tensor::default())
+                } else {
+                    None
+                },
+            )
+        };
+
+        Variable(Rc::new(RefCell::new(BackpropNode {
+            value,
+            grad,
+            backward_op_name: "clamp_back",
+            backward_closure: Box::new(|| ()),
+        })))
+    }
+}
+
+#[expand_operations(
+    add_bcast<T: Send + Sync + Copy + AddAssign + Add<Output = T> + 'static>,
+    sub_bcast<T: Send + Sync + Copy + AddAssign + Sub<Output = T> + Ring + Neg<Output = T> + Mul<Output = T> + 'static>,
+    mul_bcast<T: Send + Sync + Copy + AddAssign + MulAssign + Mul<Output = T> + 'static>,
+    div_bcast<T: Send + Sync + Copy + AddAssign + MulAssign + DivAssign + Div<Output = T> + Mul<Output = T> + Ring + Neg<Output = T> + 'static>,
+)]
+#[define_closure(
+    add_bcast: move |grad| {
+        self.backward(grad.sum_to::<S>());
+        other.backward(grad.sum_to::<Srhs>());
+    }
+)]
+#[define_closure(
+    sub_bcast: move |grad| {
+        let mut other_grad = grad.sum_to::<Srhs>();
+        other_grad.scal_mul_(-T::ONE);
+
+        self.backward(grad.sum_to::<S>());
+        other.backward(other_grad);
+    }
+)]
+#[define_closure(
+    mul_bcast: move |mut grad| {
+        let other_grad = {
+            let self_ref = self.borrow();
+            let other_grad = grad.mul(&self_ref.value.broadcast::<Z>());
+
+            other_grad
+        };
+
+        {
+            let other_ref = other.borrow();
+            grad.mul_(&other_ref.value.broadcast::<Z>());
+        }
+
+        self.backward(grad.sum_to::<S>());
+        other.backward(other_grad.sum_to::<Srhs>());
+    }
+)]
+#[define_closure(
+    div_bcast: move |mut grad| {
+        {
+            let other_ref = other.borrow();
+            grad.div_(&other_ref.value.broadcast::<Z>());
+        }
+
+        let mut other_grad = {
+            let self_ref = self.borrow();
+            let other_ref = other.borrow();
+            let mut other_grad = grad.mul(&self_ref.value.broadcast::<Z>());
+            other_grad.div_(&other_ref.value.broadcast::<Z>());
+
+            other_grad
+        };
+        other_grad.scal_mul_(-T::ONE);
+
+        self.backward(grad.sum_to::<S>());
+        other.backward(other_grad.sum_to::<Srhs>());
+    }
+)]
+impl<T, S, C, L, P, Pback> Variable<T, S, C, L, P, P::Layout, Contiguous, Pback::Layout, Pback>
+where
+    S: StaticShape + 'static,
+    C: 'static,
+    L: for<'a> Layout<'a, T> + 'static,
+    P: StaticAllocationPolicy<T, S> + 'static,
+    P::Layout: for<'a> Layout<'a, T> + 'static,
+    Pback: StaticAllocationPolicy<T, S> + 'static,
+    Pback::Layout: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T> + 'static,
+{
+    /// Applies the operation to `self` and `other` broadcast to the shape `Z`
+    /// both broadcast to. Gradients are summed over the broadcast axes.
+    #[allow(clippy::type_complexity)]
+    pub fn operation<Z, Srhs, Crhs, Lrhs, Prhs, Cback, Lback>(
+        self,
+        other: Variable<
+            T,
+            Srhs,
+            Crhs,
+            Lrhs,
+            Prhs,
+            <Prhs as StaticAllocationPolicy<T, Srhs>>::Layout,
+            Contiguous,
+            <Pback as StaticAllocationPolicy<T, Srhs>>::Layout,
+            Pback,
+        >,
+    ) -> Variable<
+        T,
+        Z,
+        Contiguous,
+        <P as StaticAllocationPolicy<T, Z>>::Layout,
+        P,
+        <P as StaticAllocationPolicy<T, Z>>::Layout,
+        Cback,
+        Lback,
+        Pback,
+    >
+    where
+        S: BroadcastShape<Srhs, Output = Z> + Broadcast<Z> + BroadcastTo<Z>,
+        <S as Broadcast<Z>>::Output: TRUE,
+        <S as BroadcastTo<Z>>::Output: TRUE,
+        Srhs: StaticShape + Broadcast<Z> + BroadcastTo<Z> + 'static,
+        <Srhs as Broadcast<Z>>::Output: TRUE,
+        <Srhs as BroadcastTo<Z>>::Output: TRUE,
+        Z: StaticShape + 'static,
+        Crhs: 'static,
+        Lrhs: for<'a> Layout<'a, T> + 'static,
+        Prhs: StaticAllocationPolicy<T, Srhs> + 'static,
+        <Prhs as StaticAllocationPolicy<T, Srhs>>::Layout: for<'a> Layout<'a, T> + 'static,
+        P: StaticAllocationPolicy<T, Z>,
+        <P as StaticAllocationPolicy<T, Z>>::Layout: for<'a> Layout<'a, T>,
+        Pback: StaticAllocationPolicy<T, Srhs> + StaticAllocationPolicy<T, Z>,
+        <Pback as StaticAllocationPolicy<T, Srhs>>::Layout:
+            for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T> + 'static,
+        <Pback as StaticAllocationPolicy<T, Z>>::Layout:
+            for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>,
+        Cback: 'static,
+        Lback: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T> + 'static,
+    {
+        let (value, grad) = {
+            let self_ref = self.borrow();
+            let other_ref = other.borrow();
+
+            (
+                self_ref.value.placeholder(&other_ref.value),
+                if self_ref.grad.is_some() || other_ref.grad.is_some() {
+                    Some(Tensor::default())
+                } else {
+                    None
+                },
+            )
+        };
+
+        Variable(Rc::new(RefCell::new(BackpropNode {
+            value,
+            grad,
+            backward_op_name: "bcast_back",
+            backward_closure: Box::new(|| ()),
+        })))
+    }
+}
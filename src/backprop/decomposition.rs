@@ -0,0 +1,317 @@
+//! `decomposition` contains the differentiable operations of the
+//! `decomposition` module of `tensor`: `solve`, `inverse` and `det`.
+//!
+//! Operations that can fail return a `Result` with a `LinalgError`
+//! before any variable is created. Backpropagation closures use the
+//! following identities where `G` is the gradient of the result:
+//! * `X = solve(A, B)`: `dB = solve(A^T, G)` and `dA = -dB * X^T`,
+//! * `Y = inverse(A)`: `dA = -Y^T * G * Y^T`,
+//! * `d = det(A)`: `dA = G * adjugate(A)^T`.
+
+use super::variable::{BackpropNode, Variable};
+use crate::tensor::allocation_policy::StaticAllocationPolicy;
+use crate::tensor::decomposition::{LinalgError, Matrix};
+use crate::tensor::prelude::*;
+use crate::tensor::transpose_policy::{Contiguous, TransposePolicy};
+use melange_macros::{define_closure, expand_operations};
+use std::cell::RefCell;
+use std::rc::Rc;
+use typenum::U1;
+
+#[expand_operations(
+    solve<T=f64>,
+    solve<T=f32>,
+)]
+#[define_closure(
+    solve: move |grad| {
+        let grad = grad.as_contiguous();
+        let other_grad = {
+            let self_ref = self.borrow();
+            let solved = self_ref
+                .value
+                .transpose()
+                .solve(&grad)
+                .expect("Matrix should be invertible as in the forward pass.");
+            let mut other_grad: Matrix<T, N, K, Pback> = Tensor::default();
+            other_grad.copy_from_slice(&solved);
+            other_grad
+        };
+
+        let mut self_grad = other_grad.dot(&solution.transpose());
+        self_grad.scal_mul_(-1.0);
+
+        self.backward(self_grad);
+        other.backward(other_grad);
+    }
+)]
+impl<T, N, C, L, P, Pback>
+    Variable<
+        T,
+        Shape2D<N, N>,
+        C,
+        L,
+        P,
+        <P as StaticAllocationPolicy<T, Shape2D<N, N>>>::Layout,
+        Contiguous,
+        <Pback as StaticAllocationPolicy<T, Shape2D<N, N>>>::Layout,
+        Pback,
+    >
+where
+    N: StaticDim + 'static,
+    C: TransposePolicy + 'static,
+    L: for<'a> Layout<'a, T> + 'static,
+    P: StaticAllocationPolicy<T, Shape2D<N, N>> + 'static,
+    <P as StaticAllocationPolicy<T, Shape2D<N, N>>>::Layout: for<'a> Layout<'a, T> + 'static,
+    Pback: StaticAllocationPolicy<T, Shape2D<N, N>> + 'static,
+    <Pback as StaticAllocationPolicy<T, Shape2D<N, N>>>::Layout:
+        for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T> + 'static,
+{
+    /// Solves the linear system `self * X = other` for `X`.
+    ///
+    /// Returns an error if the value of `self` is singular.
+    #[allow(clippy::type_complexity)]
+    pub fn operation<K, Crhs, Lrhs, Prhs, Cback, Lback>(
+        self,
+        other: Variable<
+            T,
+            Shape2D<N, K>,
+            Crhs,
+            Lrhs,
+            Prhs,
+            <Prhs as StaticAllocationPolicy<T, Shape2D<N, K>>>::Layout,
+            Contiguous,
+            <Pback as StaticAllocationPolicy<T, Shape2D<N, K>>>::Layout,
+            Pback,
+        >,
+    ) -> Result<
+        Variable<
+            T,
+            Shape2D<N, K>,
+            Contiguous,
+            <P as StaticAllocationPolicy<T, Shape2D<N, K>>>::Layout,
+            P,
+            <P as StaticAllocationPolicy<T, Shape2D<N, K>>>::Layout,
+            Cback,
+            Lback,
+            Pback,
+        >,
+        LinalgError,
+    >
+    where
+        K: StaticDim + 'static,
+        Crhs: 'static,
+        Lrhs: for<'a> Layout<'a, T> + 'static,
+        Prhs: StaticAllocationPolicy<T, Shape2D<N, K>> + 'static,
+        <Prhs as StaticAllocationPolicy<T, Shape2D<N, K>>>::Layout: for<'a> Layout<'a, T> + 'static,
+        P: StaticAllocationPolicy<T, Shape2D<N, K>>,
+        <P as StaticAllocationPolicy<T, Shape2D<N, K>>>::Layout: for<'a> Layout<'a, T> + 'static,
+        Pback: StaticAllocationPolicy<T, Shape2D<N, K>>,
+        <Pback as StaticAllocationPolicy<T, Shape2D<N, K>>>::Layout:
+            for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T> + 'static,
+        Cback: 'static,
+        Lback: for<'a> Layout<'a, T> + 'static,
+    {
+        let (value, grad) = {
+            let self_ref = self.borrow();
+            let other_ref = other.borrow();
+            (
+                self_ref.value.placeholder(&other_ref.value)?,
+                if self_ref.grad.is_some() || other_ref.grad.is_some() {
+                    Some(Tensor::default())
+                } else {
+                    None
+                },
+            )
+        };
+        let solution = value.as_contiguous();
+
+        Ok(Variable(Rc::new(RefCell::new(BackpropNode {
+            value,
+            grad,
+            backward_op_name: "solve_back",
+            backward_closure: Box::new(|| ()),
+        }))))
+    }
+}
+
+#[expand_operations(
+    inverse<T=f64>,
+    inverse<T=f32>,
+)]
+#[define_closure(
+    inverse: move |grad| {
+        let grad = grad.as_contiguous();
+        let mut self_grad = grad
+            .dot(&inverse.transpose())
+            .transpose()
+            .dot(&inverse)
+            .transpose()
+            .as_contiguous();
+        self_grad.scal_mul_(-1.0);
+
+        self.backward(self_grad);
+    }
+)]
+impl<T, N, C, L, P, Pback>
+    Variable<
+        T,
+        Shape2D<N, N>,
+        C,
+        L,
+        P,
+        <P as StaticAllocationPolicy<T, Shape2D<N, N>>>::Layout,
+        Contiguous,
+        <Pback as StaticAllocationPolicy<T, Shape2D<N, N>>>::Layout,
+        Pback,
+    >
+where
+    N: StaticDim + 'static,
+    C: 'static,
+    L: for<'a> Layout<'a, T> + 'static,
+    P: StaticAllocationPolicy<T, Shape2D<N, N>> + 'static,
+    <P as StaticAllocationPolicy<T, Shape2D<N, N>>>::Layout: for<'a> Layout<'a, T> + 'static,
+    Pback: StaticAllocationPolicy<T, Shape2D<N, N>> + 'static,
+    <Pback as StaticAllocationPolicy<T, Shape2D<N, N>>>::Layout:
+        for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T> + 'static,
+{
+    /// Computes the inverse of the matrix.
+    ///
+    /// Returns an error if the value of `self` is singular.
+    #[allow(clippy::type_complexity)]
+    pub fn operation<Cback, Lback>(
+        self,
+    ) -> Result<
+        Variable<
+            T,
+            Shape2D<N, N>,
+            Contiguous,
+            <P as StaticAllocationPolicy<T, Shape2D<N, N>>>::Layout,
+            P,
+            <P as StaticAllocationPolicy<T, Shape2D<N, N>>>::Layout,
+            Cback,
+            Lback,
+            Pback,
+        >,
+        LinalgError,
+    >
+    where
+        Cback: 'static,
+        Lback: for<'a> Layout<'a, T> + 'static,
+    {
+        let (value, grad) = {
+            let self_ref = self.borrow();
+            (
+                self_ref.value.placeholder()?,
+                if self_ref.grad.is_some() {
+                    Some(Tensor::default())
+                } else {
+                    None
+                },
+            )
+        };
+        let inverse = value.as_contiguous();
+
+        Ok(Variable(Rc::new(RefCell::new(BackpropNode {
+            value,
+            grad,
+            backward_op_name: "inverse_back",
+            backward_closure: Box::new(|| ()),
+        }))))
+    }
+}
+
+#[expand_operations(
+    det<T=f64>,
+    det<T=f32>,
+)]
+#[define_closure(
+    det: move |grad| {
+        let mut self_grad: Matrix<T, N, N, Pback> = Tensor::default();
+        {
+            let self_ref = self.borrow();
+            // The adjugate of the transpose is the transpose of the adjugate.
+            let adjugate = self_ref
+                .value
+                .transpose()
+                .adjugate()
+                .expect("Singular value decomposition should converge.");
+            self_grad.copy_from_slice(&adjugate);
+        }
+        self_grad.scal_mul_(grad[0]);
+
+        self.backward(self_grad);
+    }
+)]
+impl<T, N, C, L, P, Pback>
+    Variable<
+        T,
+        Shape2D<N, N>,
+        C,
+        L,
+        P,
+        <P as StaticAllocationPolicy<T, Shape2D<N, N>>>::Layout,
+        Contiguous,
+        <Pback as StaticAllocationPolicy<T, Shape2D<N, N>>>::Layout,
+        Pback,
+    >
+where
+    N: StaticDim + 'static,
+    Shape2D<N, N>: MinDim,
+    C: TransposePolicy + 'static,
+    L: for<'a> Layout<'a, T> + 'static,
+    P: StaticAllocationPolicy<T, Shape2D<N, N>> + 'static,
+    <P as StaticAllocationPolicy<T, Shape2D<N, N>>>::Layout: for<'a> Layout<'a, T> + 'static,
+    Pback: StaticAllocationPolicy<T, Shape2D<N, N>> + 'static,
+    <Pback as StaticAllocationPolicy<T, Shape2D<N, N>>>::Layout:
+        for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T> + 'static,
+{
+    /// Computes the determinant of the matrix as a tensor with a single element.
+    #[allow(clippy::type_complexity)]
+    pub fn operation<Cback, Lback>(
+        self,
+    ) -> Variable<
+        T,
+        Shape1D<U1>,
+        Contiguous,
+        <P as StaticAllocationPolicy<T, Shape1D<U1>>>::Layout,
+        P,
+        <P as StaticAllocationPolicy<T, Shape1D<U1>>>::Layout,
+        Cback,
+        Lback,
+        Pback,
+    >
+    where
+        P: StaticAllocationPolicy<T, Shape1D<U1>>,
+        <P as StaticAllocationPolicy<T, Shape1D<U1>>>::Layout: for<'a> Layout<'a, T> + 'static,
+        Pback: StaticAllocationPolicy<T, Shape1D<U1>>,
+        Cback: 'static,
+        Lback: for<'a> Layout<'a, T> + 'static,
+    {
+        let (det, grad) = {
+            let self_ref = self.borrow();
+            (
+                self_ref.value.placeholder(),
+                if self_ref.grad.is_some() {
+                    Some(Tensor::default())
+                } else {
+                    None
+                },
+            )
+        };
+        let mut value: Tensor<
+            T,
+            Shape1D<U1>,
+            Contiguous,
+            <P as StaticAllocationPolicy<T, Shape1D<U1>>>::Layout,
+            P,
+        > = Tensor::default();
+        value[0] = det;
+
+        Variable(Rc::new(RefCell::new(BackpropNode {
+            value,
+            grad,
+            backward_op_name: "det_back",
+            backward_closure: Box::new(|| ()),
+        })))
+    }
+}
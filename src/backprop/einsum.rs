@@ -0,0 +1,93 @@
+//! `einsum` contains the support of the `einsum!` procedural macro
+//! at the variable level, invoked as `einsum!(var "bij,bjk->bik", x, y)`.
+//!
+//! The gradient of an Einstein summation with respect to an operand is
+//! itself an Einstein summation of the gradient of the result and of the
+//! other operands, whose output subscripts are those of the operand.
+//! The macro generates these summations and the methods of this module
+//! compute them and build the computation graph.
+
+use super::variable::{BackpropNode, Variable};
+use crate::tensor::allocation_policy::StaticAllocationPolicy;
+use crate::tensor::einsum::{contract, contract_dot, Contraction, Operand};
+use crate::tensor::prelude::*;
+use crate::tensor::transpose_policy::Contiguous;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+impl<T, S, C, L, P, Cback, Lback, Pback> Variable<T, S, C, L, P, P::Layout, Cback, Lback, Pback>
+where
+    T: Copy,
+    L: for<'a> Layout<'a, T>,
+    P: StaticAllocationPolicy<T, S>,
+{
+    /// Copies the value of `self` as an einsum operand.
+    #[doc(hidden)]
+    pub fn einsum_operand(&self) -> Operand<T> {
+        Operand::new(&self.borrow().value)
+    }
+
+    /// Returns true if `self` retains its gradient.
+    #[doc(hidden)]
+    pub fn einsum_requires_grad(&self) -> bool {
+        self.borrow().grad.is_some()
+    }
+
+    /// Creates the variable resulting from an einsum.
+    #[doc(hidden)]
+    #[allow(clippy::type_complexity)]
+    pub fn einsum_result(
+        value: Tensor<T, S, C, L, P>,
+        require_grad: bool,
+        backward_closure: Box<dyn Fn(Tensor<T, S, Cback, Lback, Pback>)>,
+    ) -> Self
+    where
+        S: StaticShape,
+    {
+        Variable(Rc::new(RefCell::new(BackpropNode {
+            value,
+            grad: if require_grad {
+                Some(Tensor::default())
+            } else {
+                None
+            },
+            backward_op_name: "einsum_back",
+            backward_closure,
+        })))
+    }
+}
+
+impl<T, S, C, L, P, Pback> Variable<T, S, C, L, P, P::Layout, Contiguous, Pback::Layout, Pback>
+where
+    T: Contraction,
+    S: StaticShape,
+    L: for<'a> Layout<'a, T>,
+    P: StaticAllocationPolicy<T, S>,
+    Pback: StaticAllocationPolicy<T, S>,
+    Pback::Layout: for<'a> Layout<'a, T>,
+{
+    /// Computes the gradient of `self`, whose subscripts are `self_labels`,
+    /// as the summation of `operands` and backpropagates it.
+    #[doc(hidden)]
+    pub fn einsum_backward(
+        &self,
+        labels: &[&[u8]],
+        operands: &[&Operand<T>],
+        self_labels: &[u8],
+        dot: bool,
+    ) {
+        let mut grad: Tensor<T, S, Contiguous, Pback::Layout, Pback> = Tensor::default();
+        if dot {
+            contract_dot(
+                [labels[0], labels[1]],
+                [operands[0], operands[1]],
+                self_labels,
+                &mut grad,
+            );
+        } else {
+            contract(labels, operands, self_labels, &S::to_vec(), &mut grad);
+        }
+
+        self.backward(grad);
+    }
+}
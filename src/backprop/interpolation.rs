@@ -0,0 +1,154 @@
+//! `interpolation` contains resizing operations at the variable level
+//! that rely on the implementation of the `tensor` module.
+//!
+//! The gradient of an interpolation distributes each element of the
+//! gradient of the result to the elements of `self` it was interpolated
+//! from, with the same weights.
+
+use super::variable::{BackpropNode, Variable};
+use crate::tensor::allocation_policy::StaticAllocationPolicy;
+use crate::tensor::prelude::*;
+use crate::tensor::transpose_policy::Contiguous;
+use melange_macros::{define_closure, expand_operations};
+use num_traits::Float;
+use std::cell::RefCell;
+use std::ops::AddAssign;
+use std::rc::Rc;
+
+#[expand_operations(
+    interpolate<T: Float + Send + Sync + AddAssign + 'static>,
+)]
+#[define_closure(
+    interpolate: move |grad| {
+        let mut self_grad: Tensor<T, S, Contiguous, Pback::Layout, Pback> = Tensor::default();
+        grad.interpolate_adjoint(S::to_vec(), mode, align_corners, &mut self_grad);
+
+        self.backward(self_grad);
+    }
+)]
+impl<T, S, C, L, P, Pback> Variable<T, S, C, L, P, P::Layout, Contiguous, Pback::Layout, Pback>
+where
+    S: StaticShape + 'static,
+    C: 'static,
+    L: for<'a> Layout<'a, T> + 'static,
+    P: StaticAllocationPolicy<T, S> + 'static,
+    P::Layout: for<'a> Layout<'a, T> + 'static,
+    Pback: StaticAllocationPolicy<T, S> + 'static,
+    Pback::Layout: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T> + 'static,
+{
+    /// Resizes the spatial axes of `self` to those of `Z` with the given `mode`.
+    #[allow(clippy::type_complexity)]
+    pub fn operation<Z, Cback, Lback>(
+        self,
+        mode: Interpolation,
+        align_corners: bool,
+    ) -> Variable<
+        T,
+        Z,
+        Contiguous,
+        <P as StaticAllocationPolicy<T, Z>>::Layout,
+        P,
+        <P as StaticAllocationPolicy<T, Z>>::Layout,
+        Cback,
+        Lback,
+        Pback,
+    >
+    where
+        S: Resize<Z>,
+        <S as Resize<Z>>::Output: TRUE,
+        Z: StaticShape,
+        P: StaticAllocationPolicy<T, Z>,
+        <P as StaticAllocationPolicy<T, Z>>::Layout: for<'a> Layout<'a, T>,
+        Cback: 'static,
+        Lback: for<'a> Layout<'a, T> + 'static,
+    {
+        let (value, grad) = {
+            let self_ref = self.borrow();
+            (
+                self_ref.value.placeholder::<Z>(mode, align_corners),
+                if self_ref.grad.is_some() {
+                    Some(Tensor::default())
+                } else {
+                    None
+                },
+            )
+        };
+
+        Variable(Rc::new(RefCell::new(BackpropNode {
+            value,
+            grad,
+            backward_op_name: "interpolate_back",
+            backward_closure: Box::new(|| ()),
+        })))
+    }
+}
+
+#[expand_operations(
+    upsample<T: Float + Send + Sync + AddAssign + 'static>,
+)]
+#[define_closure(
+    upsample: move |grad| {
+        let mut self_grad: Tensor<T, S, Contiguous, Pback::Layout, Pback> = Tensor::default();
+        grad.interpolate_adjoint(S::to_vec(), mode, align_corners, &mut self_grad);
+
+        self.backward(self_grad);
+    }
+)]
+impl<T, S, C, L, P, Pback> Variable<T, S, C, L, P, P::Layout, Contiguous, Pback::Layout, Pback>
+where
+    S: StaticShape + 'static,
+    C: 'static,
+    L: for<'a> Layout<'a, T> + 'static,
+    P: StaticAllocationPolicy<T, S> + 'static,
+    P::Layout: for<'a> Layout<'a, T> + 'static,
+    Pback: StaticAllocationPolicy<T, S> + 'static,
+    Pback::Layout: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T> + 'static,
+{
+    /// Multiplies the spatial dimensions of `self` by the
+    /// scale factors `Factors` with the given `mode`.
+    #[allow(clippy::type_complexity)]
+    pub fn operation<Factors, Cback, Lback>(
+        self,
+        mode: Interpolation,
+        align_corners: bool,
+    ) -> Variable<
+        T,
+        <S as Upsample<Factors>>::Output,
+        Contiguous,
+        <P as StaticAllocationPolicy<T, <S as Upsample<Factors>>::Output>>::Layout,
+        P,
+        <P as StaticAllocationPolicy<T, <S as Upsample<Factors>>::Output>>::Layout,
+        Cback,
+        Lback,
+        Pback,
+    >
+    where
+        S: Upsample<Factors>,
+        <S as Upsample<Factors>>::Output: StaticShape,
+        P: StaticAllocationPolicy<T, <S as Upsample<Factors>>::Output>,
+        <P as StaticAllocationPolicy<T, <S as Upsample<Factors>>::Output>>::Layout:
+            for<'a> Layout<'a, T>,
+        Factors: 'static,
+        Cback: 'static,
+        Lback: for<'a> Layout<'a, T> + 'static,
+    {
+        let (value, grad) = {
+            let self_ref = self.borrow();
+            (
+                self_ref.value.placeholder::<Factors>(mode, align_corners),
+                if self_ref.grad.is_some() {
+                    Some(Tensor::default())
+                } else {
+                    None
+                },
+            )
+        };
+
+        Variable(Rc::new(RefCell::new(BackpropNode {
+            value,
+            grad,
+            backward_op_name: "upsample_back",
+            backward_closure: Box::new(|| ()),
+        })))
+    }
+}
@@ -27,7 +27,14 @@
 //! backpropagated gradient. Those parameters cannot be inferred by the compiler
 //! unless the computation graph is complete and a backpropagation is performed.
 
+pub mod clip;
 pub mod core_ops;
+pub mod einsum;
+#[cfg(feature = "blas")]
+pub mod decomposition;
+pub mod interpolation;
+pub mod pad;
 pub mod prelude;
 pub mod reduction;
+pub mod scan;
 pub mod variable;
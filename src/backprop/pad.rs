@@ -0,0 +1,85 @@
+//! `pad` contains padding operations at the variable level
+//! that rely on the implementation of the `tensor` module.
+//!
+//! The gradient of `pad` is the gradient of the result cropped back
+//! to the shape of `self`. With non-constant modes, the gradients of
+//! padded elements are also accumulated to the elements they were read from.
+
+use super::variable::{BackpropNode, Variable};
+use crate::tensor::allocation_policy::StaticAllocationPolicy;
+use crate::tensor::prelude::*;
+use crate::tensor::transpose_policy::Contiguous;
+use melange_macros::{define_closure, expand_operations};
+use std::cell::RefCell;
+use std::ops::*;
+use std::rc::Rc;
+
+#[expand_operations(
+    pad<T: Send + Sync + Copy + AddAssign + 'static>,
+)]
+#[define_closure(
+    pad: move |grad| {
+        let mut self_grad: Tensor<T, S, Contiguous, Pback::Layout, Pback> = Tensor::default();
+        grad.pad_adjoint(&Before::amounts(), S::to_vec(), mode, &mut self_grad);
+
+        self.backward(self_grad);
+    }
+)]
+impl<T, S, C, L, P, Pback> Variable<T, S, C, L, P, P::Layout, Contiguous, Pback::Layout, Pback>
+where
+    S: StaticShape + 'static,
+    C: 'static,
+    L: for<'a> Layout<'a, T> + 'static,
+    P: StaticAllocationPolicy<T, S> + 'static,
+    P::Layout: for<'a> Layout<'a, T> + 'static,
+    Pback: StaticAllocationPolicy<T, S> + 'static,
+    Pback::Layout: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T> + 'static,
+{
+    /// Extends each axis of `self` with the dimensions of `Before`
+    /// and `After` on the same axis, filled according to `mode`.
+    #[allow(clippy::type_complexity)]
+    pub fn operation<Before, After, Cback, Lback>(
+        self,
+        mode: PadMode<T>,
+    ) -> Variable<
+        T,
+        <S as Pad<Before, After>>::Output,
+        Contiguous,
+        <P as StaticAllocationPolicy<T, <S as Pad<Before, After>>::Output>>::Layout,
+        P,
+        <P as StaticAllocationPolicy<T, <S as Pad<Before, After>>::Output>>::Layout,
+        Cback,
+        Lback,
+        Pback,
+    >
+    where
+        S: Pad<Before, After>,
+        <S as Pad<Before, After>>::Output: StaticShape,
+        P: StaticAllocationPolicy<T, <S as Pad<Before, After>>::Output>,
+        <P as StaticAllocationPolicy<T, <S as Pad<Before, After>>::Output>>::Layout:
+            for<'a> Layout<'a, T>,
+        Before: Padding + 'static,
+        After: 'static,
+        Cback: 'static,
+        Lback: for<'a> Layout<'a, T> + 'static,
+    {
+        let (value, grad) = {
+            let self_ref = self.borrow();
+            (
+                self_ref.value.placeholder::<Before, After>(mode),
+                if self_ref.grad.is_some() {
+                    Some(Tensor::default())
+                } else {
+                    None
+                },
+            )
+        };
+
+        Variable(Rc::new(RefCell::new(BackpropNode {
+            value,
+            grad,
+            backward_op_name: "pad_back",
+            backward_closure: Box::new(|| ()),
+        })))
+    }
+}
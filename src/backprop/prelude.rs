@@ -1 +1,2 @@
+pub use super::clip::{clip_grad_norm, clip_grad_value, Gradient};
 pub use super::variable::Variable;
@@ -0,0 +1,191 @@
+//! `scan` contains cumulative operations at the variable level
+//! that rely on the implementation of the `tensor` module.
+//!
+//! The gradient of `cumsum` is the reversed cumulative sum of the
+//! gradient of the result, computed as `sum(G) - cumsum(G) + G`.
+//! The gradient of `cumprod` along a line `x` with gradient `g` is
+//! `prod(x[..j]) * r[j]` where `r[j] = g[j] + x[j + 1] * r[j + 1]`,
+//! which avoids dividing by `self` so that zeros are supported.
+
+use super::variable::{BackpropNode, Variable};
+use crate::tensor::allocation_policy::StaticAllocationPolicy;
+use crate::tensor::prelude::*;
+use crate::tensor::transpose_policy::Contiguous;
+use melange_macros::{define_closure, expand_operations};
+use rayon::prelude::*;
+use std::cell::RefCell;
+use std::ops::*;
+use std::rc::Rc;
+use typenum::Unsigned;
+
+#[expand_operations(
+    cumsum<T: Send + Sync + Copy + Add<Output = T> + Sub<Output = T> + AddAssign + 'static>,
+)]
+#[define_closure(
+    cumsum: move |grad| {
+        let grad = grad.as_contiguous();
+        let mut self_grad = grad.sum::<Ax>().broadcast().sub(&grad.cumsum::<Ax>());
+        self_grad.add_(&grad);
+
+        self.backward(self_grad);
+    }
+)]
+impl<T, S, C, L, P, Pback> Variable<T, S, C, L, P, P::Layout, Contiguous, Pback::Layout, Pback>
+where
+    S: StaticShape + 'static,
+    C: 'static,
+    L: for<'a> Layout<'a, T> + 'static,
+    P: StaticAllocationPolicy<T, S> + 'static,
+    P::Layout: for<'a> Layout<'a, T> + 'static,
+    Pback: StaticAllocationPolicy<T, S> + 'static,
+    Pback::Layout: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T> + 'static,
+{
+    /// Computes the cumulative sum of `self` along the axis `Ax`.
+    #[allow(clippy::type_complexity)]
+    pub fn operation<Ax, Cback, Lback>(
+        self,
+    ) -> Variable<
+        T,
+        S,
+        Contiguous,
+        <P as StaticAllocationPolicy<T, S>>::Layout,
+        P,
+        <P as StaticAllocationPolicy<T, S>>::Layout,
+        Cback,
+        Lback,
+        Pback,
+    >
+    where
+        S: Reduction<Ax> + ReductionOptChunckSize<T, Ax> + At<Ax>,
+        <S as Reduction<Ax>>::Output: StaticShape + Broadcast<S>,
+        <<S as Reduction<Ax>>::Output as Broadcast<S>>::Output: TRUE,
+        Pback: StaticAllocationPolicy<T, <S as Reduction<Ax>>::Output>,
+        <Pback as StaticAllocationPolicy<T, <S as Reduction<Ax>>::Output>>::Layout:
+            for<'a> Layout<'a, T>,
+        Ax: 'static,
+        Cback: 'static,
+        Lback: for<'a> Layout<'a, T> + 'static,
+    {
+        let (value, grad) = {
+            let self_ref = self.borrow();
+            (
+                self_ref.value.placeholder::<Ax>(),
+                if self_ref.grad.is_some() {
+                    Some(Tensor::default())
+                } else {
+                    None
+                },
+            )
+        };
+
+        Variable(Rc::new(RefCell::new(BackpropNode {
+            value,
+            grad,
+            backward_op_name: "cumsum_back",
+            backward_closure: Box::new(|| ()),
+        })))
+    }
+}
+
+#[expand_operations(
+    cumprod<T: Send + Sync + Copy + Add<Output = T> + Mul<Output = T> + AddAssign + 'static>,
+)]
+#[define_closure(
+    cumprod: move |grad| {
+        let grad = grad.as_contiguous();
+        let chunk_size = <<S as ReductionOptChunckSize<T, Ax>>::Output as Unsigned>::USIZE;
+        let axis_len = <<S as At<Ax>>::Output as Unsigned>::USIZE;
+        let mut self_grad: Tensor<T, S, Contiguous, Pback::Layout, Pback> = Tensor::default();
+
+        {
+            let self_ref = self.borrow();
+            let value = self_ref.value.as_contiguous();
+            cumprod_backward(&grad, &value, &mut self_grad, chunk_size, axis_len);
+        }
+
+        self.backward(self_grad);
+    }
+)]
+impl<T, S, C, L, P, Pback> Variable<T, S, C, L, P, P::Layout, Contiguous, Pback::Layout, Pback>
+where
+    S: StaticShape + 'static,
+    C: 'static,
+    L: for<'a> Layout<'a, T> + 'static,
+    P: StaticAllocationPolicy<T, S> + 'static,
+    P::Layout: for<'a> Layout<'a, T> + 'static,
+    Pback: StaticAllocationPolicy<T, S> + 'static,
+    Pback::Layout: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T> + 'static,
+{
+    /// Computes the cumulative product of `self` along the axis `Ax`.
+    #[allow(clippy::type_complexity)]
+    pub fn operation<Ax, Cback, Lback>(
+        self,
+    ) -> Variable<
+        T,
+        S,
+        Contiguous,
+        <P as StaticAllocationPolicy<T, S>>::Layout,
+        P,
+        <P as StaticAllocationPolicy<T, S>>::Layout,
+        Cback,
+        Lback,
+        Pback,
+    >
+    where
+        S: ReductionOptChunckSize<T, Ax> + At<Ax>,
+        Ax: 'static,
+        Cback: 'static,
+        Lback: for<'a> Layout<'a, T> + 'static,
+    {
+        let (value, grad) = {
+            let self_ref = self.borrow();
+            (
+                self_ref.value.placeholder::<Ax>(),
+                if self_ref.grad.is_some() {
+                    Some(Tensor::default())
+                } else {
+                    None
+                },
+            )
+        };
+
+        Variable(Rc::new(RefCell::new(BackpropNode {
+            value,
+            grad,
+            backward_op_name: "cumprod_back",
+            backward_closure: Box::new(|| ()),
+        })))
+    }
+}
+
+/// Computes the gradient of `cumprod` in `out` from the contiguous gradient
+/// of the result `grad` and the contiguous input `x`.
+fn cumprod_backward<T>(grad: &[T], x: &[T], out: &mut [T], chunk_size: usize, axis_len: usize)
+where
+    T: Send + Sync + Copy + Add<Output = T> + Mul<Output = T>,
+{
+    if chunk_size == 0 || axis_len == 0 {
+        return;
+    }
+    let block_size = chunk_size * axis_len;
+    out.par_chunks_mut(block_size)
+        .zip(grad.par_chunks(block_size))
+        .zip(x.par_chunks(block_size))
+        .for_each(|((out, g), x)| {
+            for c in 0..chunk_size {
+                let at = |k: usize| k * chunk_size + c;
+                let mut r = g[at(axis_len - 1)];
+                out[at(axis_len - 1)] = r;
+                for k in (0..axis_len - 1).rev() {
+                    r = g[at(k)] + x[at(k + 1)] * r;
+                    out[at(k)] = r;
+                }
+
+                let mut prefix = x[at(0)];
+                for k in 1..axis_len {
+                    out[at(k)] = prefix * out[at(k)];
+                    prefix = prefix * x[at(k)];
+                }
+            }
+        });
+}
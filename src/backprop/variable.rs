@@ -1,8 +1,10 @@
+use crate::io::state_dict::{StateDict, Stateful};
 use crate::tensor::allocation_policy::{DynamicAllocationPolicy, StaticAllocationPolicy};
 use crate::tensor::prelude::*;
 use crate::tensor::transpose_policy::Contiguous;
 use std::cell::RefCell;
 use std::fmt;
+use std::io;
 use std::ops::{AddAssign, Deref};
 use std::rc::Rc;
 
@@ -34,6 +36,25 @@ where
     }
 }
 
+impl<T, S, C, L, P, Lgrad, Cback, Lback, Pback> fmt::Display
+    for BackpropNode<T, S, C, L, P, Lgrad, Cback, Lback, Pback>
+where
+    T: fmt::Display,
+    L: for<'a> Layout<'a, T> + Deref<Target = [T]>,
+    Lgrad: for<'a> Layout<'a, T> + Deref<Target = [T]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Variable(backward_op: {})", self.backward_op_name)?;
+        write!(f, "value: ")?;
+        self.value.fmt_with_indent(f, "value: ".len())?;
+        write!(f, "\ngrad: ")?;
+        match &self.grad {
+            Some(grad) => grad.fmt_with_indent(f, "grad: ".len()),
+            None => write!(f, "None"),
+        }
+    }
+}
+
 /// Core type of `backprop` module that represents a node in the computation
 /// graph. It contains a combination of `Rc` and `RefCell` to allow
 /// mutable reference counting of the actual `BackpropNode`s.
@@ -66,6 +87,19 @@ impl<T, S, C, L, P, Lgrad, Cback, Lback, Pback> Deref
     }
 }
 
+impl<T, S, C, L, P, Lgrad, Cback, Lback, Pback> fmt::Display
+    for Variable<T, S, C, L, P, Lgrad, Cback, Lback, Pback>
+where
+    T: fmt::Display,
+    L: for<'a> Layout<'a, T> + Deref<Target = [T]>,
+    Lgrad: for<'a> Layout<'a, T> + Deref<Target = [T]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let node = self.borrow();
+        fmt::Display::fmt(&*node, f)
+    }
+}
+
 impl<T, S, C, L, P, Lgrad, Cback, Lback, Pback> Variable<T, S, C, L, P, Lgrad, Cback, Lback, Pback>
 where
     Tensor<T, S, Contiguous, Lgrad, P>: Clone,
@@ -168,3 +202,18 @@ impl<T, S, C, L, P, Lgrad, Cback, Lback, Pback> Clone
         Variable(Rc::clone(&self.0))
     }
 }
+
+impl<T, S, C, L, P, Lgrad, Cback, Lback, Pback> Stateful
+    for Variable<T, S, C, L, P, Lgrad, Cback, Lback, Pback>
+where
+    Tensor<T, S, C, L, P>: Stateful,
+{
+    /// Saves the value of the variable, gradients are not part of the state.
+    fn save_state(&self, prefix: &str, state_dict: &mut StateDict) {
+        self.borrow().value.save_state(prefix, state_dict);
+    }
+
+    fn load_state(&mut self, prefix: &str, state_dict: &StateDict) -> io::Result<()> {
+        self.borrow_mut().value.load_state(prefix, state_dict)
+    }
+}
@@ -0,0 +1,139 @@
+//! `checkpoint` defines `Checkpoint` that gathers everything needed
+//! to resume a training run: the state of the model and of the optimizer,
+//! the epoch and step counters and the state of the random number generator.
+//!
+//! Checkpoints are stored in a single safetensors file. Model and optimizer
+//! tensors are respectively prefixed by `model.` and `optimizer.`, the random
+//! number generator state is stored as an opaque `U8` tensor and counters
+//! are stored in the metadata along with a format version number.
+
+use super::npy::invalid_data;
+use super::safetensors::{Entry, SafeTensors, SafeTensorsWriter};
+use super::state_dict::{join, StateDict};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+const FORMAT: &str = "melange_checkpoint";
+const RNG_STATE: &str = "rng_state";
+
+/// Training state that can be saved to and restored from a single file.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Checkpoint {
+    pub model: StateDict,
+    pub optimizer: StateDict,
+    pub epoch: u64,
+    pub step: u64,
+    /// Opaque serialized state of the random number generator.
+    pub rng_state: Vec<u8>,
+}
+
+fn metadata_value<'a>(tensors: &'a SafeTensors, key: &str) -> io::Result<&'a str> {
+    tensors
+        .metadata()
+        .get(key)
+        .map(String::as_str)
+        .ok_or_else(|| invalid_data(format!("Key `{}` is missing in checkpoint metadata.", key)))
+}
+
+fn metadata_u64(tensors: &SafeTensors, key: &str) -> io::Result<u64> {
+    metadata_value(tensors, key)?.parse().map_err(invalid_data)
+}
+
+impl Checkpoint {
+    /// Version of the checkpoint format written by this version of the crate.
+    pub const VERSION: u64 = 1;
+
+    /// Writes the checkpoint to `writer` and returns it.
+    pub fn write<W: Write>(&self, writer: W) -> io::Result<W> {
+        let mut writer = SafeTensorsWriter::new(writer);
+        writer.add_metadata("format", FORMAT);
+        writer.add_metadata("version", &Self::VERSION.to_string());
+        writer.add_metadata("epoch", &self.epoch.to_string());
+        writer.add_metadata("step", &self.step.to_string());
+
+        for (prefix, state_dict) in [("model", &self.model), ("optimizer", &self.optimizer)].iter()
+        {
+            for (name, entry) in state_dict.entries.iter() {
+                let mut entry = entry.clone();
+                entry.name = join(prefix, name);
+                writer.add_entry(entry);
+            }
+        }
+        writer.add_entry(Entry {
+            name: RNG_STATE.to_string(),
+            dtype: "U8".to_string(),
+            size: 1,
+            shape: vec![self.rng_state.len()],
+            data: self.rng_state.clone(),
+        });
+
+        writer.finish()
+    }
+
+    /// Reads a checkpoint from the content of a checkpoint file.
+    pub fn read(buffer: &[u8]) -> io::Result<Self> {
+        let tensors = SafeTensors::deserialize(buffer)?;
+        if metadata_value(&tensors, "format")? != FORMAT {
+            return Err(invalid_data("Not a melange checkpoint."));
+        }
+        let version = metadata_u64(&tensors, "version")?;
+        if version > Self::VERSION {
+            return Err(invalid_data(format!(
+                "Checkpoint format version {} is not supported, latest supported version is {}.",
+                version,
+                Self::VERSION
+            )));
+        }
+
+        let mut checkpoint = Checkpoint {
+            epoch: metadata_u64(&tensors, "epoch")?,
+            step: metadata_u64(&tensors, "step")?,
+            ..Self::default()
+        };
+        for name in tensors.names() {
+            let mut entry = tensors.entry(name)?;
+            if name == RNG_STATE {
+                checkpoint.rng_state = entry.data;
+            } else if let Some(stripped) = name.strip_prefix("model.") {
+                entry.name = stripped.to_string();
+                checkpoint.model.entries.insert(entry.name.clone(), entry);
+            } else if let Some(stripped) = name.strip_prefix("optimizer.") {
+                entry.name = stripped.to_string();
+                checkpoint
+                    .optimizer
+                    .entries
+                    .insert(entry.name.clone(), entry);
+            } else {
+                return Err(invalid_data(format!(
+                    "Unexpected tensor `{}` in checkpoint.",
+                    name
+                )));
+            }
+        }
+
+        Ok(checkpoint)
+    }
+
+    /// Saves the checkpoint to a file at `path`.
+    ///
+    /// The checkpoint is first written to a temporary file that then replaces
+    /// the file at `path` so that a crash while saving does not corrupt
+    /// a previous checkpoint.
+    pub fn save<Q: AsRef<Path>>(&self, path: Q) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+
+        let mut writer = self.write(BufWriter::new(File::create(&tmp_path)?))?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+
+        std::fs::rename(&tmp_path, path)
+    }
+
+    /// Loads a checkpoint from the file at `path`.
+    pub fn load<Q: AsRef<Path>>(path: Q) -> io::Result<Self> {
+        Self::read(&std::fs::read(path)?)
+    }
+}
@@ -0,0 +1,81 @@
+//! `io` contains tools to save tensors to and load tensors from
+//! files in formats that are shared with other numerical libraries.
+//!
+//! It currently supports NumPy's `.npy` format for single tensors,
+//! `.npz` archives that store multiple named tensors and the
+//! safetensors format that allows zero-copy loading of named tensors.
+//! On top of these formats, `state_dict` collects the named tensors that
+//! make up the state of models and optimizers and `checkpoint` saves
+//! everything needed to resume training.
+//!
+//! Loading functions come in two flavors, following the conventions
+//! of the rest of the crate: static loaders that check the shape
+//! found in the file against the type-level shape of the tensor and
+//! dynamic loaders (suffixed by `_dynamic`) that allocate a tensor
+//! with the shape read from the file.
+//!
+//! Contrary to mathematical operations that panic on misuse, I/O
+//! operations can fail for reasons that are out of the control
+//! of the programmer. They therefore return `std::io::Result` and
+//! report malformed or incompatible files as `InvalidData` errors.
+
+pub mod checkpoint;
+pub mod npy;
+pub mod npz;
+pub mod prelude;
+pub mod safetensors;
+pub mod state_dict;
+
+/// Scalar types that have a fixed-size binary representation and can
+/// be (de)serialized by the `io` module.
+///
+/// It is implemented for all numeric primitive types.
+pub trait Element: Sized + Copy + Default + Send + Sync + 'static {
+    /// Kind of the type following NumPy conventions:
+    /// `f` for floating point numbers, `i` for signed integers
+    /// and `u` for unsigned integers.
+    const KIND: char;
+    /// Size of the type in bytes.
+    const SIZE: usize;
+
+    /// Appends the little endian representation of `self` to `buffer`.
+    fn extend_le(self, buffer: &mut Vec<u8>);
+    /// Reads a value from exactly `SIZE` little endian bytes.
+    fn from_le(bytes: &[u8]) -> Self;
+    /// Reads a value from exactly `SIZE` big endian bytes.
+    fn from_be(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_element {
+    ($kind:expr => $($t:ty),*) => {
+        $(
+            impl Element for $t {
+                const KIND: char = $kind;
+                const SIZE: usize = std::mem::size_of::<$t>();
+
+                #[inline]
+                fn extend_le(self, buffer: &mut Vec<u8>) {
+                    buffer.extend_from_slice(&self.to_le_bytes());
+                }
+
+                #[inline]
+                fn from_le(bytes: &[u8]) -> Self {
+                    let mut array = [0; std::mem::size_of::<$t>()];
+                    array.copy_from_slice(bytes);
+                    <$t>::from_le_bytes(array)
+                }
+
+                #[inline]
+                fn from_be(bytes: &[u8]) -> Self {
+                    let mut array = [0; std::mem::size_of::<$t>()];
+                    array.copy_from_slice(bytes);
+                    <$t>::from_be_bytes(array)
+                }
+            }
+        )*
+    };
+}
+
+impl_element!('f' => f64, f32);
+impl_element!('i' => i128, i64, i32, i16, i8, isize);
+impl_element!('u' => u128, u64, u32, u16, u8, usize);
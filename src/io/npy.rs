@@ -0,0 +1,385 @@
+//! `npy` implements NumPy's `.npy` binary format for single tensors.
+//!
+//! A `.npy` file starts with a magic string and a version number followed
+//! by a header, a Python dictionary literal that describes the data type
+//! (`descr`), the memory order (`fortran_order`) and the shape (`shape`)
+//! of the array. Raw data follows the header.
+//!
+//! Tensors are always written in C order (row-major), whatever their
+//! layout or transpose policy, by iterating over the logical layout.
+//! Both C-order and Fortran-order files can be read, Fortran-order data
+//! being reordered when loaded.
+
+use super::Element;
+use crate::tensor::layout::{Alloc, Layout, LayoutMut};
+use crate::tensor::shape::{Shape, StaticShape};
+use crate::tensor::tensor::Tensor;
+use crate::tensor::transpose_policy::Contiguous;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+const ALIGNMENT: usize = 64;
+
+/// Decoded content of a `.npy` header.
+#[derive(Debug, PartialEq, Clone)]
+struct Header {
+    descr: String,
+    fortran_order: bool,
+    shape: Vec<usize>,
+}
+
+pub(crate) fn invalid_data<E>(error: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Returns the NumPy type descriptor of `T` such as `<f8` for `f64`.
+fn descr<T: Element>() -> String {
+    let byte_order = if T::SIZE == 1 { '|' } else { '<' };
+    format!("{}{}{}", byte_order, T::KIND, T::SIZE)
+}
+
+impl Header {
+    fn to_bytes(&self) -> Vec<u8> {
+        let shape = match self.shape.len() {
+            1 => format!("({},)", self.shape[0]),
+            _ => format!(
+                "({})",
+                self.shape
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        let mut dict = format!(
+            "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}",
+            self.descr,
+            if self.fortran_order { "True" } else { "False" },
+            shape,
+        );
+
+        // Header is padded with spaces and terminated by a new line
+        // so that data starts on an aligned offset.
+        let (version, prefix_len) = if dict.len() + ALIGNMENT < u16::MAX as usize {
+            (1, MAGIC.len() + 4)
+        } else {
+            (2, MAGIC.len() + 6)
+        };
+        let padding = ALIGNMENT - (prefix_len + dict.len() + 1) % ALIGNMENT;
+        dict.push_str(&" ".repeat(padding % ALIGNMENT));
+        dict.push('\n');
+
+        let mut bytes = Vec::with_capacity(prefix_len + dict.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(version);
+        bytes.push(0);
+        if version == 1 {
+            bytes.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+        } else {
+            bytes.extend_from_slice(&(dict.len() as u32).to_le_bytes());
+        }
+        bytes.extend_from_slice(dict.as_bytes());
+
+        bytes
+    }
+
+    fn read<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut prefix = [0; 8];
+        reader.read_exact(&mut prefix)?;
+        if &prefix[..6] != MAGIC {
+            return Err(invalid_data("Not a `.npy` file, magic string is missing."));
+        }
+
+        let header_len = match prefix[6] {
+            1 => {
+                let mut len = [0; 2];
+                reader.read_exact(&mut len)?;
+                u16::from_le_bytes(len) as usize
+            }
+            2 | 3 => {
+                let mut len = [0; 4];
+                reader.read_exact(&mut len)?;
+                u32::from_le_bytes(len) as usize
+            }
+            version => {
+                return Err(invalid_data(format!(
+                    "Unsupported `.npy` format version {}.",
+                    version
+                )))
+            }
+        };
+
+        let mut header = vec![0; header_len];
+        reader.read_exact(&mut header)?;
+        let header = String::from_utf8(header).map_err(invalid_data)?;
+
+        Self::parse(&header)
+    }
+
+    fn parse(header: &str) -> io::Result<Self> {
+        let descr = {
+            let value = dict_value(header, "descr")?;
+            let value = value.trim_start_matches(&['\'', '"'][..]);
+            let end = value
+                .find(&['\'', '"'][..])
+                .ok_or_else(|| invalid_data("Malformed `descr` in `.npy` header."))?;
+            value[..end].to_string()
+        };
+
+        let fortran_order = {
+            let value = dict_value(header, "fortran_order")?;
+            if value.starts_with("True") {
+                true
+            } else if value.starts_with("False") {
+                false
+            } else {
+                return Err(invalid_data("Malformed `fortran_order` in `.npy` header."));
+            }
+        };
+
+        let shape = {
+            let value = dict_value(header, "shape")?;
+            let end = value
+                .find(')')
+                .filter(|_| value.starts_with('('))
+                .ok_or_else(|| invalid_data("Malformed `shape` in `.npy` header."))?;
+            value[1..end]
+                .split(',')
+                .map(|x| x.trim())
+                .filter(|x| !x.is_empty())
+                .map(|x| x.trim_end_matches('L').parse().map_err(invalid_data))
+                .collect::<io::Result<Vec<usize>>>()?
+        };
+        if num_elements(&shape).is_none() {
+            return Err(invalid_data(format!(
+                "Shape {:?} found in `.npy` header has too many elements.",
+                shape
+            )));
+        }
+
+        Ok(Header {
+            descr,
+            fortran_order,
+            shape,
+        })
+    }
+
+    /// Checks that the data described by the header fits in memory
+    /// before it is allocated.
+    fn check_size<T: Element>(&self) -> io::Result<()> {
+        num_elements(&self.shape)
+            .and_then(|x| x.checked_mul(T::SIZE))
+            .filter(|x| *x <= isize::MAX as usize)
+            .map(|_| ())
+            .ok_or_else(|| {
+                invalid_data(format!(
+                    "Data of shape {:?} found in `.npy` header does not fit in memory.",
+                    self.shape
+                ))
+            })
+    }
+
+    fn check_descr<T: Element>(&self) -> io::Result<()> {
+        let expected = descr::<T>();
+        let type_matches = self.descr.get(1..) == expected.get(1..);
+        let order_matches = match self.descr.chars().next() {
+            Some('<') | Some('>') | Some('=') => true,
+            Some('|') => T::SIZE == 1,
+            _ => false,
+        };
+
+        if type_matches && order_matches {
+            Ok(())
+        } else {
+            Err(invalid_data(format!(
+                "Data type `{}` found in `.npy` header is not compatible with the tensor's type `{}`.",
+                self.descr, expected
+            )))
+        }
+    }
+}
+
+/// Returns the number of elements of `shape` or `None` on overflow.
+pub(crate) fn num_elements(shape: &[usize]) -> Option<usize> {
+    shape.iter().try_fold(1usize, |acc, x| acc.checked_mul(*x))
+}
+
+/// Returns the trimmed content following `'key':` in the header.
+fn dict_value<'a>(header: &'a str, key: &str) -> io::Result<&'a str> {
+    let position = header
+        .find(&format!("'{}'", key))
+        .or_else(|| header.find(&format!("\"{}\"", key)))
+        .ok_or_else(|| invalid_data(format!("Key `{}` is missing in `.npy` header.", key)))?;
+    let value = &header[position + key.len() + 2..];
+    let value = value.trim_start();
+    if !value.starts_with(':') {
+        return Err(invalid_data(format!(
+            "Malformed key `{}` in `.npy` header.",
+            key
+        )));
+    }
+
+    Ok(value[1..].trim_start())
+}
+
+/// Reads the data that follows `header` and writes it in C order in `out`.
+fn read_data<T, R>(reader: &mut R, header: &Header, out: &mut [T]) -> io::Result<()>
+where
+    T: Element,
+    R: Read,
+{
+    let mut bytes = vec![0; out.len() * T::SIZE];
+    reader.read_exact(&mut bytes)?;
+    let big_endian = header.descr.starts_with('>');
+
+    let values = bytes.chunks_exact(T::SIZE).map(|x| {
+        if big_endian {
+            T::from_be(x)
+        } else {
+            T::from_le(x)
+        }
+    });
+
+    if header.fortran_order {
+        // The first axis varies fastest in Fortran order, the corresponding
+        // position in C order is computed from C strides.
+        let strides = crate::tensor::shape::intrinsic_strides_in_place(header.shape.clone());
+        let mut counter = vec![0; header.shape.len()];
+        for value in values {
+            let index: usize = counter.iter().zip(strides.iter()).map(|(x, y)| x * y).sum();
+            out[index] = value;
+
+            for (digit, bound) in counter.iter_mut().zip(header.shape.iter()) {
+                *digit += 1;
+                if *digit < *bound {
+                    break;
+                }
+                *digit = 0;
+            }
+        }
+    } else {
+        for (x, value) in out.iter_mut().zip(values) {
+            *x = value;
+        }
+    }
+
+    Ok(())
+}
+
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    T: Element,
+    L: for<'a> Layout<'a, T>,
+{
+    /// Writes the tensor in `.npy` format to `writer`.
+    ///
+    /// Data is written in C order following the logical layout
+    /// of the tensor which means that views are supported.
+    pub fn write_npy<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let header = Header {
+            descr: descr::<T>(),
+            fortran_order: false,
+            shape: self.shape(),
+        };
+        writer.write_all(&header.to_bytes())?;
+
+        let mut buffer = Vec::with_capacity(self.num_elements() * T::SIZE);
+        let chunk_size = self.opt_chunk_size();
+        for chunk in self.chunks(chunk_size) {
+            for x in chunk {
+                x.extend_le(&mut buffer);
+            }
+        }
+
+        writer.write_all(&buffer)
+    }
+
+    /// Saves the tensor in a `.npy` file at `path`.
+    pub fn save_npy<Q: AsRef<Path>>(&self, path: Q) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_npy(&mut writer)?;
+        writer.flush()
+    }
+}
+
+impl<T, S, L, P> Tensor<T, S, Contiguous, L, P>
+where
+    T: Element,
+    L: for<'a> LayoutMut<'a, T>,
+{
+    /// Reads a tensor in `.npy` format from `reader`.
+    ///
+    /// The shape found in the header must match the static type-level shape `S`.
+    pub fn read_npy<R: Read>(reader: &mut R) -> io::Result<Self>
+    where
+        S: StaticShape,
+        L: Default,
+    {
+        let header = Header::read(reader)?;
+        header.check_descr::<T>()?;
+        header.check_size::<T>()?;
+        if !S::runtime_compat(&header.shape) {
+            return Err(invalid_data(format!(
+                "Shape {:?} found in `.npy` header is not compatible with the tensor's shape {:?}.",
+                header.shape,
+                S::to_vec()
+            )));
+        }
+
+        let mut out = Self::default();
+        read_data(reader, &header, &mut out)?;
+
+        Ok(out)
+    }
+
+    /// Reads a tensor in `.npy` format from `reader` with the shape
+    /// found in the header.
+    ///
+    /// The shape must still be compatible with the type-level shape `S`,
+    /// which can contain `Dyn` dimensions.
+    pub fn read_npy_dynamic<R: Read>(reader: &mut R) -> io::Result<Self>
+    where
+        S: Shape,
+        L: Alloc,
+    {
+        let header = Header::read(reader)?;
+        header.check_descr::<T>()?;
+        header.check_size::<T>()?;
+        if !S::runtime_compat(&header.shape) {
+            return Err(invalid_data(format!(
+                "Shape {:?} found in `.npy` header is not compatible with the tensor's type-level shape.",
+                header.shape,
+            )));
+        }
+
+        let mut out = Self::alloc(header.shape.clone());
+        read_data(reader, &header, &mut out)?;
+
+        Ok(out)
+    }
+
+    /// Loads a tensor from the `.npy` file at `path`.
+    ///
+    /// The shape found in the file must match the static type-level shape `S`.
+    pub fn load_npy<Q: AsRef<Path>>(path: Q) -> io::Result<Self>
+    where
+        S: StaticShape,
+        L: Default,
+    {
+        Self::read_npy(&mut BufReader::new(File::open(path)?))
+    }
+
+    /// Loads a tensor from the `.npy` file at `path` with the shape found in the file.
+    pub fn load_npy_dynamic<Q: AsRef<Path>>(path: Q) -> io::Result<Self>
+    where
+        S: Shape,
+        L: Alloc,
+    {
+        Self::read_npy_dynamic(&mut BufReader::new(File::open(path)?))
+    }
+}
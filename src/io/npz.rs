@@ -0,0 +1,135 @@
+//! `npz` implements NumPy's `.npz` archives that store multiple
+//! named tensors in a single file.
+//!
+//! An `.npz` file is a zip archive whose entries are `.npy` files
+//! named after the arrays they contain. Archives written by
+//! `numpy.savez` (stored) and `numpy.savez_compressed` (deflated)
+//! can both be read. `NpzWriter` stores entries uncompressed.
+
+use super::npy::invalid_data;
+use super::Element;
+use crate::tensor::layout::{Alloc, Layout, LayoutMut};
+use crate::tensor::shape::{Shape, StaticShape};
+use crate::tensor::tensor::Tensor;
+use crate::tensor::transpose_policy::Contiguous;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Seek, Write};
+use std::path::Path;
+use zip::result::ZipError;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+fn zip_error(error: ZipError) -> io::Error {
+    match error {
+        ZipError::Io(error) => error,
+        ZipError::FileNotFound => io::Error::new(io::ErrorKind::NotFound, error),
+        error => invalid_data(error),
+    }
+}
+
+/// Writer that adds named tensors to an `.npz` archive.
+///
+/// The archive is only valid once `finish` has been called.
+pub struct NpzWriter<W: Write + Seek> {
+    archive: ZipWriter<W>,
+}
+
+impl NpzWriter<BufWriter<File>> {
+    /// Creates a new `.npz` archive at `path`.
+    pub fn create<Q: AsRef<Path>>(path: Q) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write + Seek> NpzWriter<W> {
+    /// Creates a new `.npz` archive written to `writer`.
+    pub fn new(writer: W) -> Self {
+        NpzWriter {
+            archive: ZipWriter::new(writer),
+        }
+    }
+
+    /// Adds `tensor` to the archive under `name`.
+    pub fn add<T, S, C, L, P>(
+        &mut self,
+        name: &str,
+        tensor: &Tensor<T, S, C, L, P>,
+    ) -> io::Result<()>
+    where
+        T: Element,
+        L: for<'a> Layout<'a, T>,
+    {
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        self.archive
+            .start_file(format!("{}.npy", name), options)
+            .map_err(zip_error)?;
+        tensor.write_npy(&mut self.archive)
+    }
+
+    /// Writes the central directory of the archive and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.archive.finish().map_err(zip_error)
+    }
+}
+
+/// Reader that loads named tensors from an `.npz` archive.
+pub struct NpzReader<R: Read + Seek> {
+    archive: ZipArchive<R>,
+}
+
+impl NpzReader<BufReader<File>> {
+    /// Opens the `.npz` archive at `path`.
+    pub fn open<Q: AsRef<Path>>(path: Q) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read + Seek> NpzReader<R> {
+    /// Reads the directory of the `.npz` archive from `reader`.
+    pub fn new(reader: R) -> io::Result<Self> {
+        Ok(NpzReader {
+            archive: ZipArchive::new(reader).map_err(zip_error)?,
+        })
+    }
+
+    /// Returns the names of the tensors stored in the archive.
+    pub fn names(&self) -> Vec<String> {
+        self.archive
+            .file_names()
+            .map(|x| x.trim_end_matches(".npy").to_string())
+            .collect()
+    }
+
+    /// Loads the tensor stored under `name`.
+    ///
+    /// The shape found in the archive must match the static type-level shape `S`.
+    pub fn by_name<T, S, L, P>(&mut self, name: &str) -> io::Result<Tensor<T, S, Contiguous, L, P>>
+    where
+        T: Element,
+        S: StaticShape,
+        L: Default + for<'a> LayoutMut<'a, T>,
+    {
+        let mut file = self
+            .archive
+            .by_name(&format!("{}.npy", name))
+            .map_err(zip_error)?;
+        Tensor::read_npy(&mut file)
+    }
+
+    /// Loads the tensor stored under `name` with the shape found in the archive.
+    pub fn by_name_dynamic<T, S, L, P>(
+        &mut self,
+        name: &str,
+    ) -> io::Result<Tensor<T, S, Contiguous, L, P>>
+    where
+        T: Element,
+        S: Shape,
+        L: Alloc + for<'a> LayoutMut<'a, T>,
+    {
+        let mut file = self
+            .archive
+            .by_name(&format!("{}.npy", name))
+            .map_err(zip_error)?;
+        Tensor::read_npy_dynamic(&mut file)
+    }
+}
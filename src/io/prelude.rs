@@ -0,0 +1,5 @@
+pub use super::checkpoint::Checkpoint;
+pub use super::npz::{NpzReader, NpzWriter};
+pub use super::safetensors::{SafeTensors, SafeTensorsWriter};
+pub use super::Element;
+pub use super::state_dict::{StateDict, Stateful};
@@ -0,0 +1,432 @@
+//! `safetensors` implements the safetensors format that stores named
+//! tensors in a single buffer.
+//!
+//! A safetensors file starts with the size of its header as a little endian
+//! `u64`, followed by the header itself, a JSON object that maps tensor names
+//! to their data type (`dtype`), shape (`shape`) and position in the data
+//! buffer (`data_offsets`). An optional `__metadata__` entry maps strings
+//! to strings. The raw little endian data of all tensors follows the header.
+//!
+//! Loading is zero-copy: tensors are `SliceLayout` views that borrow
+//! directly from the buffer holding the file's content. This requires
+//! the data of each tensor to be correctly aligned in memory for its
+//! scalar type which is guaranteed by `SafeTensorsWriter` relatively to
+//! the start of the buffer. The buffer itself should then be allocated
+//! with sufficient alignment, which is the case of buffers returned by
+//! the system allocator such as the result of `std::fs::read`.
+//! Misaligned tensors are reported as errors.
+
+use super::npy::{invalid_data, num_elements};
+use super::Element;
+use crate::tensor::layout::Layout;
+use crate::tensor::shape::{Shape, StaticShape};
+use crate::tensor::slice_layout::SliceLayout;
+use crate::tensor::tensor::Tensor;
+use crate::tensor::transpose_policy::Contiguous;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Scalar types that can be stored in the safetensors format and
+/// viewed directly from the raw bytes of a buffer.
+///
+/// # Safety
+///
+/// All bit patterns of size `Element::SIZE` must be valid values
+/// of the implementor and it must not contain any padding.
+pub unsafe trait SafeTensorsElement: Element {
+    /// Data type identifier used in safetensors headers such as `F32`.
+    const DTYPE: &'static str;
+}
+
+macro_rules! impl_safetensors_element {
+    ($($t:ty => $dtype:expr),*) => {
+        $(
+            unsafe impl SafeTensorsElement for $t {
+                const DTYPE: &'static str = $dtype;
+            }
+        )*
+    };
+}
+
+impl_safetensors_element!(
+    f64 => "F64", f32 => "F32",
+    i64 => "I64", i32 => "I32", i16 => "I16", i8 => "I8",
+    u64 => "U64", u32 => "U32", u16 => "U16", u8 => "U8"
+);
+
+/// Outputs the size in bytes of an element of the data type `dtype`.
+fn dtype_size(dtype: &str) -> Option<usize> {
+    match dtype {
+        "BOOL" | "U8" | "I8" | "F8_E5M2" | "F8_E4M3" => Some(1),
+        "U16" | "I16" | "F16" | "BF16" => Some(2),
+        "U32" | "I32" | "F32" => Some(4),
+        "U64" | "I64" | "F64" => Some(8),
+        _ => None,
+    }
+}
+
+/// Description of a tensor stored in a safetensors buffer.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TensorInfo {
+    pub dtype: String,
+    pub shape: Vec<usize>,
+    /// Start and end of the tensor's data relative to the beginning of the data buffer.
+    pub data_offsets: (usize, usize),
+}
+
+/// Parsed safetensors buffer from which tensors can be borrowed.
+#[derive(Debug, Clone)]
+pub struct SafeTensors<'a> {
+    data: &'a [u8],
+    tensors: HashMap<String, TensorInfo>,
+    metadata: HashMap<String, String>,
+}
+
+fn parse_info(name: &str, value: &Value) -> io::Result<TensorInfo> {
+    let malformed = || invalid_data(format!("Malformed entry `{}` in safetensors header.", name));
+
+    let dtype = value
+        .get("dtype")
+        .and_then(Value::as_str)
+        .ok_or_else(malformed)?
+        .to_string();
+    let shape = value
+        .get("shape")
+        .and_then(Value::as_array)
+        .ok_or_else(malformed)?
+        .iter()
+        .map(|x| x.as_u64().map(|x| x as usize).ok_or_else(malformed))
+        .collect::<io::Result<Vec<usize>>>()?;
+    let data_offsets = match value.get("data_offsets").and_then(Value::as_array) {
+        Some(offsets) if offsets.len() == 2 => (
+            offsets[0].as_u64().ok_or_else(malformed)? as usize,
+            offsets[1].as_u64().ok_or_else(malformed)? as usize,
+        ),
+        _ => return Err(malformed()),
+    };
+
+    Ok(TensorInfo {
+        dtype,
+        shape,
+        data_offsets,
+    })
+}
+
+impl<'a> SafeTensors<'a> {
+    /// Parses the header of the safetensors `buffer`.
+    pub fn deserialize(buffer: &'a [u8]) -> io::Result<Self> {
+        if buffer.len() < 8 {
+            return Err(invalid_data(
+                "Safetensors buffer is too small to contain a header.",
+            ));
+        }
+        let mut header_len = [0; 8];
+        header_len.copy_from_slice(&buffer[..8]);
+        let header_len = u64::from_le_bytes(header_len) as usize;
+        if buffer.len() - 8 < header_len {
+            return Err(invalid_data(
+                "Safetensors header exceeds the size of the buffer.",
+            ));
+        }
+
+        let header: Map<String, Value> =
+            serde_json::from_slice(&buffer[8..8 + header_len]).map_err(invalid_data)?;
+        let data = &buffer[8 + header_len..];
+
+        let mut tensors = HashMap::new();
+        let mut metadata = HashMap::new();
+        for (name, value) in header.iter() {
+            if name == "__metadata__" {
+                let entries = value
+                    .as_object()
+                    .ok_or_else(|| invalid_data("Malformed metadata in safetensors header."))?;
+                for (key, value) in entries.iter() {
+                    let value = value
+                        .as_str()
+                        .ok_or_else(|| invalid_data("Metadata values must be strings."))?;
+                    metadata.insert(key.clone(), value.to_string());
+                }
+            } else {
+                let info = parse_info(name, value)?;
+                if info.data_offsets.0 > info.data_offsets.1 || info.data_offsets.1 > data.len() {
+                    return Err(invalid_data(format!(
+                        "Data offsets of `{}` are out of the buffer.",
+                        name
+                    )));
+                }
+                tensors.insert(name.clone(), info);
+            }
+        }
+
+        Ok(SafeTensors {
+            data,
+            tensors,
+            metadata,
+        })
+    }
+
+    /// Returns the names of the tensors stored in the buffer.
+    pub fn names(&self) -> Vec<&str> {
+        self.tensors.keys().map(String::as_str).collect()
+    }
+
+    /// Returns the description of the tensor stored under `name` if any.
+    pub fn info(&self, name: &str) -> Option<&TensorInfo> {
+        self.tensors.get(name)
+    }
+
+    /// Returns the free-form metadata stored in the header.
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+
+    /// Copies the raw data of the tensor stored under `name` after
+    /// checking that its size matches its data type and shape.
+    pub(crate) fn entry(&self, name: &str) -> io::Result<Entry> {
+        let info = self.tensors.get(name).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No tensor named `{}` in safetensors buffer.", name),
+            )
+        })?;
+        let size = dtype_size(&info.dtype).ok_or_else(|| {
+            invalid_data(format!("Unknown data type `{}` of `{}`.", info.dtype, name))
+        })?;
+
+        let (begin, end) = info.data_offsets;
+        if num_elements(&info.shape).and_then(|x| x.checked_mul(size)) != Some(end - begin) {
+            return Err(invalid_data(format!(
+                "Data offsets of `{}` do not match its shape {:?}.",
+                name, info.shape
+            )));
+        }
+
+        Ok(Entry {
+            name: name.to_string(),
+            dtype: info.dtype.clone(),
+            size,
+            shape: info.shape.clone(),
+            data: self.data[begin..end].to_vec(),
+        })
+    }
+
+    /// Checks the data type of the tensor stored under `name` against `T`
+    /// and returns its description along with a slice borrowing its data.
+    fn slice<T>(&self, name: &str) -> io::Result<(&TensorInfo, &'a [T])>
+    where
+        T: SafeTensorsElement,
+    {
+        let info = self.tensors.get(name).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No tensor named `{}` in safetensors buffer.", name),
+            )
+        })?;
+        if info.dtype != T::DTYPE {
+            return Err(invalid_data(format!(
+                "Data type `{}` of `{}` is not compatible with the tensor's type `{}`.",
+                info.dtype,
+                name,
+                T::DTYPE
+            )));
+        }
+
+        let (begin, end) = info.data_offsets;
+        let num_elements = num_elements(&info.shape)
+            .filter(|x| x.checked_mul(T::SIZE) == Some(end - begin))
+            .ok_or_else(|| {
+                invalid_data(format!(
+                    "Data offsets of `{}` do not match its shape {:?}.",
+                    name, info.shape
+                ))
+            })?;
+
+        let bytes = &self.data[begin..end];
+        if cfg!(target_endian = "big")
+            || bytes.as_ptr().align_offset(std::mem::align_of::<T>()) != 0
+        {
+            return Err(invalid_data(format!(
+                "Data of `{}` cannot be borrowed, it is not aligned in memory.",
+                name
+            )));
+        }
+
+        // Safety: the pointer is aligned for `T`, bounds have been checked above,
+        // data is little endian like the target and all bit patterns are valid `T`s.
+        let slice = unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const T, num_elements) };
+
+        Ok((info, slice))
+    }
+
+    /// Borrows the tensor stored under `name`.
+    ///
+    /// Its shape must match the static type-level shape `S`.
+    pub fn tensor<T, S, P>(
+        &self,
+        name: &str,
+    ) -> io::Result<Tensor<T, S, Contiguous, SliceLayout<'a, T>, P>>
+    where
+        T: SafeTensorsElement,
+        S: StaticShape,
+    {
+        let (info, slice) = self.slice(name)?;
+        if !S::runtime_compat(&info.shape) {
+            return Err(invalid_data(format!(
+                "Shape {:?} of `{}` is not compatible with the tensor's shape {:?}.",
+                info.shape,
+                name,
+                S::to_vec()
+            )));
+        }
+
+        Ok(Tensor::from_slice(slice))
+    }
+
+    /// Borrows the tensor stored under `name` with the shape found in the header.
+    pub fn tensor_dynamic<T, S, P>(
+        &self,
+        name: &str,
+    ) -> io::Result<Tensor<T, S, Contiguous, SliceLayout<'a, T>, P>>
+    where
+        T: SafeTensorsElement,
+        S: Shape,
+    {
+        let (info, slice) = self.slice(name)?;
+        if !S::runtime_compat(&info.shape) {
+            return Err(invalid_data(format!(
+                "Shape {:?} of `{}` is not compatible with the tensor's type-level shape.",
+                info.shape, name,
+            )));
+        }
+
+        Ok(Tensor::from_slice_dyn(slice, info.shape.clone()))
+    }
+}
+
+/// Raw little endian data of a named tensor along with its description.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Entry {
+    pub(crate) name: String,
+    pub(crate) dtype: String,
+    pub(crate) size: usize,
+    pub(crate) shape: Vec<usize>,
+    pub(crate) data: Vec<u8>,
+}
+
+impl Entry {
+    /// Copies the data of `tensor` in logical order which means that views are supported.
+    pub(crate) fn from_tensor<T, S, C, L, P>(name: &str, tensor: &Tensor<T, S, C, L, P>) -> Self
+    where
+        T: SafeTensorsElement,
+        L: for<'a> Layout<'a, T>,
+    {
+        let mut data = Vec::with_capacity(tensor.num_elements() * T::SIZE);
+        let chunk_size = tensor.opt_chunk_size();
+        for chunk in tensor.chunks(chunk_size) {
+            for x in chunk {
+                x.extend_le(&mut data);
+            }
+        }
+
+        Entry {
+            name: name.to_string(),
+            dtype: T::DTYPE.to_string(),
+            size: T::SIZE,
+            shape: tensor.shape(),
+            data,
+        }
+    }
+}
+
+/// Writer that gathers named tensors and writes them in the safetensors format.
+///
+/// Because the header must describe the position of all tensors before
+/// their data, tensors are buffered until `finish` is called.
+pub struct SafeTensorsWriter<W: Write> {
+    writer: W,
+    tensors: Vec<Entry>,
+    metadata: Map<String, Value>,
+}
+
+impl SafeTensorsWriter<BufWriter<File>> {
+    /// Creates a new safetensors file at `path`.
+    pub fn create<Q: AsRef<Path>>(path: Q) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> SafeTensorsWriter<W> {
+    /// Creates a new safetensors writer that outputs to `writer`.
+    pub fn new(writer: W) -> Self {
+        SafeTensorsWriter {
+            writer,
+            tensors: Vec::new(),
+            metadata: Map::new(),
+        }
+    }
+
+    /// Adds `tensor` under `name`. Data is copied in logical order
+    /// which means that views are supported.
+    pub fn add<T, S, C, L, P>(&mut self, name: &str, tensor: &Tensor<T, S, C, L, P>)
+    where
+        T: SafeTensorsElement,
+        L: for<'a> Layout<'a, T>,
+    {
+        self.tensors.push(Entry::from_tensor(name, tensor));
+    }
+
+    pub(crate) fn add_entry(&mut self, entry: Entry) {
+        self.tensors.push(entry);
+    }
+
+    /// Adds the free-form metadata entry `key: value` to the header.
+    pub fn add_metadata(&mut self, key: &str, value: &str) {
+        self.metadata
+            .insert(key.to_string(), Value::String(value.to_string()));
+    }
+
+    /// Writes the header and data and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        // Tensors are sorted by decreasing scalar size so that each
+        // tensor's data is aligned for its type.
+        self.tensors
+            .sort_by(|x, y| y.size.cmp(&x.size).then_with(|| x.name.cmp(&y.name)));
+
+        let mut header = Map::new();
+        if !self.metadata.is_empty() {
+            header.insert(
+                "__metadata__".to_string(),
+                Value::Object(std::mem::take(&mut self.metadata)),
+            );
+        }
+        let mut offset = 0;
+        for tensor in self.tensors.iter() {
+            let mut entry = Map::new();
+            entry.insert("dtype".to_string(), Value::from(tensor.dtype.clone()));
+            entry.insert("shape".to_string(), Value::from(tensor.shape.clone()));
+            entry.insert(
+                "data_offsets".to_string(),
+                Value::from(vec![offset, offset + tensor.data.len()]),
+            );
+            header.insert(tensor.name.clone(), Value::Object(entry));
+            offset += tensor.data.len();
+        }
+
+        // Header is padded with spaces so that data starts on an 8 bytes boundary.
+        let mut header = serde_json::to_vec(&header).map_err(invalid_data)?;
+        header.resize(header.len() + (8 - header.len() % 8) % 8, b' ');
+
+        self.writer
+            .write_all(&(header.len() as u64).to_le_bytes())?;
+        self.writer.write_all(&header)?;
+        for tensor in self.tensors.iter() {
+            self.writer.write_all(&tensor.data)?;
+        }
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
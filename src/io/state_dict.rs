@@ -0,0 +1,201 @@
+//! `state_dict` defines `StateDict`, a collection of named tensors that
+//! captures the state of a model or an optimizer, and the `Stateful` trait
+//! implemented by types that can save their state to and restore it from
+//! a `StateDict`.
+//!
+//! Names are built hierarchically: a model implements `Stateful` by
+//! forwarding calls to its submodules and parameters with names prefixed
+//! by the model's own prefix, using `join` to insert separators.
+//! Tensors and variables are the leaves of this hierarchy and are stored
+//! under the prefix itself.
+//!
+//! # Example
+//!
+//! ```
+//! use melange::prelude::*;
+//! use melange::io::state_dict::{join, StateDict, Stateful};
+//! use typenum::U2;
+//!
+//! struct Linear {
+//!     weight: StaticTensor<f64, Shape2D<U2, U2>>,
+//!     bias: StaticTensor<f64, Shape1D<U2>>,
+//! }
+//!
+//! impl Stateful for Linear {
+//!     fn save_state(&self, prefix: &str, state_dict: &mut StateDict) {
+//!         self.weight.save_state(&join(prefix, "weight"), state_dict);
+//!         self.bias.save_state(&join(prefix, "bias"), state_dict);
+//!     }
+//!
+//!     fn load_state(&mut self, prefix: &str, state_dict: &StateDict) -> std::io::Result<()> {
+//!         self.weight.load_state(&join(prefix, "weight"), state_dict)?;
+//!         self.bias.load_state(&join(prefix, "bias"), state_dict)
+//!     }
+//! }
+//!
+//! let linear = Linear { weight: Tensor::fill(1.0), bias: Tensor::fill(0.5) };
+//! let state_dict = linear.state_dict();
+//! assert_eq!(state_dict.names(), vec!["bias", "weight"]);
+//! ```
+
+use super::npy::invalid_data;
+use super::safetensors::{Entry, SafeTensorsElement};
+use crate::tensor::layout::{Layout, LayoutMut};
+use crate::tensor::tensor::Tensor;
+use crate::tensor::transpose_policy::Contiguous;
+use std::collections::BTreeMap;
+use std::io;
+
+/// Joins `prefix` and `name` with a dot, omitting the dot if `prefix` is empty.
+pub fn join(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+/// Collection of named tensors stored as raw little endian data
+/// along with their data type and shape.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct StateDict {
+    pub(super) entries: BTreeMap<String, Entry>,
+}
+
+impl StateDict {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of tensors in the state dictionary.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the sorted names of the tensors in the state dictionary.
+    pub fn names(&self) -> Vec<&str> {
+        self.entries.keys().map(String::as_str).collect()
+    }
+
+    /// Returns the shape of the tensor stored under `name` if any.
+    pub fn shape(&self, name: &str) -> Option<&[usize]> {
+        self.entries.get(name).map(|x| x.shape.as_slice())
+    }
+
+    /// Copies `tensor` under `name`, replacing any previous entry.
+    pub fn insert<T, S, C, L, P>(&mut self, name: &str, tensor: &Tensor<T, S, C, L, P>)
+    where
+        T: SafeTensorsElement,
+        L: for<'a> Layout<'a, T>,
+    {
+        self.entries
+            .insert(name.to_string(), Entry::from_tensor(name, tensor));
+    }
+
+    /// Copies the data stored under `name` into `tensor` after checking
+    /// that both data types and shapes are identical.
+    pub fn restore<T, S, L, P>(
+        &self,
+        name: &str,
+        tensor: &mut Tensor<T, S, Contiguous, L, P>,
+    ) -> io::Result<()>
+    where
+        T: SafeTensorsElement,
+        L: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>,
+    {
+        let entry = self.entries.get(name).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No tensor named `{}` in state dictionary.", name),
+            )
+        })?;
+        if entry.dtype != T::DTYPE {
+            return Err(invalid_data(format!(
+                "Data type `{}` of `{}` is not compatible with the tensor's type `{}`.",
+                entry.dtype,
+                name,
+                T::DTYPE
+            )));
+        }
+        let shape = tensor.shape();
+        if entry.shape != shape {
+            return Err(invalid_data(format!(
+                "Shape {:?} of `{}` is not compatible with the tensor's shape {:?}.",
+                entry.shape, name, shape
+            )));
+        }
+
+        for (x, bytes) in tensor.iter_mut().zip(entry.data.chunks_exact(T::SIZE)) {
+            *x = T::from_le(bytes);
+        }
+
+        Ok(())
+    }
+
+    /// Moves all entries of `other` in `self` under names prefixed by `prefix`.
+    pub fn extend(&mut self, prefix: &str, other: StateDict) {
+        for (name, mut entry) in other.entries.into_iter() {
+            entry.name = join(prefix, &name);
+            self.entries.insert(entry.name.clone(), entry);
+        }
+    }
+
+    /// Extracts the entries whose names start with `prefix` followed by a dot
+    /// and returns them in a new state dictionary with the prefix stripped.
+    pub fn sub_dict(&self, prefix: &str) -> StateDict {
+        let start = format!("{}.", prefix);
+        let entries = self
+            .entries
+            .iter()
+            .filter(|(name, _)| name.starts_with(&start))
+            .map(|(name, entry)| {
+                let mut entry = entry.clone();
+                entry.name = name[start.len()..].to_string();
+                (entry.name.clone(), entry)
+            })
+            .collect();
+
+        StateDict { entries }
+    }
+}
+
+/// Types whose state can be saved in and restored from a `StateDict`.
+pub trait Stateful {
+    /// Saves the state of `self` in `state_dict` under names prefixed by `prefix`.
+    fn save_state(&self, prefix: &str, state_dict: &mut StateDict);
+
+    /// Restores the state of `self` from the entries of `state_dict`
+    /// prefixed by `prefix`.
+    fn load_state(&mut self, prefix: &str, state_dict: &StateDict) -> io::Result<()>;
+
+    /// Returns a new `StateDict` containing the state of `self`.
+    fn state_dict(&self) -> StateDict {
+        let mut state_dict = StateDict::new();
+        self.save_state("", &mut state_dict);
+
+        state_dict
+    }
+
+    /// Restores the state of `self` from `state_dict`.
+    fn load_state_dict(&mut self, state_dict: &StateDict) -> io::Result<()> {
+        self.load_state("", state_dict)
+    }
+}
+
+impl<T, S, L, P> Stateful for Tensor<T, S, Contiguous, L, P>
+where
+    T: SafeTensorsElement,
+    L: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>,
+{
+    fn save_state(&self, prefix: &str, state_dict: &mut StateDict) {
+        state_dict.insert(prefix, self);
+    }
+
+    fn load_state(&mut self, prefix: &str, state_dict: &StateDict) -> io::Result<()> {
+        state_dict.restore(prefix, self)
+    }
+}
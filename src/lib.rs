@@ -21,6 +21,10 @@ mod tests {
             <Shape3D<U2, U4, U2> as Broadcast<Shape4D<U5, U1, U3, U2>>>::Output::BOOL,
             false
         );
+        assert!(<Shape2D<U1, U3> as BroadcastTo<Shape3D<U4, U2, U3>>>::Output::to_bool());
+        assert!(!<Shape2D<U2, U3> as BroadcastTo<Shape2D<U1, U3>>>::Output::to_bool());
+        assert!(<Shape2D<U2, U1> as BroadcastTo<Shape2D<U2, U3>>>::Output::to_bool());
+        assert!(!<Shape2D<U2, U4> as BroadcastTo<Shape2D<U2, U3>>>::Output::to_bool());
         assert_eq!(
             <Shape1D<U6> as SameNumElements<i32, Shape2D<U3, U2>>>::Output::BOOL,
             true
@@ -67,6 +71,8 @@ mod tests {
         );
         assert_eq!(<Shape4D<U5, U1, U3, U2> as At<U2>>::Output::USIZE, 3);
         assert_eq!(<<Shape4D<U4, U3, U6, U6> as Transpose>::Output as StaticShape>::to_vec(), vec![6, 6, 3, 4]);
+        assert_eq!(<Shape2D<U3, U5> as MinDim>::Output::USIZE, 3);
+        assert_eq!(<Shape2D<U6, U2> as MinDim>::Output::USIZE, 2);
     }
 
     #[test]
@@ -93,6 +99,40 @@ mod tests {
         assert_eq!(b.opt_chunk_size(), 2);
     }
 
+    #[test]
+    fn broadcast_dynamic_opt_chunk_size() {
+        let a: SliceTensor<i32, Shape1D<U3>> = Tensor::from_slice(&[1, 2, 3]);
+        let b: StridedSliceTensor<_, Shape2D<Dyn, U3>> = a.broadcast_dynamic(vec![2, 3]);
+        assert_eq!(b.strides(), vec![0, 1]);
+        assert_eq!(b.opt_chunk_size(), 3);
+        let data: Vec<i32> = b.chunks(b.opt_chunk_size()).flatten().copied().collect();
+        assert_eq!(data, vec![1, 2, 3, 1, 2, 3]);
+    }
+
+    #[test]
+    fn broadcast_unit_axis() {
+        let a: SliceTensor<i32, Shape2D<U3, U1>> = Tensor::from_slice(&[1, 2, 3]);
+        let b: StridedSliceTensor<_, Shape2D<U3, U4>> = a.broadcast();
+        assert_eq!(b.strides(), vec![1, 0]);
+        assert_eq!(b.opt_chunk_size(), 1);
+        let data: Vec<i32> = b.chunks(b.opt_chunk_size()).flatten().copied().collect();
+        assert_eq!(data, vec![1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3]);
+
+        let b: StridedSliceTensor<_, Shape2D<U3, Dyn>> = a.broadcast_dynamic(vec![3, 4]);
+        assert_eq!(b.strides(), vec![1, 0]);
+        let data: Vec<i32> = b.chunks(b.opt_chunk_size()).flatten().copied().collect();
+        assert_eq!(data, vec![1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3]);
+
+        // Broadcasting keeps the strides of transposed views.
+        let a: SliceTensor<i32, Shape2D<U2, U2>> = Tensor::from_slice(&[1, 2, 3, 4]);
+        let t = a.transpose();
+        let b: StridedSliceTensor<_, Shape3D<U2, U2, U2>> = t.broadcast();
+        assert_eq!(b.strides(), vec![0, 1, 2]);
+        assert_eq!(b.opt_chunk_size(), 1);
+        let data: Vec<i32> = b.chunks(b.opt_chunk_size()).flatten().copied().collect();
+        assert_eq!(data, vec![1, 3, 2, 4, 1, 3, 2, 4]);
+    }
+
     #[test]
     fn reshape() {
         let mut a: StaticTensor<i32, Shape1D<U4>> = Tensor::default();
@@ -389,6 +429,32 @@ mod tests {
         assert!(d < EPSILON);
     }
 
+    #[test]
+    fn transposed_dot() {
+        let a: SliceTensor<f64, Shape2D<U2, U3>> =
+            Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let b: SliceTensor<f64, Shape2D<U2, U3>> =
+            Tensor::from_slice(&[1.0, 0.0, -1.0, 2.0, 1.0, 0.0]);
+
+        assert_eq!(&a.dot(&b.transpose())[..], &[-2.0, 4.0, -2.0, 13.0]);
+        assert_eq!(
+            &a.transpose().dot(&b)[..],
+            &[9.0, 4.0, -1.0, 12.0, 5.0, -2.0, 15.0, 6.0, -3.0]
+        );
+        assert_eq!(
+            &a.transpose().dot(&b.transpose().transpose())[..],
+            &a.transpose().dot(&b)[..]
+        );
+        let c: DynamicTensor<f64, Shape2D<U3, U3>> = a.transpose().dot_dynamic(&b);
+        assert_eq!(&c[..], &[9.0, 4.0, -1.0, 12.0, 5.0, -2.0, 15.0, 6.0, -3.0]);
+
+        let v: SliceTensor<f64, Shape1D<U2>> = Tensor::from_slice(&[1.0, 2.0]);
+        assert_eq!(&a.transpose().dotv(&v)[..], &[9.0, 12.0, 15.0]);
+        assert_eq!(&a.transpose().dotv_coerce(&v)[..], &[9.0, 12.0, 15.0]);
+        let w: DynamicTensor<f64, Shape1D<U3>> = a.transpose().dotv_dynamic(&v);
+        assert_eq!(&w[..], &[9.0, 12.0, 15.0]);
+    }
+
     #[test]
     fn transpose() {
         let a: SliceTensor<i32, Shape2D<U2, U3>> = Tensor::from_slice(&[1, 2, 3, 4, 5, 6]);
@@ -431,9 +497,1194 @@ mod tests {
         
         assert_eq!(a.grad().unwrap().as_view(), Tensor::from_slice(&[2.0, 1.0, 0.0, 2.0]));
     }
+
+    #[test]
+    fn npy_roundtrip() {
+        let a: SliceTensor<i32, Shape2D<U2, U3>> = Tensor::from_slice(&[1, 2, 3, 4, 5, 6]);
+        let mut buffer = Vec::new();
+        a.transpose().write_npy(&mut buffer).unwrap();
+
+        let b: StaticTensor<i32, Shape2D<U3, U2>> =
+            Tensor::read_npy(&mut buffer.as_slice()).unwrap();
+        let c: SliceTensor<i32, Shape2D<U3, U2>> = Tensor::from_slice(&[1, 4, 2, 5, 3, 6]);
+        assert_eq!(b.as_view(), c);
+
+        let d: DynamicTensor<i32, Shape2D<Dyn, Dyn>> =
+            Tensor::read_npy_dynamic(&mut buffer.as_slice()).unwrap();
+        assert_eq!(d.shape(), vec![3, 2]);
+        assert_eq!(d.as_static::<Shape2D<U3, U2>>(), c);
+
+        let e: std::io::Result<StaticTensor<i32, Shape2D<U2, U3>>> =
+            Tensor::read_npy(&mut buffer.as_slice());
+        assert!(e.is_err());
+        let f: std::io::Result<StaticTensor<f32, Shape2D<U3, U2>>> =
+            Tensor::read_npy(&mut buffer.as_slice());
+        assert!(f.is_err());
+
+        let g: SliceTensor<usize, Shape1D<U3>> = Tensor::from_slice(&[1, 2, usize::MAX]);
+        let mut buffer = Vec::new();
+        g.write_npy(&mut buffer).unwrap();
+        let h: StaticTensor<usize, Shape1D<U3>> = Tensor::read_npy(&mut buffer.as_slice()).unwrap();
+        assert_eq!(h.as_view(), g);
+        let i: StaticTensor<u64, Shape1D<U3>> = Tensor::read_npy(&mut buffer.as_slice()).unwrap();
+        assert_eq!(i[2], usize::MAX as u64);
+        let j: SliceTensor<isize, Shape1D<U2>> = Tensor::from_slice(&[-1, isize::MIN]);
+        let mut buffer = Vec::new();
+        j.write_npy(&mut buffer).unwrap();
+        let k: DynamicTensor<isize, Shape1D<Dyn>> =
+            Tensor::read_npy_dynamic(&mut buffer.as_slice()).unwrap();
+        assert_eq!(&k[..], &[-1, isize::MIN]);
+    }
+
+    #[test]
+    fn npy_corrupt_shape() {
+        let read = |shape: &str| {
+            let header = format!(
+                "{{'descr': '<f8', 'fortran_order': False, 'shape': {}, }}",
+                shape
+            );
+            let mut buffer = b"\x93NUMPY\x01\x00".to_vec();
+            buffer.extend_from_slice(&(header.len() as u16).to_le_bytes());
+            buffer.extend_from_slice(header.as_bytes());
+            let a: std::io::Result<DynamicTensor<f64, Shape2D<Dyn, Dyn>>> =
+                Tensor::read_npy_dynamic(&mut buffer.as_slice());
+            a.map(|_| ()).map_err(|e| e.kind())
+        };
+
+        let overflow = format!("({}, {})", usize::MAX, 2);
+        assert_eq!(read(&overflow), Err(std::io::ErrorKind::InvalidData));
+        let too_large = format!("({}, {})", usize::MAX / 8, 2);
+        assert_eq!(read(&too_large), Err(std::io::ErrorKind::InvalidData));
+        assert_eq!(read("(0, 2)"), Ok(()));
+    }
+
+    #[test]
+    fn npy_fortran_order() {
+        let header = "{'descr': '<f8', 'fortran_order': True, 'shape': (2, 3), }";
+        let mut buffer = b"\x93NUMPY\x01\x00".to_vec();
+        buffer.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        buffer.extend_from_slice(header.as_bytes());
+        for x in &[1.0_f64, 4.0, 2.0, 5.0, 3.0, 6.0] {
+            buffer.extend_from_slice(&x.to_le_bytes());
+        }
+
+        let a: StaticTensor<f64, Shape2D<U2, U3>> =
+            Tensor::read_npy(&mut buffer.as_slice()).unwrap();
+        let b: SliceTensor<f64, Shape2D<U2, U3>> =
+            Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(a.as_view(), b);
+    }
+
+    #[test]
+    fn npz_roundtrip() {
+        let a: SliceTensor<f32, Shape1D<U3>> = Tensor::from_slice(&[1.0, 2.0, 3.0]);
+        let b: SliceTensor<u8, Shape2D<U2, U2>> = Tensor::from_slice(&[1, 2, 3, 4]);
+
+        let mut writer = NpzWriter::new(std::io::Cursor::new(Vec::new()));
+        writer.add("weight", &a).unwrap();
+        writer.add("bias", &b).unwrap();
+        let buffer = writer.finish().unwrap();
+
+        let mut reader = NpzReader::new(buffer).unwrap();
+        let mut names = reader.names();
+        names.sort();
+        assert_eq!(names, vec!["bias", "weight"]);
+
+        let c: StaticTensor<f32, Shape1D<U3>> = reader.by_name("weight").unwrap();
+        assert_eq!(c.as_view(), a);
+        let d: DynamicTensor<u8, Shape2D<Dyn, Dyn>> = reader.by_name_dynamic("bias").unwrap();
+        assert_eq!(d.as_static::<Shape2D<U2, U2>>(), b);
+        let e: std::io::Result<StaticTensor<u8, Shape1D<U3>>> = reader.by_name("missing");
+        assert!(e.is_err());
+    }
+    #[test]
+    fn safetensors_roundtrip() {
+        let a: SliceTensor<f64, Shape2D<U2, U2>> = Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0]);
+        let b: SliceTensor<i32, Shape1D<U3>> = Tensor::from_slice(&[1, 2, 3]);
+
+        let mut writer = SafeTensorsWriter::new(Vec::new());
+        writer.add("b", &b);
+        writer.add("a", &a.transpose());
+        writer.add_metadata("format", "pt");
+        let buffer = writer.finish().unwrap();
+
+        let tensors = SafeTensors::deserialize(&buffer).unwrap();
+        assert_eq!(tensors.metadata()["format"], "pt");
+        assert_eq!(tensors.info("b").unwrap().shape, vec![3]);
+
+        let c: SliceTensor<f64, Shape2D<U2, U2>> = tensors.tensor("a").unwrap();
+        let d: SliceTensor<f64, Shape2D<U2, U2>> = Tensor::from_slice(&[1.0, 3.0, 2.0, 4.0]);
+        assert_eq!(c, d);
+        let e: SliceTensor<i32, Shape1D<Dyn>> = tensors.tensor_dynamic("b").unwrap();
+        assert_eq!(e.as_static::<Shape1D<U3>>(), b);
+
+        let f: std::io::Result<SliceTensor<f32, Shape2D<U2, U2>>> = tensors.tensor("a");
+        assert!(f.is_err());
+        let g: std::io::Result<SliceTensor<i32, Shape1D<U4>>> = tensors.tensor("b");
+        assert!(g.is_err());
+    }
+
+    #[test]
+    fn safetensors_corrupt_shape() {
+        // The size of the shape overflows and wraps to the empty data.
+        let header = format!(
+            r#"{{"o":{{"dtype":"F32","shape":[{},4],"data_offsets":[0,0]}}}}"#,
+            usize::MAX / 4 + 1
+        );
+        let mut buffer = (header.len() as u64).to_le_bytes().to_vec();
+        buffer.extend_from_slice(header.as_bytes());
+        let tensors = SafeTensors::deserialize(&buffer).unwrap();
+        let o: std::io::Result<SliceTensor<f32, Shape2D<Dyn, Dyn>>> = tensors.tensor_dynamic("o");
+        assert!(o.is_err());
+        assert!(tensors.entry("o").is_err());
+    }
+
+    #[test]
+    fn checkpoint_roundtrip() {
+        use crate::tensor::allocation_policy::DefaultPolicy;
+        use crate::tensor::static_heap_layout::StaticHeapLayout;
+        use crate::tensor::transpose_policy::Contiguous;
+
+        let mut value: StaticTensor<f64, Shape2D<U2, U2>> = Tensor::default();
+        value.copy_from_slice(&[1.0, 2.0, 3.0, 4.0]);
+        type Param = Variable<
+            f64,
+            Shape2D<U2, U2>,
+            Contiguous,
+            StaticHeapLayout<f64, Shape2D<U2, U2>>,
+            DefaultPolicy,
+            StaticHeapLayout<f64, Shape2D<U2, U2>>,
+            Contiguous,
+            StaticHeapLayout<f64, Shape2D<U2, U2>>,
+            DefaultPolicy,
+        >;
+        let weight: Param = Variable::new(value, true);
+        let mut momentum: StaticTensor<f32, Shape1D<U3>> = Tensor::default();
+        momentum.copy_from_slice(&[0.5, 0.25, 0.125]);
+
+        let mut checkpoint = Checkpoint {
+            model: weight.state_dict(),
+            epoch: 3,
+            step: 1200,
+            rng_state: vec![7, 8, 9],
+            ..Checkpoint::default()
+        };
+        momentum.save_state("momentum", &mut checkpoint.optimizer);
+
+        let buffer = checkpoint.write(Vec::new()).unwrap();
+        let restored = Checkpoint::read(&buffer).unwrap();
+        assert_eq!(restored, checkpoint);
+
+        let mut other: Param = Variable::new(Tensor::default(), false);
+        other.load_state_dict(&restored.model).unwrap();
+        assert_eq!(other.state_dict(), weight.state_dict());
+
+        let mut wrong_type: StaticTensor<f64, Shape1D<U3>> = Tensor::default();
+        assert!(wrong_type.load_state("momentum", &restored.optimizer).is_err());
+        let mut wrong_shape: StaticTensor<f32, Shape1D<U4>> = Tensor::default();
+        assert!(wrong_shape.load_state("momentum", &restored.optimizer).is_err());
+        let mut right: StaticTensor<f32, Shape1D<U3>> = Tensor::default();
+        right.load_state("momentum", &restored.optimizer).unwrap();
+        assert_eq!(right, momentum);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let a: SliceTensor<f64, Shape2D<U2, U3>> =
+            Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let json = serde_json::to_string(&a.transpose()).unwrap();
+        assert_eq!(json, r#"{"shape":[3,2],"data":[1.0,4.0,2.0,5.0,3.0,6.0]}"#);
+
+        let b: StaticTensor<f64, Shape2D<U3, U2>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(&b[..], &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+        let c: DynamicTensor<f64, Shape2D<Dyn, U2>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(c.shape(), vec![3, 2]);
+        assert_eq!(&c[..], &b[..]);
+        let d: StackTensor<f64, Shape2D<U3, U2>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(&d[..], &b[..]);
+
+        let e: Result<StaticTensor<f64, Shape2D<U2, U3>>, _> = serde_json::from_str(&json);
+        assert!(e.is_err());
+        let f: Result<DynamicTensor<f64, Shape2D<Dyn, U3>>, _> = serde_json::from_str(&json);
+        assert!(f.is_err());
+        let g: Result<DynamicTensor<f64, Shape1D<Dyn>>, _> =
+            serde_json::from_str(r#"{"shape":[3],"data":[1.0,2.0]}"#);
+        assert!(g.is_err());
+        let h: Result<DynamicTensor<f64, Shape2D<Dyn, Dyn>>, _> =
+            serde_json::from_str(&format!(r#"{{"shape":[{},2],"data":[]}}"#, usize::MAX));
+        assert!(h.is_err());
+    }
+
+    #[test]
+    fn display() {
+        use crate::tensor::allocation_policy::DefaultPolicy;
+        use crate::tensor::static_heap_layout::StaticHeapLayout;
+        use crate::tensor::transpose_policy::Contiguous;
+
+        let a: SliceTensor<f64, Shape2D<U2, U3>> =
+            Tensor::from_slice(&[1.0, 2.5, -3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(format!("{}", a), "[[  1, 2.5,  -3],\n [  4,   5,   6]]");
+        assert_eq!(
+            format!("{:.2}", a.transpose()),
+            "[[ 1.00,  4.00],\n [ 2.50,  5.00],\n [-3.00,  6.00]]"
+        );
+
+        let b: SliceTensor<i32, Shape3D<U2, U1, U2>> = Tensor::from_slice(&[1, 2, 3, 4]);
+        assert_eq!(format!("{}", b), "[[[1, 2]],\n\n [[3, 4]]]");
+
+        let data: Vec<i32> = (0..2000).collect();
+        let c: SliceTensor<i32, Shape1D<Dyn>> = Tensor::from_slice_dyn(&data, vec![2000]);
+        assert_eq!(format!("{}", c), "[   0,    1,    2, ..., 1997, 1998, 1999]");
+
+        let d: SliceTensor<i32, Shape2D<U2, U2>> = Tensor::from_slice(&[1, 2, 3, 4]);
+        let v: Variable<
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            Contiguous,
+            StaticHeapLayout<i32, Shape2D<U2, U2>>,
+            DefaultPolicy,
+        > = Variable::new(d, false);
+        assert_eq!(
+            format!("{}", v),
+            "Variable(backward_op: no_op)\nvalue: [[1, 2],\n        [3, 4]]\ngrad: None"
+        );
+    }
+
+    #[test]
+    fn dot_into() {
+        // `a` is the transpose of a 2 x 3 matrix stored row-major.
+        let a_data: SliceTensor<f64, Shape2D<U2, U3>> =
+            Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let a = a_data.transpose();
+        let b: SliceTensor<f64, Shape2D<U2, U2>> = Tensor::from_slice(&[1.0, 0.0, 0.0, 1.0]);
+        let mut out: StaticTensor<f64, Shape2D<U3, U2>> = Tensor::fill(1.0);
+        a.dot_into(&b, &mut out, 2.0, 3.0);
+        assert_eq!(&out[..], &[5.0, 11.0, 7.0, 13.0, 9.0, 15.0]);
+
+        let x: SliceTensor<f64, Shape1D<U2>> = Tensor::from_slice(&[1.0, -1.0]);
+        let mut y: StaticTensor<f64, Shape1D<U3>> = Tensor::fill(1.0);
+        a.dotv_into(&x, &mut y, 1.0, -1.0);
+        assert_eq!(&y[..], &[-4.0, -4.0, -4.0]);
+
+        let z: SliceTensor<f64, Shape1D<U3>> = Tensor::from_slice(&[2.0, 4.0, 6.0]);
+        y.axpy_(0.5, &z);
+        y.scal_(-1.0);
+        assert_eq!(&y[..], &[3.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn gemm_kernel() {
+        use crate::tensor::gemm;
+        use crate::tensor::transpose_policy::Transpose;
+
+        // Sizes cross the row and depth block boundaries.
+        let (m, n, k) = (70, 5, 300);
+        let a: Vec<i64> = (0..m * k).map(|x| (x as i64 * 7) % 11 - 5).collect();
+        let b: Vec<i64> = (0..k * n).map(|x| (x as i64 * 5) % 13 - 6).collect();
+        let mut expected = vec![1; m * n];
+        for i in 0..m {
+            for j in 0..n {
+                let x: i64 = (0..k).map(|p| a[i * k + p] * b[p * n + j]).sum();
+                expected[i * n + j] = 2 * x - 1;
+            }
+        }
+
+        let transpose = |data: &[i64], rows: usize, cols: usize| {
+            let mut out = vec![0; data.len()];
+            for i in 0..rows {
+                for j in 0..cols {
+                    out[j * rows + i] = data[i * cols + j];
+                }
+            }
+            out
+        };
+        let a_t = transpose(&a, m, k);
+        let b_t = transpose(&b, k, n);
+        let operands = [
+            (Transpose::None, &a, k, Transpose::None, &b, n),
+            (Transpose::Ordinary, &a_t, m, Transpose::None, &b, n),
+            (Transpose::None, &a, k, Transpose::Ordinary, &b_t, k),
+            (Transpose::Ordinary, &a_t, m, Transpose::Ordinary, &b_t, k),
+        ];
+        for (trans_a, a, lda, trans_b, b, ldb) in operands.iter() {
+            let mut c = vec![1; m * n];
+            gemm::gemm(*trans_a, *trans_b, m, n, k, 2, a, *lda, b, *ldb, -1, &mut c, n);
+            assert_eq!(c, expected);
+        }
+
+        let x: Vec<i64> = (0..k as i64).collect();
+        let mut y = vec![0; m];
+        gemm::gemv(Transpose::Ordinary, k, m, 1, &a_t, m, &x, 1, 0, &mut y, 1);
+        let expected: Vec<i64> = (0..m)
+            .map(|i| (0..k).map(|p| a[i * k + p] * x[p]).sum())
+            .collect();
+        assert_eq!(y, expected);
+    }
+
+    #[test]
+    fn matmul() {
+        let a: SliceTensor<i64, Shape2D<U2, U3>> = Tensor::from_slice(&[1, 2, 3, 4, 5, 6]);
+        let b: SliceTensor<i64, Shape2D<U2, U3>> = Tensor::from_slice(&[1, 0, -1, 2, 1, 0]);
+        let c = a.matmul(&b.transpose());
+        assert_eq!(&c[..], &[-2, 4, -2, 13]);
+
+        let a: SliceTensor<u8, Shape2D<Dyn, Dyn>> =
+            Tensor::from_slice_dyn(&[1, 2, 3, 4, 5, 6], vec![3, 2]);
+        let b: SliceTensor<u8, Shape2D<U2, Dyn>> = Tensor::from_slice_dyn(&[1, 1, 0, 2], vec![2, 2]);
+        let c = a.matmul_dynamic(&b);
+        assert_eq!(c.shape(), vec![3, 2]);
+        assert_eq!(&c[..], &[1, 5, 3, 11, 5, 17]);
+
+        let a: SliceTensor<i8, Shape2D<U1, U3>> = Tensor::from_slice(&[100, -100, 127]);
+        let b: SliceTensor<i8, Shape2D<U3, U2>> = Tensor::from_slice(&[100, 1, 100, 1, 127, -128]);
+        let c = a.matmul_i32(&b);
+        assert_eq!(&c[..], &[16129, -16256]);
+    }
+
+    #[test]
+    fn half_precision() {
+        use half::{bf16, f16};
+
+        let a: SliceTensor<f32, Shape1D<U3>> = Tensor::from_slice(&[0.5, -2.0, 1.0]);
+        let b = a.to_f16();
+        let c = b.add(&b).exp().to_f32();
+        let expected = [1.0f32.exp(), (-4.0f32).exp(), 2.0f32.exp()];
+        for (x, y) in c.iter().zip(expected.iter()) {
+            assert!((x - y).abs() < 1e-2 * y);
+        }
+        assert_eq!(&a.to_bf16().to_f32()[..], &a[..]);
+        assert_eq!(b.sum::<U0>()[0], f16::from_f32(-0.5));
+
+        // Accumulating in `f16` would stall at 2048.
+        let x: StaticTensor<bf16, Shape2D<U1, typenum::U4096>> = Tensor::fill(bf16::ONE);
+        let y: StaticTensor<bf16, Shape2D<U1, typenum::U4096>> = Tensor::fill(bf16::ONE);
+        assert_eq!(x.dot(&y.transpose())[0], bf16::from_f32(4096.0));
+        let x: StaticTensor<f16, Shape2D<U1, typenum::U4096>> = Tensor::fill(f16::ONE);
+        let y: StaticTensor<f16, Shape2D<U1, typenum::U4096>> = Tensor::fill(f16::ONE);
+        assert_eq!(x.dot(&y.transpose())[0], f16::from_f32(4096.0));
+    }
+
+    #[test]
+    fn complex() {
+        use num_complex::Complex64;
+
+        let data = [
+            Complex64::new(1.0, 1.0),
+            Complex64::new(0.0, 2.0),
+            Complex64::new(-1.0, 0.0),
+            Complex64::new(3.0, -4.0),
+        ];
+        let a: SliceTensor<Complex64, Shape2D<U2, U2>> = Tensor::from_slice(&data);
+        let b = a.mul(&a.conj());
+        assert_eq!(&b.re()[..], &[2.0, 4.0, 1.0, 25.0]);
+        assert_eq!(&b.im()[..], &[0.0; 4]);
+        assert_eq!(&a.norm()[..], &[2.0f64.sqrt(), 2.0, 1.0, 5.0]);
+        assert_eq!(a.arg()[1], std::f64::consts::FRAC_PI_2);
+        let e = a.exp();
+        assert!((e[0] - Complex64::new(1.0f64.cos(), 1.0f64.sin()) * 1.0f64.exp()).norm() < 1e-12);
+
+        let c = a.dot(&a.transpose());
+        let expected = [
+            Complex64::new(-4.0, 2.0),
+            Complex64::new(7.0, 5.0),
+            Complex64::new(7.0, 5.0),
+            Complex64::new(-6.0, -24.0),
+        ];
+        assert_eq!(&c[..], &expected);
+        let d: DynamicTensor<Complex64, Shape2D<U2, U2>> = a.dot_dynamic(&a.transpose());
+        assert_eq!(&d[..], &expected);
+    }
+
+    #[test]
+    fn cast() {
+        let pixels: SliceTensor<u8, Shape2D<U2, U2>> = Tensor::from_slice(&[0, 51, 204, 255]);
+        let x: StaticTensor<f32, Shape2D<U2, U2>> = pixels.cast::<f32>();
+        assert_eq!(&x[..], &[0.0, 51.0, 204.0, 255.0]);
+        let t = pixels.transpose().cast_dynamic::<i64>();
+        assert_eq!(&t[..], &[0, 204, 51, 255]);
+
+        let labels: SliceTensor<f32, Shape1D<U3>> = Tensor::from_slice(&[2.0, 0.0, 1.7]);
+        assert_eq!(&labels.cast::<usize>()[..], &[2, 0, 1]);
+        assert_eq!(&labels.try_cast::<usize>().unwrap()[..], &[2, 0, 1]);
+        let negative: SliceTensor<f32, Shape1D<U3>> = Tensor::from_slice(&[2.0, -1.0, 1.0]);
+        assert_eq!(&negative.cast::<u8>()[..], &[2, 0, 1]);
+        assert!(negative.try_cast::<usize>().is_none());
+        assert!(pixels.try_cast_dynamic::<i8>().is_none());
+        assert!(pixels.try_cast_dynamic::<i16>().is_some());
+    }
+
+    #[test]
+    fn masks() {
+        let a: SliceTensor<f64, Shape2D<U2, U3>> =
+            Tensor::from_slice(&[1.0, -2.0, 3.0, -4.0, 5.0, 0.0]);
+        let b: SliceTensor<f64, Shape2D<U2, U3>> =
+            Tensor::from_slice(&[1.0, 2.0, 2.0, 4.0, 5.0, -1.0]);
+
+        let positive = a.scal_gt(0.0);
+        assert_eq!(&positive[..], &[true, false, true, false, true, false]);
+        assert_eq!(&a.eq_elem(&b)[..], &[true, false, false, false, true, false]);
+        assert_eq!(&a.le(&b)[..], &[true, true, false, true, true, false]);
+        assert_eq!(&a.ne_elem_dynamic(&b)[..], &[false, true, true, true, false, true]);
+        // `eq` and `ne` still compare whole tensors.
+        assert!(a.ne(&b));
+        assert!(!a.eq(&b));
+
+        let large = a.abs().scal_ge(3.0);
+        assert_eq!(&positive.and(&large)[..], &[false, false, true, false, true, false]);
+        assert_eq!(&positive.xor(&large)[..], &[true, false, false, true, false, false]);
+        let mut mask = positive.or(&large);
+        mask.not_();
+        assert_eq!(&mask[..], &[false, true, false, false, false, true]);
+        assert_eq!(&positive.not().any::<U1>()[..], &[true, true]);
+        assert_eq!(&positive.all::<U0>()[..], &[false, false, false]);
+        assert_eq!(&mask.any_dynamic::<U0>()[..], &[false, true, true]);
+        assert_eq!(&a.scal_ne(7.0).all::<U1>()[..], &[true, true]);
+
+        // ReLU and broadcasting of the mask and of `other`.
+        let zero: StaticTensor<f64, Shape1D<U1>> = Tensor::default();
+        assert_eq!(&a.where_(&positive, &zero)[..], &[1.0, 0.0, 3.0, 0.0, 5.0, 0.0]);
+
+        let x = Variable::new(a.as_contiguous(), true);
+        let y = Variable::new(b.as_contiguous(), true);
+        let z = Variable::clone(&x).where_(&positive, Variable::clone(&y));
+        z.backward(StaticTensor::fill(1.0));
+        assert_eq!(&x.grad().unwrap()[..], &[1.0, 0.0, 1.0, 0.0, 1.0, 0.0]);
+        assert_eq!(&y.grad().unwrap()[..], &[0.0, 1.0, 0.0, 1.0, 0.0, 1.0]);
+
+        // Mask broadcast along the leading axis.
+        let columns: SliceTensor<bool, Shape2D<U1, U3>> = Tensor::from_slice(&[true, false, true]);
+        let selected: DynamicTensor<f64, Shape2D<U2, U3>> = a.where_dynamic(&columns, &b);
+        assert_eq!(&selected[..], &[1.0, 2.0, 3.0, -4.0, 5.0, 0.0]);
+
+        let x = Variable::new(a, true);
+        let y = Variable::new(b, true);
+        let z = Variable::clone(&x).where_(&columns, Variable::clone(&y));
+        z.backward(StaticTensor::fill(1.0));
+        assert_eq!(&x.grad().unwrap()[..], &[1.0, 0.0, 1.0, 1.0, 0.0, 1.0]);
+        assert_eq!(&y.grad().unwrap()[..], &[0.0, 1.0, 0.0, 0.0, 1.0, 0.0]);
+
+        // The three operands broadcast to a shape larger than the one of `self`.
+        let rows: SliceTensor<f64, Shape2D<U2, U1>> = Tensor::from_slice(&[1.0, 2.0]);
+        let selected = rows.where_(&columns, &zero);
+        assert_eq!(selected.shape(), vec![2, 3]);
+        assert_eq!(&selected[..], &[1.0, 0.0, 1.0, 2.0, 0.0, 2.0]);
+        let selected: DynamicTensor<f64, Shape2D<U2, U3>> = rows.where_dynamic(&columns, &zero);
+        assert_eq!(&selected[..], &[1.0, 0.0, 1.0, 2.0, 0.0, 2.0]);
+
+        let x = Variable::new(rows.as_contiguous(), true);
+        let y = Variable::new(zero, true);
+        let z = Variable::clone(&x).where_(&columns, Variable::clone(&y));
+        z.backward(StaticTensor::fill(1.0));
+        assert_eq!(&x.grad().unwrap()[..], &[2.0, 2.0]);
+        assert_eq!(&y.grad().unwrap()[..], &[2.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot broadcast shapes")]
+    fn where_dynamic_incompatible() {
+        let a: DynamicTensor<f64, Shape2D<Dyn, U3>> = Tensor::alloc(vec![2, 3]);
+        let mask: DynamicTensor<bool, Shape2D<Dyn, U3>> = Tensor::alloc(vec![3, 3]);
+        let _: DynamicTensor<f64, Shape2D<Dyn, U3>> = a.where_dynamic(&mask, &a);
+    }
+
+    #[test]
+    fn clipping() {
+        let a: SliceTensor<f64, Shape1D<U4>> = Tensor::from_slice(&[-2.0, 0.5, 1.0, 3.0]);
+        assert_eq!(&a.clamp(0.0, 1.0)[..], &[0.0, 0.5, 1.0, 1.0]);
+        let mut b = a.as_contiguous();
+        b.clamp_(-1.0, 2.0);
+        assert_eq!(&b[..], &[-1.0, 0.5, 1.0, 2.0]);
+        let pixels: SliceTensor<i32, Shape1D<U4>> = Tensor::from_slice(&[-20, 5, 255, 300]);
+        assert_eq!(&pixels.clamp_dynamic(0, 255)[..], &[0, 5, 255, 255]);
+        let mut c: StaticTensor<u8, Shape1D<U4>> = Tensor::fill(7);
+        c.clamp_(0, 3);
+        assert_eq!(&c[..], &[3; 4]);
+
+        let x = Variable::new(a.as_contiguous(), true);
+        let y = Variable::clone(&x).clamp(0.0, 1.0);
+        y.backward(StaticTensor::fill(1.0));
+        assert_eq!(&x.grad().unwrap()[..], &[0.0, 1.0, 1.0, 0.0]);
+        let z = Variable::new(a.as_contiguous(), true);
+        let w = Variable::clone(&z).clamp_straight_through(0.0, 1.0);
+        w.backward(StaticTensor::fill(1.0));
+        assert_eq!(&z.grad().unwrap()[..], &[1.0; 4]);
+
+        // Global norm of the concatenated gradients is sqrt(2 + 4).
+        let norm = clip_grad_norm(&[&x, &z], 1.0);
+        assert!((norm - 6.0f64.sqrt()).abs() < 1e-12);
+        for (g, e) in x.grad().unwrap().iter().zip([0.0, 1.0, 1.0, 0.0].iter()) {
+            assert!((g - e / 6.0f64.sqrt()).abs() < 1e-6);
+        }
+        assert!((clip_grad_norm(&[&x, &z], 1.0) - 1.0).abs() < 1e-5);
+        let before = z.grad().unwrap();
+        clip_grad_norm(&[&x, &z], 10.0);
+        assert_eq!(z.grad().unwrap(), before);
+
+        let v: SliceTensor<f32, Shape1D<U3>> = Tensor::from_slice(&[1.0, 2.0, 3.0]);
+        let v = Variable::new(v.as_contiguous(), true);
+        let grad: SliceTensor<f32, Shape1D<U3>> = Tensor::from_slice(&[-4.0, 0.25, 4.0]);
+        v.backward(grad);
+        clip_grad_value(&[&v], 1.0);
+        assert_eq!(&v.grad().unwrap()[..], &[-1.0, 0.25, 1.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Clamp bounds must be ordered")]
+    fn clamp_unordered_bounds() {
+        let a: SliceTensor<f64, Shape1D<U4>> = Tensor::from_slice(&[-2.0, 0.5, 1.0, 3.0]);
+        let _b = a.clamp(1.0, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Clamp bounds must be ordered, got 3 and 2")]
+    fn clamp_unordered_integer_bounds() {
+        let mut a: StaticTensor<i64, Shape1D<U4>> = Tensor::fill(1);
+        a.clamp_(3, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Clamp bounds must be ordered, got NaN")]
+    fn clamp_nan_bound() {
+        let mut a: StaticTensor<f32, Shape1D<U4>> = Tensor::fill(1.0);
+        a.clamp_(f32::NAN, 1.0);
+    }
+
+    #[test]
+    fn scan() {
+        let a: SliceTensor<f64, Shape2D<U2, U3>> =
+            Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0, -5.0, 6.0]);
+        assert_eq!(&a.cumsum::<U1>()[..], &[1.0, 3.0, 6.0, 4.0, -1.0, 5.0]);
+        assert_eq!(&a.cumsum::<U0>()[..], &[1.0, 2.0, 3.0, 5.0, -3.0, 9.0]);
+        assert_eq!(&a.cumprod::<U1>()[..], &[1.0, 2.0, 6.0, 4.0, -20.0, -120.0]);
+        assert_eq!(&a.cummax::<U1>()[..], &[1.0, 2.0, 3.0, 4.0, 4.0, 6.0]);
+        assert_eq!(&a.cummin_dynamic::<U1>()[..], &[1.0, 1.0, 1.0, 4.0, -5.0, -5.0]);
+        assert_eq!(&a.transpose().cumsum::<U1>()[..], &[1.0, 5.0, 2.0, -3.0, 3.0, 9.0]);
+        let b: SliceTensor<i32, Shape1D<U4>> = Tensor::from_slice(&[1, 0, 1, 1]);
+        assert_eq!(&b.cumsum_dynamic::<U0>()[..], &[1, 1, 2, 3]);
+
+        let x = Variable::new(a.as_contiguous(), true);
+        let y = Variable::clone(&x).cumsum::<U0, _, _>();
+        y.backward(StaticTensor::fill(1.0));
+        assert_eq!(&x.grad().unwrap()[..], &[2.0, 2.0, 2.0, 1.0, 1.0, 1.0]);
+
+        let x = Variable::new(a.as_contiguous(), true);
+        let y = Variable::clone(&x).cumprod::<U0, _, _>();
+        y.backward(StaticTensor::fill(1.0));
+        assert_eq!(&x.grad().unwrap()[..], &[5.0, -4.0, 7.0, 1.0, 2.0, 3.0]);
+
+        let x = Variable::new(a.as_contiguous(), true);
+        let y = Variable::clone(&x).cumsum::<U1, _, _>();
+        y.backward(StaticTensor::fill(1.0));
+        assert_eq!(&x.grad().unwrap()[..], &[3.0, 2.0, 1.0, 3.0, 2.0, 1.0]);
+
+        let x = Variable::new(a.as_contiguous(), true);
+        let y = Variable::clone(&x).cumprod::<U1, _, _>();
+        y.backward(StaticTensor::fill(1.0));
+        // d/dx0 (x0 + x0 x1 + x0 x1 x2) = 1 + x1 + x1 x2
+        let expected = [9.0, 4.0, 2.0, -34.0, 28.0, -20.0];
+        for (g, e) in x.grad().unwrap().iter().zip(expected.iter()) {
+            assert!((g - e).abs() < 1e-12);
+        }
+
+        // Gradients of `cumprod` are defined for inputs containing zeros.
+        let a: SliceTensor<f64, Shape2D<U2, U3>> =
+            Tensor::from_slice(&[2.0, 0.0, 3.0, 0.0, 0.0, 5.0]);
+        let x = Variable::new(a.as_contiguous(), true);
+        let y = Variable::clone(&x).cumprod::<U1, _, _>();
+        y.backward(StaticTensor::fill(1.0));
+        assert_eq!(&x.grad().unwrap()[..], &[1.0, 8.0, 0.0, 1.0, 0.0, 0.0]);
+        let x = Variable::new(a.as_contiguous(), true);
+        let y = Variable::clone(&x).cumprod::<U0, _, _>();
+        let grad: SliceTensor<f64, Shape2D<U2, U3>> =
+            Tensor::from_slice(&[1.0, 1.0, 1.0, 1.0, 1.0, 2.0]);
+        y.backward(grad.as_contiguous());
+        assert_eq!(&x.grad().unwrap()[..], &[1.0, 1.0, 11.0, 2.0, 0.0, 6.0]);
+    }
+
+    #[test]
+    fn sort() {
+        let a: SliceTensor<f64, Shape2D<U2, U3>> =
+            Tensor::from_slice(&[3.0, 1.0, 2.0, -1.0, 5.0, -1.0]);
+        assert_eq!(
+            &a.sort::<U1>(SortOrder::Ascending)[..],
+            &[1.0, 2.0, 3.0, -1.0, -1.0, 5.0]
+        );
+        assert_eq!(&a.argsort::<U1>(SortOrder::Ascending)[..], &[1, 2, 0, 0, 2, 1]);
+        assert_eq!(&a.argsort::<U1>(SortOrder::Descending)[..], &[0, 2, 1, 1, 0, 2]);
+        assert_eq!(
+            &a.sort_dynamic::<U0>(SortOrder::Ascending)[..],
+            &[-1.0, 1.0, -1.0, 3.0, 5.0, 2.0]
+        );
+        assert_eq!(&a.argsort_dynamic::<U0>(SortOrder::Descending)[..], &[0, 1, 0, 1, 0, 1]);
+
+        let t = a.transpose();
+        assert_eq!(&t.sort::<U1>(SortOrder::Ascending)[..], &[-1.0, 3.0, 1.0, 5.0, -1.0, 2.0]);
+        assert_eq!(&t.argsort::<U0>(SortOrder::Ascending)[..], &[1, 0, 2, 2, 0, 1]);
+
+        let data = [2.0, f64::NAN, 1.0, 2.0];
+        let b: SliceTensor<f64, Shape1D<U4>> = Tensor::from_slice(&data);
+        assert_eq!(&b.argsort::<U0>(SortOrder::Ascending)[..], &[2, 0, 3, 1]);
+        let (values, counts) = b.unique();
+        assert_eq!(&values[..2], &[1.0, 2.0]);
+        assert!(values[2].is_nan());
+        assert_eq!(&counts[..], &[1, 2, 1]);
+    }
+
+    #[test]
+    fn pad() {
+        let data = [1.0, 2.0, 3.0];
+        let a: SliceTensor<f64, Shape1D<U3>> = Tensor::from_slice(&data);
+        assert_eq!(
+            &a.pad::<Shape1D<U2>, Shape1D<U2>>(PadMode::Constant(0.0))[..],
+            &[0.0, 0.0, 1.0, 2.0, 3.0, 0.0, 0.0]
+        );
+        assert_eq!(
+            &a.pad::<Shape1D<U2>, Shape1D<U2>>(PadMode::Reflect)[..],
+            &[3.0, 2.0, 1.0, 2.0, 3.0, 2.0, 1.0]
+        );
+        assert_eq!(
+            &a.pad::<Shape1D<U2>, Shape1D<U2>>(PadMode::Replicate)[..],
+            &[1.0, 1.0, 1.0, 2.0, 3.0, 3.0, 3.0]
+        );
+        assert_eq!(
+            &a.pad_dynamic::<Shape1D<U2>, Shape1D<U2>>(PadMode::Circular)[..],
+            &[2.0, 3.0, 1.0, 2.0, 3.0, 1.0, 2.0]
+        );
+
+        let b: SliceTensor<i32, Shape2D<U2, U2>> = Tensor::from_slice(&[1, 2, 3, 4]);
+        let c: StaticTensor<i32, Shape2D<U3, U4>> =
+            b.transpose().pad::<Shape2D<U1, U0>, Shape2D<U0, U2>>(PadMode::Constant(-1));
+        assert_eq!(&c[..], &[-1, -1, -1, -1, 1, 3, -1, -1, 2, 4, -1, -1]);
+
+        let x = Variable::new(a.as_contiguous(), true);
+        let y = Variable::clone(&x).pad::<Shape1D<U1>, Shape1D<U2>, _, _>(PadMode::Constant(0.0));
+        let data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let g: SliceTensor<f64, Shape1D<U6>> = Tensor::from_slice(&data);
+        y.backward(g.as_contiguous());
+        assert_eq!(&x.grad().unwrap()[..], &[2.0, 3.0, 4.0]);
+
+        let x = Variable::new(a.as_contiguous(), true);
+        let y = Variable::clone(&x).pad::<Shape1D<U1>, Shape1D<U2>, _, _>(PadMode::Reflect);
+        y.backward(g.as_contiguous());
+        assert_eq!(&x.grad().unwrap()[..], &[8.0, 9.0, 4.0]);
+    }
+
+    #[test]
+    fn interpolation() {
+        let data = [1.0, 2.0];
+        let a: SliceTensor<f64, Shape3D<U1, U1, U2>> = Tensor::from_slice(&data);
+        assert_eq!(
+            &a.upsample::<Shape1D<U2>>(Interpolation::Nearest, false)[..],
+            &[1.0, 1.0, 2.0, 2.0]
+        );
+        assert_eq!(
+            &a.interpolate::<Shape3D<U1, U1, U4>>(Interpolation::Linear, false)[..],
+            &[1.0, 1.25, 1.75, 2.0]
+        );
+        assert_eq!(
+            &a.interpolate_dynamic::<Shape3D<U1, U1, U3>>(vec![1, 1, 3], Interpolation::Linear, true)
+                [..],
+            &[1.0, 1.5, 2.0]
+        );
+
+        let data = [1.0, 2.0, 3.0, 4.0];
+        let b: SliceTensor<f64, Shape4D<U1, U1, U2, U2>> = Tensor::from_slice(&data);
+        let expected = [1.0, 1.5, 2.0, 2.0, 2.5, 3.0, 3.0, 3.5, 4.0];
+        assert_eq!(
+            &b.interpolate::<Shape4D<U1, U1, U3, U3>>(Interpolation::Bilinear, true)[..],
+            &expected
+        );
+        let c = b.interpolate::<Shape4D<U1, U1, U3, U3>>(Interpolation::Bicubic, true);
+        for (x, e) in c.iter().zip(expected.iter()) {
+            assert!((x - e).abs() < 1e-12);
+        }
+        assert_eq!(
+            &b.upsample_dynamic::<Shape2D<U1, U2>>(Interpolation::Nearest, false)[..],
+            &[1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0]
+        );
+
+        let x = Variable::new(a.as_contiguous(), true);
+        let y = Variable::clone(&x)
+            .interpolate::<Shape3D<U1, U1, U4>, _, _>(Interpolation::Linear, false);
+        let data = [1.0, 2.0, 3.0, 4.0];
+        let g: SliceTensor<f64, Shape3D<U1, U1, U4>> = Tensor::from_slice(&data);
+        y.backward(g.as_contiguous());
+        assert_eq!(&x.grad().unwrap()[..], &[3.25, 6.75]);
+
+        let x = Variable::new(a.as_contiguous(), true);
+        let y = Variable::clone(&x).upsample::<Shape1D<U2>, _, _>(Interpolation::Nearest, false);
+        y.backward(StaticTensor::fill(1.0));
+        assert_eq!(&x.grad().unwrap()[..], &[2.0, 2.0]);
+    }
+
+    #[test]
+    fn einsum() {
+        let a: SliceTensor<f64, Shape2D<U2, U3>> = Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let b: SliceTensor<f64, Shape2D<U3, U2>> = Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let c = einsum!("ij,jk->ik", &a, &b);
+        assert_eq!(c.shape(), vec![2, 2]);
+        assert_eq!(&c[..], &[22.0, 28.0, 49.0, 64.0]);
+        let c = einsum!("ij,jk", &a, &b);
+        assert_eq!(&c[..], &[22.0, 28.0, 49.0, 64.0]);
+
+        let c = einsum!("ji,jk->ik", &a, &a);
+        assert_eq!(&c[..], &[17.0, 22.0, 27.0, 22.0, 29.0, 36.0, 27.0, 36.0, 45.0]);
+        let c = einsum!("ij,jk->ik", &a.transpose(), &a);
+        assert_eq!(&c[..], &[17.0, 22.0, 27.0, 22.0, 29.0, 36.0, 27.0, 36.0, 45.0]);
+
+        let d: SliceTensor<f64, Shape3D<U2, U2, U2>> =
+            Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+        let e = einsum!("bij,bjk->bik", &d, &d);
+        assert_eq!(&e[..], &[7.0, 10.0, 15.0, 22.0, 67.0, 78.0, 91.0, 106.0]);
+
+        let m: SliceTensor<i32, Shape2D<U3, U3>> = Tensor::from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(einsum!("ii->", &m)[0], 15);
+        assert_eq!(&einsum!("ii->i", &m)[..], &[1, 5, 9]);
+
+        let v: SliceTensor<f64, Shape1D<U3>> = Tensor::from_slice(&[1.0, 2.0, 3.0]);
+        let w: SliceTensor<f64, Shape1D<U3>> = Tensor::from_slice(&[1.0, 0.0, 2.0]);
+        assert_eq!(&einsum!("ij,j,j->i", &a, &v, &w)[..], &[19.0, 40.0]);
+
+        let x = Variable::new(a, true);
+        let y = Variable::new(b, true);
+        let z = einsum!(var "ij,jk->ik", Variable::clone(&x), Variable::clone(&y));
+        z.backward(StaticTensor::fill(1.0));
+        assert_eq!(&x.grad().unwrap()[..], &[3.0, 7.0, 11.0, 3.0, 7.0, 11.0]);
+        assert_eq!(&y.grad().unwrap()[..], &[5.0, 5.0, 7.0, 7.0, 9.0, 9.0]);
+
+        let x = Variable::new(m.as_contiguous(), true);
+        let z = einsum!(var "ii->", Variable::clone(&x));
+        z.backward(StaticTensor::fill(1));
+        assert_eq!(&x.grad().unwrap()[..], &[1, 0, 0, 0, 1, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn tensordot() {
+        let u: SliceTensor<f64, Shape1D<U2>> = Tensor::from_slice(&[1.0, 2.0]);
+        let v: SliceTensor<f64, Shape1D<U3>> = Tensor::from_slice(&[3.0, 4.0, 5.0]);
+        let o = u.outer(&v);
+        assert_eq!(o.shape(), vec![2, 3]);
+        assert_eq!(&o[..], &[3.0, 4.0, 5.0, 6.0, 8.0, 10.0]);
+
+        let a: SliceTensor<f64, Shape2D<U2, U3>> = Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let b: SliceTensor<f64, Shape2D<U3, U2>> = Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let c = a.tensordot::<Shape1D<U1>, Shape1D<U0>, _, _, _, _>(&b);
+        assert_eq!(&c[..], &[22.0, 28.0, 49.0, 64.0]);
+        let c = a.tensordot::<Shape1D<U0>, Shape1D<U0>, _, _, _, _>(&a);
+        assert_eq!(c.shape(), vec![3, 3]);
+        assert_eq!(&c[..], &[17.0, 22.0, 27.0, 22.0, 29.0, 36.0, 27.0, 36.0, 45.0]);
+
+        let d: SliceTensor<f64, Shape3D<U2, U2, U2>> =
+            Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+        let e = d.tensordot::<Shape2D<U1, U2>, Shape2D<U1, U2>, _, _, _, _>(&d);
+        assert_eq!(e.shape(), vec![2, 2]);
+        assert_eq!(&e[..], &[30.0, 70.0, 70.0, 174.0]);
+        let e = d.tensordot::<Shape2D<U2, U1>, Shape2D<U1, U2>, _, _, _, _>(&d);
+        assert_eq!(&e[..], &[29.0, 69.0, 69.0, 173.0]);
+
+        let m: SliceTensor<i32, Shape2D<U2, U2>> = Tensor::from_slice(&[1, 2, 3, 4]);
+        let n: SliceTensor<i32, Shape2D<U2, U2>> = Tensor::from_slice(&[0, 1, 1, 0]);
+        let k = m.kron(&n);
+        assert_eq!(k.shape(), vec![4, 4]);
+        assert_eq!(&k[..], &[0, 1, 0, 2, 1, 0, 2, 0, 0, 3, 0, 4, 3, 0, 4, 0]);
+    }
+
+    #[test]
+    fn broadcasting_ops() {
+        let a: SliceTensor<f64, Shape2D<U3, U1>> = Tensor::from_slice(&[1.0, 2.0, 3.0]);
+        let b: SliceTensor<f64, Shape2D<U1, U4>> = Tensor::from_slice(&[10.0, 20.0, 30.0, 40.0]);
+        let c = a.add_bcast(&b);
+        assert_eq!(c.shape(), vec![3, 4]);
+        assert_eq!(
+            &c[..],
+            &[11.0, 21.0, 31.0, 41.0, 12.0, 22.0, 32.0, 42.0, 13.0, 23.0, 33.0, 43.0]
+        );
+
+        let m: SliceTensor<f64, Shape2D<U2, U3>> = Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let v: SliceTensor<f64, Shape1D<U3>> = Tensor::from_slice(&[10.0, 20.0, 30.0]);
+        assert_eq!(&m.add_bcast(&v)[..], &[11.0, 22.0, 33.0, 14.0, 25.0, 36.0]);
+        assert_eq!(&v.sub_bcast(&m)[..], &[9.0, 18.0, 27.0, 6.0, 15.0, 24.0]);
+        let w: SliceTensor<f64, Shape2D<U2, U1>> = Tensor::from_slice(&[2.0, 3.0]);
+        assert_eq!(&m.mul_bcast(&w)[..], &[2.0, 4.0, 6.0, 12.0, 15.0, 18.0]);
+        assert_eq!(&m.max_bcast(&w)[..], &[2.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        assert_eq!(&m.sum_to::<Shape1D<U3>>()[..], &[5.0, 7.0, 9.0]);
+        assert_eq!(&m.sum_to::<Shape2D<U2, U1>>()[..], &[6.0, 15.0]);
+        let s: DynamicTensor<f64, Shape2D<U1, U3>> = m.sum_to_dynamic(vec![1, 3]);
+        assert_eq!(&s[..], &[5.0, 7.0, 9.0]);
+
+        let x = Variable::new(m.as_contiguous(), true);
+        let y = Variable::new(v.as_contiguous(), true);
+        let z = Variable::clone(&x).mul_bcast(Variable::clone(&y));
+        z.backward(StaticTensor::fill(1.0));
+        assert_eq!(&x.grad().unwrap()[..], &[10.0, 20.0, 30.0, 10.0, 20.0, 30.0]);
+        assert_eq!(&y.grad().unwrap()[..], &[5.0, 7.0, 9.0]);
+
+        let u: SliceTensor<f64, Shape1D<U3>> = Tensor::from_slice(&[1.0, 2.0, 4.0]);
+        let x = Variable::new(m.as_contiguous(), true);
+        let y = Variable::new(u.as_contiguous(), true);
+        let z = Variable::clone(&x).div_bcast(Variable::clone(&y));
+        z.backward(StaticTensor::fill(1.0));
+        assert_eq!(&x.grad().unwrap()[..], &[1.0, 0.5, 0.25, 1.0, 0.5, 0.25]);
+        assert_eq!(&y.grad().unwrap()[..], &[-5.0, -1.75, -0.5625]);
+    }
+
+    #[test]
+    fn operator_overloading() {
+        let a: SliceTensor<f64, Shape2D<U2, U2>> = Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0]);
+        let b: SliceTensor<f64, Shape2D<U2, U2>> = Tensor::from_slice(&[2.0, 1.0, 0.0, 2.0]);
+        assert_eq!(&(&a + &b)[..], &[3.0, 3.0, 3.0, 6.0]);
+        assert_eq!(&(&a - &b)[..], &[-1.0, 1.0, 3.0, 2.0]);
+        assert_eq!(&(&a * &b)[..], &[2.0, 2.0, 0.0, 8.0]);
+        assert_eq!(&(&b / &a)[..], &[2.0, 0.5, 0.0, 0.5]);
+        assert_eq!(&(&(&a * &b) + &a)[..], &[3.0, 4.0, 3.0, 12.0]);
+        assert_eq!(&(&a.transpose() + &b)[..], &[3.0, 4.0, 2.0, 6.0]);
+
+        assert_eq!(&(&a * 2.0)[..], &[2.0, 4.0, 6.0, 8.0]);
+        assert_eq!(&(&a - 1.0)[..], &[0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(&(-&a)[..], &[-1.0, -2.0, -3.0, -4.0]);
+
+        let mut c = a.as_contiguous();
+        c += &b;
+        assert_eq!(&c[..], &[3.0, 3.0, 3.0, 6.0]);
+        c *= &b;
+        assert_eq!(&c[..], &[6.0, 3.0, 0.0, 12.0]);
+        c -= &a.transpose();
+        assert_eq!(&c[..], &[5.0, 0.0, -2.0, 8.0]);
+    }
+
+    #[test]
+    fn indexing() {
+        let a: SliceTensor<f64, Shape2D<U2, U3>> = Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(a[[1, 2]], 6.0);
+        assert_eq!(a.get(&[0, 1]), Some(&2.0));
+        assert_eq!(a.get(&[2, 0]), None);
+        assert_eq!(a.get(&[0]), None);
+
+        let t = a.transpose();
+        assert_eq!(t[[2, 1]], 6.0);
+        assert_eq!(t[[0, 1]], 4.0);
+        assert_eq!(
+            t.iter_logical().copied().collect::<Vec<_>>(),
+            vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]
+        );
+
+        let v: SliceTensor<f64, Shape1D<U3>> = Tensor::from_slice(&[1.0, 2.0, 3.0]);
+        let b = v.broadcast::<Shape2D<U2, U3>>();
+        assert_eq!(b[[1, 2]], 3.0);
+        assert_eq!(
+            b.iter_logical().copied().collect::<Vec<_>>(),
+            vec![1.0, 2.0, 3.0, 1.0, 2.0, 3.0]
+        );
+
+        let indexed: Vec<_> = t.indexed_iter().map(|(i, x)| (i, *x)).collect();
+        assert_eq!(indexed[0], (vec![0, 0], 1.0));
+        assert_eq!(indexed[3], (vec![1, 1], 5.0));
+        assert_eq!(indexed[5], (vec![2, 1], 6.0));
+
+        let mut c = a.as_contiguous();
+        c[[0, 2]] = 10.0;
+        *c.get_mut(&[1, 0]).unwrap() += 1.0;
+        assert_eq!(c.get_mut(&[0, 3]), None);
+        assert_eq!(&c[..], &[1.0, 2.0, 10.0, 5.0, 5.0, 6.0]);
+    }
+
+    #[test]
+    fn mutable_views() {
+        let mut w: StaticTensor<f64, Shape2D<U3, U4>> = StaticTensor::fill(1.0);
+        let update: SliceTensor<f64, Shape2D<U1, U4>> = Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0]);
+        w.narrow_mut::<U0, U1>(1).add_(&update);
+        w.narrow_mut::<U1, U1>(3).scal_mul_(10.0);
+        assert_eq!(
+            &w[..],
+            &[1.0, 1.0, 1.0, 10.0, 2.0, 3.0, 4.0, 50.0, 1.0, 1.0, 1.0, 10.0]
+        );
+
+        let mut cols = w.narrow_mut::<U1, U2>(1);
+        cols[[2, 1]] = 0.0;
+        let ones: SliceTensor<f64, Shape2D<U3, U2>> = Tensor::from_slice(&[1.0; 6]);
+        cols.sub_(&ones);
+        assert_eq!(
+            &w[..],
+            &[1.0, 0.0, 0.0, 10.0, 2.0, 2.0, 3.0, 50.0, 1.0, 0.0, -1.0, 10.0]
+        );
+
+        let mut m: StaticTensor<f64, Shape2D<U2, U3>> = StaticTensor::fill(0.0);
+        let a: SliceTensor<f64, Shape2D<U3, U2>> = Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        m.transpose_mut().add_(&a);
+        assert_eq!(&m[..], &[1.0, 3.0, 5.0, 2.0, 4.0, 6.0]);
+        m.as_view_mut().scal_add_(1.0);
+        assert_eq!(&m[..], &[2.0, 4.0, 6.0, 3.0, 5.0, 7.0]);
+
+        let mut buffer = vec![0.0; 6];
+        {
+            let mut batch: SliceMutTensor<f64, Shape2D<U3, U2>> = Tensor::from_slice_mut(&mut buffer);
+            let sample: SliceTensor<f64, Shape2D<U1, U2>> = Tensor::from_slice(&[7.0, 8.0]);
+            batch.narrow_mut::<U0, U1>(2).add_(&sample);
+            assert_eq!(batch.narrow_mut::<U0, U2>(1).logical_vec(), vec![0.0, 0.0, 7.0, 8.0]);
+        }
+        assert_eq!(buffer, vec![0.0, 0.0, 0.0, 0.0, 7.0, 8.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Chunk size 4 is not a product of the trailing dimensions")]
+    fn misaligned_chunks_mut() {
+        // Chunks of 4 elements would start on every row and overlap.
+        let mut m: StaticTensor<f64, Shape2D<U4, U3>> = StaticTensor::fill(0.0);
+        let mut view = m.as_view_mut();
+        let _chunks: Vec<&mut [f64]> = view.chunks_mut(4).collect();
+    }
+
+    #[test]
+    fn checkpoint_entry_validation() {
+        use crate::io::safetensors::SafeTensors;
+
+        let safetensors = |header: &[u8], data: &[u8]| {
+            let mut buffer = (header.len() as u64).to_le_bytes().to_vec();
+            buffer.extend_from_slice(header);
+            buffer.extend_from_slice(data);
+            buffer
+        };
+        let metadata = r#""__metadata__":{"format":"melange_checkpoint","version":"1","epoch":"0","step":"0"}"#;
+
+        // Empty tensors keep the element size of their data type.
+        let header = format!(
+            r#"{{{},"model.e":{{"dtype":"F32","shape":[0],"data_offsets":[0,0]}}}}"#,
+            metadata
+        );
+        let buffer = safetensors(header.as_bytes(), &[]);
+        let tensors = SafeTensors::deserialize(&buffer).unwrap();
+        assert_eq!(tensors.entry("model.e").unwrap().size, 4);
+        let checkpoint = Checkpoint::read(&buffer).unwrap();
+        let buffer = checkpoint.write(Vec::new()).unwrap();
+        assert_eq!(Checkpoint::read(&buffer).unwrap(), checkpoint);
+
+        // An entry whose data does not match its shape is rejected.
+        let header = format!(
+            r#"{{{},"model.w":{{"dtype":"F32","shape":[3],"data_offsets":[0,8]}}}}"#,
+            metadata
+        );
+        let buffer = safetensors(header.as_bytes(), &[0; 8]);
+        let tensors = SafeTensors::deserialize(&buffer).unwrap();
+        assert!(tensors.entry("model.w").is_err());
+        assert!(Checkpoint::read(&buffer).is_err());
+    }
+
+    #[cfg(feature = "blas")]
+    #[test]
+    fn decomposition() {
+        let close = |x: &[f64], y: &[f64]| {
+            assert_eq!(x.len(), y.len());
+            for (x, y) in x.iter().zip(y.iter()) {
+                assert!((x - y).abs() < 1e-10, "{:?} != {:?}", x, y);
+            }
+        };
+        let identity = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0];
+        let a: SliceTensor<f64, Shape2D<U3, U3>> =
+            Tensor::from_slice(&[4.0, 1.0, 2.0, 0.0, 3.0, 1.0, 1.0, 2.0, 5.0]);
+        let b: SliceTensor<f64, Shape2D<U3, U2>> =
+            Tensor::from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let x = a.solve(&b).unwrap();
+        close(&a.dot(&x)[..], &b[..]);
+        let x = a.transpose().solve(&b).unwrap();
+        close(&a.transpose().dot(&x).as_contiguous()[..], &b[..]);
+        let inverse = a.inverse().unwrap();
+        close(&a.dot(&inverse)[..], &identity);
+        close(&inverse.dot(&a)[..], &identity);
+        assert!((a.det() - 47.0).abs() < 1e-10);
+        assert!((a.transpose().det() - 47.0).abs() < 1e-10);
+
+        let singular: SliceTensor<f64, Shape2D<U3, U3>> =
+            Tensor::from_slice(&[1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 0.0, 1.0, 1.0]);
+        assert!(matches!(singular.solve(&b), Err(LinalgError::Singular(_))));
+        assert!(matches!(singular.inverse(), Err(LinalgError::Singular(_))));
+        assert_eq!(singular.det(), 0.0);
+        let adjugate = a.adjugate().unwrap();
+        let scaled: Vec<f64> = identity.iter().map(|x| 47.0 * x).collect();
+        close(&a.dot(&adjugate)[..], &scaled);
+        let scaled: Vec<f64> = inverse.iter().map(|x| 47.0 * x).collect();
+        close(&adjugate[..], &scaled);
+        close(&singular.dot(&singular.adjugate().unwrap())[..], &[0.0; 9]);
+        let singular_2: SliceTensor<f64, Shape2D<U2, U2>> =
+            Tensor::from_slice(&[1.0, 2.0, 2.0, 4.0]);
+        close(&singular_2.adjugate().unwrap()[..], &[4.0, -2.0, -2.0, 1.0]);
+
+        let spd: SliceTensor<f64, Shape2D<U3, U3>> =
+            Tensor::from_slice(&[4.0, 2.0, 0.0, 2.0, 5.0, 1.0, 0.0, 1.0, 3.0]);
+        let l = spd.cholesky().unwrap();
+        assert_eq!([l[[0, 1]], l[[0, 2]], l[[1, 2]]], [0.0; 3]);
+        close(&l.dot(&l.transpose())[..], &spd[..]);
+        let indefinite: SliceTensor<f64, Shape2D<U2, U2>> =
+            Tensor::from_slice(&[1.0, 2.0, 2.0, 1.0]);
+        assert_eq!(indefinite.cholesky(), Err(LinalgError::NotPositiveDefinite(2)));
+
+        let (p, l, u) = a.lu();
+        assert_eq!([l[[0, 0]], l[[1, 1]], l[[2, 2]]], [1.0; 3]);
+        assert_eq!([l[[0, 1]], u[[1, 0]], u[[2, 1]]], [0.0; 3]);
+        close(&p.dot(&l).dot(&u)[..], &a[..]);
+        let (p, l, u) = singular.lu();
+        close(&p.dot(&l).dot(&u)[..], &singular[..]);
+
+        let (q, r) = a.qr();
+        assert_eq!([r[[1, 0]], r[[2, 0]], r[[2, 1]]], [0.0; 3]);
+        close(&q.dot(&r)[..], &a[..]);
+        close(&q.transpose().dot(&q)[..], &identity);
+
+        let a: SliceTensor<f32, Shape2D<U2, U2>> = Tensor::from_slice(&[2.0, 1.0, 1.0, 3.0]);
+        assert!((a.det() - 5.0).abs() < 1e-5);
+        for (x, y) in a.inverse().unwrap().dot(&a).iter().zip([1.0, 0.0, 0.0, 1.0].iter()) {
+            assert!((x - y).abs() < 1e-6);
+        }
+    }
+
+    #[cfg(feature = "blas")]
+    #[test]
+    fn decomposition_gradients() {
+        // Gradients of `sum(w * f(x))` are compared to central finite differences.
+        fn numerical(x: &[f64], f: impl Fn(&[f64]) -> f64) -> Vec<f64> {
+            let eps = 1e-6;
+            (0..x.len())
+                .map(|i| {
+                    let (mut plus, mut minus) = (x.to_vec(), x.to_vec());
+                    plus[i] += eps;
+                    minus[i] -= eps;
+                    (f(&plus) - f(&minus)) / (2.0 * eps)
+                })
+                .collect()
+        }
+        fn weighted(w: &[f64], y: &[f64]) -> f64 {
+            w.iter().zip(y.iter()).map(|(w, y)| w * y).sum()
+        }
+        let close = |x: &[f64], y: &[f64]| {
+            for (x, y) in x.iter().zip(y.iter()) {
+                assert!((x - y).abs() < 1e-6, "{:?} != {:?}", x, y);
+            }
+        };
+        let a_data = [4.0, 1.0, 2.0, 0.0, 3.0, 1.0, 1.0, 2.0, 5.0];
+        let b_data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let a: SliceTensor<f64, Shape2D<U3, U3>> = Tensor::from_slice(&a_data);
+        let b: SliceTensor<f64, Shape2D<U3, U2>> = Tensor::from_slice(&b_data);
+
+        let w_data = [1.0, -2.0, 0.5, 3.0, -1.0, 2.0];
+        let w: SliceTensor<f64, Shape2D<U3, U2>> = Tensor::from_slice(&w_data);
+        let x = Variable::new(a.as_contiguous(), true);
+        let y = Variable::new(b.as_contiguous(), true);
+        let z = Variable::clone(&x).solve(Variable::clone(&y)).unwrap();
+        z.backward(w.as_contiguous());
+        let expected = numerical(&a_data, |a| {
+            let a: SliceTensor<f64, Shape2D<U3, U3>> = Tensor::from_slice(a);
+            weighted(&w_data, &a.solve(&b).unwrap()[..])
+        });
+        close(&x.grad().unwrap()[..], &expected);
+        let expected = numerical(&b_data, |b| {
+            let b: SliceTensor<f64, Shape2D<U3, U2>> = Tensor::from_slice(b);
+            weighted(&w_data, &a.solve(&b).unwrap()[..])
+        });
+        close(&y.grad().unwrap()[..], &expected);
+
+        let w_data = [1.0, -2.0, 0.5, 3.0, -1.0, 2.0, 0.0, 1.5, -0.5];
+        let w: SliceTensor<f64, Shape2D<U3, U3>> = Tensor::from_slice(&w_data);
+        let x = Variable::new(a.as_contiguous(), true);
+        let z = Variable::clone(&x).inverse().unwrap();
+        z.backward(w.as_contiguous());
+        let expected = numerical(&a_data, |a| {
+            let a: SliceTensor<f64, Shape2D<U3, U3>> = Tensor::from_slice(a);
+            weighted(&w_data, &a.inverse().unwrap()[..])
+        });
+        close(&x.grad().unwrap()[..], &expected);
+
+        let x = Variable::new(a.as_contiguous(), true);
+        let z = Variable::clone(&x).det();
+        z.backward(StaticTensor::fill(2.0));
+        let expected = numerical(&a_data, |a| {
+            let a: SliceTensor<f64, Shape2D<U3, U3>> = Tensor::from_slice(a);
+            2.0 * a.det()
+        });
+        close(&x.grad().unwrap()[..], &expected);
+
+        // The gradient of `det` is the transposed adjugate, even for singular matrices.
+        let singular: SliceTensor<f64, Shape2D<U2, U2>> = Tensor::from_slice(&[1.0, 2.0, 3.0, 6.0]);
+        let x = Variable::new(singular.as_contiguous(), true);
+        let z = Variable::clone(&x).det();
+        z.backward(StaticTensor::fill(2.0));
+        close(&x.grad().unwrap()[..], &[12.0, -6.0, -4.0, 2.0]);
+    }
+
+    #[cfg(feature = "blas")]
+    #[test]
+    fn spectral_decomposition() {
+        let close = |x: &[f64], y: &[f64]| {
+            assert_eq!(x.len(), y.len());
+            for (x, y) in x.iter().zip(y.iter()) {
+                assert!((x - y).abs() < 1e-10, "{:?} != {:?}", x, y);
+            }
+        };
+        let m_data = [3.0, 2.0, 2.0, 2.0, 3.0, -2.0];
+        let m: SliceTensor<f64, Shape2D<U2, U3>> = Tensor::from_slice(&m_data);
+
+        let (u, s, vt) = m.svd().unwrap();
+        close(&s[..], &[5.0, 3.0]);
+        close(&u.dot(&u.transpose())[..], &[1.0, 0.0, 0.0, 1.0]);
+        close(&vt.dot(&vt.transpose())[..], &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+        let reconstructed: Vec<f64> = (0..2)
+            .flat_map(|i| (0..3).map(move |j| (i, j)))
+            .map(|(i, j)| (0..2).map(|l| u[[i, l]] * s[[l]] * vt[[l, j]]).sum())
+            .collect();
+        close(&reconstructed, &m_data);
+
+        let (u, s, vt) = m.svd_thin().unwrap();
+        close(&s[..], &[5.0, 3.0]);
+        close(&vt.dot(&vt.transpose())[..], &[1.0, 0.0, 0.0, 1.0]);
+        let mut us = u.as_contiguous();
+        us.iter_mut().enumerate().for_each(|(i, x)| *x *= s[[i % 2]]);
+        close(&us.dot(&vt)[..], &m_data);
+        let (u, s, vt) = m.transpose().svd_thin().unwrap();
+        close(&s[..], &[5.0, 3.0]);
+        close(&u.transpose().dot(&u)[..], &[1.0, 0.0, 0.0, 1.0]);
+        let mut us = u.as_contiguous();
+        us.iter_mut().enumerate().for_each(|(i, x)| *x *= s[[i % 2]]);
+        close(&us.dot(&vt)[..], &m.transpose().as_contiguous()[..]);
+
+        let p = m.pinv().unwrap();
+        close(&m.dot(&p)[..], &[1.0, 0.0, 0.0, 1.0]);
+        close(&m.dot(&p).dot(&m)[..], &m_data);
+
+        assert_eq!(m.matrix_rank(), Ok(2));
+        let rank_one: SliceTensor<f64, Shape2D<U2, U3>> =
+            Tensor::from_slice(&[1.0, 2.0, 3.0, 2.0, 4.0, 6.0]);
+        assert_eq!(rank_one.matrix_rank(), Ok(1));
+        assert_eq!(rank_one.transpose().matrix_rank(), Ok(1));
+        let p = rank_one.pinv().unwrap();
+        close(&rank_one.dot(&p).dot(&rank_one)[..], &[1.0, 2.0, 3.0, 2.0, 4.0, 6.0]);
+        let zeros: StaticTensor<f32, Shape2D<U3, U2>> = Tensor::default();
+        assert_eq!(zeros.matrix_rank(), Ok(0));
+
+        // The upper triangle is ignored.
+        let a: SliceTensor<f64, Shape2D<U3, U3>> =
+            Tensor::from_slice(&[2.0, 7.0, -3.0, 1.0, 2.0, 7.0, 0.0, 1.0, 2.0]);
+        let (w, v) = a.eigh().unwrap();
+        let sqrt2 = 2f64.sqrt();
+        close(&w[..], &[2.0 - sqrt2, 2.0, 2.0 + sqrt2]);
+        close(&v.transpose().dot(&v)[..], &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+        let symmetric: SliceTensor<f64, Shape2D<U3, U3>> =
+            Tensor::from_slice(&[2.0, 1.0, 0.0, 1.0, 2.0, 1.0, 0.0, 1.0, 2.0]);
+        let mut vw = v.as_contiguous();
+        vw.iter_mut().enumerate().for_each(|(i, x)| *x *= w[[i % 3]]);
+        close(&symmetric.dot(&v)[..], &vw[..]);
+    }
 }
 
+// Allows the code generated by procedural macros to refer to `melange` within the crate.
+extern crate self as melange;
+
 pub mod prelude;
 pub mod tensor;
 pub mod backprop;
 pub mod ring;
+pub mod io;
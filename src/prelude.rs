@@ -1,2 +1,3 @@
 pub use crate::backprop::prelude::*;
 pub use crate::tensor::prelude::*;
+pub use crate::io::prelude::*;
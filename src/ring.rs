@@ -4,9 +4,13 @@
 //! 
 //! It is implemented for all numeric primitive types
 //! thanks to `expand_operations` procedural macro from
-//! `melange_macros` crate.
+//! `melange_macros` crate, for the half-precision
+//! floats `f16` and `bf16` of the `half` crate and for
+//! complex numbers whose parts implement `Ring`.
 
+use half::{bf16, f16};
 use melange_macros::expand_operations;
+use num_complex::Complex;
 use std::ops::*;
 
 pub trait Ring {
@@ -46,3 +50,24 @@ impl<T> Ring for T {
     const ZERO: T = 0;
     const ONE: T = 1;
 }
+
+impl Ring for f16 {
+    const ZERO: f16 = f16::ZERO;
+    const ONE: f16 = f16::ONE;
+}
+
+impl Ring for bf16 {
+    const ZERO: bf16 = bf16::ZERO;
+    const ONE: bf16 = bf16::ONE;
+}
+
+impl<T: Ring> Ring for Complex<T> {
+    const ZERO: Self = Complex {
+        re: T::ZERO,
+        im: T::ZERO,
+    };
+    const ONE: Self = Complex {
+        re: T::ONE,
+        im: T::ZERO,
+    };
+}
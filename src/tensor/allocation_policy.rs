@@ -6,7 +6,7 @@
 //! implement both traits.
 
 use super::heap_layout::HeapLayout;
-use super::layout::{Alloc, LayoutMut};
+use super::layout::{Alloc, Layout, LayoutMut};
 use super::shape::{NumElements, StaticShape};
 use super::stack_layout::StackLayout;
 use super::static_heap_layout::StaticHeapLayout;
@@ -14,13 +14,13 @@ use super::static_heap_layout::StaticHeapLayout;
 /// Trait that defines the `Layout` that should be used with the implementor
 /// policy in the context of statically sized (compile time) tensors.
 pub trait StaticAllocationPolicy<T, S> {
-    type Layout: Default + for<'a> LayoutMut<'a, T>;
+    type Layout: Default + for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>;
 }
 
 /// Trait that defines the `Layout` that should be used with the implementor
 /// policy in the context of dynamically sized (run time) tensors.
 pub trait DynamicAllocationPolicy<T> {
-    type Layout: Alloc + for<'a> LayoutMut<'a, T>;
+    type Layout: Alloc + for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>;
 }
 
 /// This policy uses `StaticHeapLayout` for statically sized tensors
@@ -0,0 +1,121 @@
+//! `cast` contains conversions between tensors of different element types
+//! such as `u8` pixels to `f32` or `f32` labels to `usize`.
+//!
+//! `cast` and `cast_dynamic` follow the semantics of the `as` keyword
+//! through `num_traits::AsPrimitive`: narrowing conversions are lossy,
+//! floats are truncated towards zero and saturate when converted to integers.
+//! `try_cast` and `try_cast_dynamic` rely on `num_traits::NumCast` instead
+//! and return `None` as soon as an element cannot be represented in the
+//! target type.
+//!
+//! Results are contiguous and use the allocation policy of `self`
+//! for the target element type.
+
+use super::allocation_policy::{DynamicAllocationPolicy, StaticAllocationPolicy};
+use super::core_ops::convert;
+use super::layout::{Layout, LayoutMut};
+use super::shape::StaticShape;
+use super::tensor::Tensor;
+use super::transpose_policy::Contiguous;
+use num_traits::{AsPrimitive, NumCast, ToPrimitive};
+use rayon::prelude::*;
+
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    T: Send + Sync + Copy,
+    L: for<'a> Layout<'a, T>,
+{
+    /// Converts each element to `U` as with the `as` keyword.
+    pub fn cast<U>(
+        &self,
+    ) -> Tensor<U, S, Contiguous, <P as StaticAllocationPolicy<U, S>>::Layout, P>
+    where
+        T: AsPrimitive<U>,
+        U: Send + Sync + Copy + 'static,
+        S: StaticShape,
+        P: StaticAllocationPolicy<U, S>,
+    {
+        let mut out: Tensor<U, S, Contiguous, _, P> = Tensor::default();
+        convert(self, &mut out, T::as_);
+
+        out
+    }
+
+    /// Converts each element to `U` as with the `as` keyword
+    /// with a shape known at runtime.
+    pub fn cast_dynamic<U>(
+        &self,
+    ) -> Tensor<U, S, Contiguous, <P as DynamicAllocationPolicy<U>>::Layout, P>
+    where
+        T: AsPrimitive<U>,
+        U: Send + Sync + Copy + 'static,
+        P: DynamicAllocationPolicy<U>,
+    {
+        let mut out: Tensor<U, S, Contiguous, _, P> = Tensor::alloc(self.shape());
+        convert(self, &mut out, T::as_);
+
+        out
+    }
+
+    /// Converts each element to `U`, returning `None` if
+    /// any element is out of the range of `U`.
+    #[allow(clippy::type_complexity)]
+    pub fn try_cast<U>(
+        &self,
+    ) -> Option<Tensor<U, S, Contiguous, <P as StaticAllocationPolicy<U, S>>::Layout, P>>
+    where
+        T: ToPrimitive,
+        U: Send + Sync + NumCast,
+        S: StaticShape,
+        P: StaticAllocationPolicy<U, S>,
+    {
+        let mut out: Tensor<U, S, Contiguous, _, P> = Tensor::default();
+        try_convert(self, &mut out)?;
+
+        Some(out)
+    }
+
+    /// Converts each element to `U` with a shape known at runtime,
+    /// returning `None` if any element is out of the range of `U`.
+    #[allow(clippy::type_complexity)]
+    pub fn try_cast_dynamic<U>(
+        &self,
+    ) -> Option<Tensor<U, S, Contiguous, <P as DynamicAllocationPolicy<U>>::Layout, P>>
+    where
+        T: ToPrimitive,
+        U: Send + Sync + NumCast,
+        P: DynamicAllocationPolicy<U>,
+    {
+        let mut out: Tensor<U, S, Contiguous, _, P> = Tensor::alloc(self.shape());
+        try_convert(self, &mut out)?;
+
+        Some(out)
+    }
+}
+
+/// Writes each element of `input` converted with `NumCast` in `out`,
+/// stopping at the first element that cannot be converted.
+fn try_convert<T, U, S, C, L, P, Lout>(
+    input: &Tensor<T, S, C, L, P>,
+    out: &mut Tensor<U, S, Contiguous, Lout, P>,
+) -> Option<()>
+where
+    T: Send + Sync + Copy + ToPrimitive,
+    U: Send + Sync + NumCast,
+    L: for<'a> Layout<'a, T>,
+    Lout: for<'a> LayoutMut<'a, U>,
+{
+    let chunk_size = input.opt_chunk_size();
+
+    for (chunk_in, chunk_out) in input.chunks(chunk_size).zip(out.chunks_mut(chunk_size)) {
+        chunk_in
+            .par_iter()
+            .zip(chunk_out.par_iter_mut())
+            .try_for_each(|(x, y)| {
+                *y = U::from(*x)?;
+                Some(())
+            })?;
+    }
+
+    Some(())
+}
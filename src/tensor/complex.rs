@@ -0,0 +1,75 @@
+//! `complex` contains the operations that extract real tensors
+//! from tensors of complex numbers `Complex64` and `Complex32`
+//! from the `num-complex` crate: `re`, `im`, `arg` and `norm`.
+//!
+//! Complex tensors support the arithmetic operations of `core_ops`
+//! as well as `conj` and the usual elementwise transcendental functions.
+//! Matrix products are available through `dot`, see `linear_algebra`.
+//!
+//! Results follow the allocation policy of `self`.
+
+use super::allocation_policy::{DynamicAllocationPolicy, StaticAllocationPolicy};
+use super::core_ops::convert;
+use super::layout::Layout;
+use super::shape::StaticShape;
+use super::tensor::Tensor;
+use super::transpose_policy::Contiguous;
+use num_complex::Complex;
+
+macro_rules! impl_complex {
+    ($t:ty, $($name:ident, $name_dynamic:ident, $f:expr, $doc:literal;)*) => {
+        impl<S, C, L, P> Tensor<Complex<$t>, S, C, L, P>
+        where
+            L: for<'a> Layout<'a, Complex<$t>>,
+        {
+            $(
+                #[doc = concat!("Returns the ", $doc, " of each element.")]
+                pub fn $name(
+                    &self,
+                ) -> Tensor<$t, S, Contiguous, <P as StaticAllocationPolicy<$t, S>>::Layout, P>
+                where
+                    S: StaticShape,
+                    P: StaticAllocationPolicy<$t, S>,
+                {
+                    let mut out: Tensor<$t, S, Contiguous, _, P> = Tensor::default();
+                    convert(self, &mut out, $f);
+
+                    out
+                }
+
+                #[doc = concat!(
+                    "Returns the ",
+                    $doc,
+                    " of each element with a shape known at runtime."
+                )]
+                pub fn $name_dynamic(
+                    &self,
+                ) -> Tensor<$t, S, Contiguous, <P as DynamicAllocationPolicy<$t>>::Layout, P>
+                where
+                    P: DynamicAllocationPolicy<$t>,
+                {
+                    let mut out: Tensor<$t, S, Contiguous, _, P> = Tensor::alloc(self.shape());
+                    convert(self, &mut out, $f);
+
+                    out
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! impl_complex_all {
+    ($($t:ty),*) => {
+        $(
+            impl_complex!(
+                $t,
+                re, re_dynamic, |x: Complex<$t>| x.re, "real part";
+                im, im_dynamic, |x: Complex<$t>| x.im, "imaginary part";
+                arg, arg_dynamic, |x: Complex<$t>| x.arg(), "argument in `(-pi, pi]`";
+                norm, norm_dynamic, |x: Complex<$t>| x.norm(), "modulus";
+            );
+        )*
+    };
+}
+
+impl_complex_all!(f64, f32);
@@ -15,7 +15,10 @@
 //! Both families of operations perform ad-hoc parallel computation
 //! acording to how data is stored. Note that operations on more than one
 //! tensors require all tensors to have compatible shapes (all dimensions
-//! must be equal or `Dyn`). If this is not the case consider broadcasting.
+//! must be equal or `Dyn`). If this is not the case consider broadcasting,
+//! either explicitly with `broadcast` or implicitly with the `_bcast`
+//! versions of binary operations that broadcast both operands to the
+//! shape computed at type level by `BroadcastShape`.
 //!
 //! Due to the large amount of duplicate code
 //! between all the operations, this module relies on the `expand_operations`
@@ -25,20 +28,36 @@
 //! replacement with a concrete type like `f64`.
 //!
 //! Note that some methods are only available for tensors based on float
-//! types `f32` and `f64`. This is inherent to how numeric types are treated
-//! in rust.
+//! types `f32` and `f64` and on the half-precision types `f16` and `bf16`
+//! from the `half` crate. This is inherent to how numeric types are treated
+//! in rust. Complex tensors of `Complex64` and `Complex32` support arithmetic
+//! operations and the transcendental functions of `num_complex`.
+//!
+//! Comparisons `eq_elem`, `ne_elem`, `lt`, `le`, `gt` and `ge` (and their
+//! `scal_` counterparts) output `bool` tensors that can be combined with the
+//! logical operations `and`, `or`, `xor` and `not` and used as masks with
+//! `where_`. Element-wise equality is suffixed by `_elem` so that it does not
+//! shadow `PartialEq::eq` and `PartialEq::ne` that compare whole tensors.
 //! 
+//! Operators of `std::ops` are overloaded on tensor references and dispatch
+//! to these methods: `&a + &b` and `&a * 2.0` are functional operations
+//! whereas `a += &b` is in-place, and shapes are still checked at compile
+//! time.
+//!
 //! Please refer to the definition of the scalar version of the mathematical
 //! operation in `std` for more.
 
 use super::allocation_policy::{DynamicAllocationPolicy, StaticAllocationPolicy};
 use super::layout::{Layout, LayoutMut};
-use super::shape::{ReprShape, ReprShapeDyn, Same, StaticShape, TRUE};
+use super::shape::{Broadcast, BroadcastShape, ReprShape, ReprShapeDyn, Same, StaticShape, TRUE};
 use super::tensor::Tensor;
 use super::transpose_policy::Contiguous;
 use crate::ring::Ring;
 use rayon::prelude::*;
+use half::{bf16, f16};
 use melange_macros::expand_operations;
+use num_complex::{Complex32, Complex64};
+use num_traits::Float;
 use std::ops::*;
 
 #[expand_operations(
@@ -47,6 +66,9 @@ use std::ops::*;
     mul<T: Send + Sync + Copy + Mul<Output=T>>,
     div<T: Send + Sync + Copy + Div<Output=T>>,
     rem<T: Send + Sync + Copy + Rem<Output=T>>,
+    bitand<T=bool> as and,
+    bitor<T=bool> as or,
+    bitxor<T=bool> as xor,
     atan2<T=f64>,
     copysign<T=f64>,
     div_euclid<T=f64>,
@@ -59,6 +81,14 @@ use std::ops::*;
     max<T=f32>,
     min<T=f32>,
     rem_euclid<T=f32>,
+    atan2<T=f16>,
+    copysign<T=f16>,
+    max<T=f16>,
+    min<T=f16>,
+    atan2<T=bf16>,
+    copysign<T=bf16>,
+    max<T=bf16>,
+    min<T=bf16>,
     div_euclid<T=u128>,
     rem_euclid<T=u128>,
     div_euclid<T=u64>,
@@ -178,6 +208,65 @@ where
     }
 }
 
+#[expand_operations(
+    add<T: Send + Sync + Copy + Add<Output=T>> as add_bcast,
+    sub<T: Send + Sync + Copy + Sub<Output=T>> as sub_bcast,
+    mul<T: Send + Sync + Copy + Mul<Output=T>> as mul_bcast,
+    div<T: Send + Sync + Copy + Div<Output=T>> as div_bcast,
+    rem<T: Send + Sync + Copy + Rem<Output=T>> as rem_bcast,
+    and<T=bool> as and_bcast,
+    or<T=bool> as or_bcast,
+    xor<T=bool> as xor_bcast,
+    atan2<T=f64> as atan2_bcast,
+    copysign<T=f64> as copysign_bcast,
+    max<T=f64> as max_bcast,
+    min<T=f64> as min_bcast,
+    atan2<T=f32> as atan2_bcast,
+    copysign<T=f32> as copysign_bcast,
+    max<T=f32> as max_bcast,
+    min<T=f32> as min_bcast,
+    atan2<T=f16> as atan2_bcast,
+    copysign<T=f16> as copysign_bcast,
+    max<T=f16> as max_bcast,
+    min<T=f16> as min_bcast,
+    atan2<T=bf16> as atan2_bcast,
+    copysign<T=bf16> as copysign_bcast,
+    max<T=bf16> as max_bcast,
+    min<T=bf16> as min_bcast,
+)]
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    L: for<'a> Layout<'a, T>,
+{
+    /// Applies the operation to `self` and `other` broadcast to
+    /// the shape both broadcast to, e.g. (3, 1) and (1, 4) to (3, 4).
+    #[allow(clippy::type_complexity)]
+    pub fn operation<Srhs, Crhs, Lrhs, Prhs>(
+        &self,
+        other: &Tensor<T, Srhs, Crhs, Lrhs, Prhs>,
+    ) -> Tensor<
+        T,
+        <S as BroadcastShape<Srhs>>::Output,
+        Contiguous,
+        <P as StaticAllocationPolicy<T, <S as BroadcastShape<Srhs>>::Output>>::Layout,
+        P,
+    >
+    where
+        S: StaticShape + BroadcastShape<Srhs> + Broadcast<<S as BroadcastShape<Srhs>>::Output>,
+        <S as Broadcast<<S as BroadcastShape<Srhs>>::Output>>::Output: TRUE,
+        Srhs: StaticShape + Broadcast<<S as BroadcastShape<Srhs>>::Output>,
+        <Srhs as Broadcast<<S as BroadcastShape<Srhs>>::Output>>::Output: TRUE,
+        <S as BroadcastShape<Srhs>>::Output: StaticShape,
+        Lrhs: for<'a> Layout<'a, T>,
+        P: StaticAllocationPolicy<T, <S as BroadcastShape<Srhs>>::Output>,
+    {
+        let lhs = self.broadcast::<<S as BroadcastShape<Srhs>>::Output>();
+        let rhs = other.broadcast::<<S as BroadcastShape<Srhs>>::Output>();
+
+        lhs.placeholder(&rhs)
+    }
+}
+
 #[expand_operations(
     add<T: Send + Sync + Copy + Add<Output=T>>(T) as scal_add,
     sub<T: Send + Sync + Copy + Sub<Output=T>>(T) as scal_sub,
@@ -195,6 +284,14 @@ where
     powf<T=f32>(f32),
     rem_euclid<T=f32>(f32) as scal_rem_euclid,
     powi<T=f32>(i32),
+    max<T=f16>(f16) as scal_max,
+    min<T=f16>(f16) as scal_min,
+    powf<T=f16>(f16),
+    powi<T=f16>(i32),
+    max<T=bf16>(bf16) as scal_max,
+    min<T=bf16>(bf16) as scal_min,
+    powf<T=bf16>(bf16),
+    powi<T=bf16>(i32),
     div_euclid<T=u128>(u128) as scal_div_euclid,
     rem_euclid<T=u128>(u128) as scal_rem_euclid,
     div_euclid<T=u64>(u64) as scal_div_euclid,
@@ -318,6 +415,96 @@ where
     recip<T=f32>,
     to_degrees<T=f32>,
     to_radians<T=f32>,
+    exp<T=f16>,
+    exp2<T=f16>,
+    exp_m1<T=f16>,
+    ln<T=f16>,
+    ln_1p<T=f16>,
+    log2<T=f16>,
+    log10<T=f16>,
+    sin<T=f16>,
+    cos<T=f16>,
+    tan<T=f16>,
+    sinh<T=f16>,
+    cosh<T=f16>,
+    tanh<T=f16>,
+    asin<T=f16>,
+    acos<T=f16>,
+    atan<T=f16>,
+    asinh<T=f16>,
+    acosh<T=f16>,
+    atanh<T=f16>,
+    sqrt<T=f16>,
+    cbrt<T=f16>,
+    abs<T=f16>,
+    signum<T=f16>,
+    ceil<T=f16>,
+    floor<T=f16>,
+    round<T=f16>,
+    recip<T=f16>,
+    to_degrees<T=f16>,
+    to_radians<T=f16>,
+    exp<T=bf16>,
+    exp2<T=bf16>,
+    exp_m1<T=bf16>,
+    ln<T=bf16>,
+    ln_1p<T=bf16>,
+    log2<T=bf16>,
+    log10<T=bf16>,
+    sin<T=bf16>,
+    cos<T=bf16>,
+    tan<T=bf16>,
+    sinh<T=bf16>,
+    cosh<T=bf16>,
+    tanh<T=bf16>,
+    asin<T=bf16>,
+    acos<T=bf16>,
+    atan<T=bf16>,
+    asinh<T=bf16>,
+    acosh<T=bf16>,
+    atanh<T=bf16>,
+    sqrt<T=bf16>,
+    cbrt<T=bf16>,
+    abs<T=bf16>,
+    signum<T=bf16>,
+    ceil<T=bf16>,
+    floor<T=bf16>,
+    round<T=bf16>,
+    recip<T=bf16>,
+    to_degrees<T=bf16>,
+    to_radians<T=bf16>,
+    conj<T=Complex64>,
+    exp<T=Complex64>,
+    ln<T=Complex64>,
+    sqrt<T=Complex64>,
+    sin<T=Complex64>,
+    cos<T=Complex64>,
+    tan<T=Complex64>,
+    sinh<T=Complex64>,
+    cosh<T=Complex64>,
+    tanh<T=Complex64>,
+    asin<T=Complex64>,
+    acos<T=Complex64>,
+    atan<T=Complex64>,
+    asinh<T=Complex64>,
+    acosh<T=Complex64>,
+    atanh<T=Complex64>,
+    conj<T=Complex32>,
+    exp<T=Complex32>,
+    ln<T=Complex32>,
+    sqrt<T=Complex32>,
+    sin<T=Complex32>,
+    cos<T=Complex32>,
+    tan<T=Complex32>,
+    sinh<T=Complex32>,
+    cosh<T=Complex32>,
+    tanh<T=Complex32>,
+    asin<T=Complex32>,
+    acos<T=Complex32>,
+    atan<T=Complex32>,
+    asinh<T=Complex32>,
+    acosh<T=Complex32>,
+    atanh<T=Complex32>,
     abs<T=i128>,
     signum<T=i128>,
     abs<T=i64>,
@@ -329,6 +516,7 @@ where
     abs<T=i8>,
     signum<T=i8>,
     clone<T: Send + Sync + Copy> as as_contiguous,
+    not<T=bool>,
     inv<T: Send + Sync + Copy + Ring + Div<Output = T>>,
 )]
 impl<T, S, C, L, P> Tensor<T, S, C, L, P>
@@ -377,6 +565,8 @@ where
 #[expand_operations(
     mul_add<T=f64>(f64, f64) as scal_mul_add,
     mul_add<T=f32>(f32, f32) as scal_mul_add,
+    mul_add<T=f16>(f16, f16) as scal_mul_add,
+    mul_add<T=bf16>(bf16, bf16) as scal_mul_add,
 )]
 impl<T, S, C, L, P> Tensor<T, S, C, L, P>
 where
@@ -425,9 +615,90 @@ where
     }
 }
 
+#[expand_operations(
+    clamp<T=f64>(f64, f64),
+    clamp<T=f32>(f32, f32),
+    clamp<T=f16>(f16, f16),
+    clamp<T=bf16>(bf16, bf16),
+    clamp<T=u128>(u128, u128),
+    clamp<T=u64>(u64, u64),
+    clamp<T=u32>(u32, u32),
+    clamp<T=u16>(u16, u16),
+    clamp<T=u8>(u8, u8),
+    clamp<T=i128>(i128, i128),
+    clamp<T=i64>(i64, i64),
+    clamp<T=i32>(i32, i32),
+    clamp<T=i16>(i16, i16),
+    clamp<T=i8>(i8, i8),
+)]
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    L: for<'a> Layout<'a, T>,
+{
+    #[inline]
+    fn unchecked<Lout>(
+        &self,
+        min: type0,
+        max: type1,
+        out: &mut Tensor<T, S, Contiguous, Lout, P>,
+    ) where
+        Lout: for<'a> LayoutMut<'a, T>,
+    {
+        assert!(
+            min <= max,
+            "Clamp bounds must be ordered, got {:?} and {:?}.",
+            min,
+            max
+        );
+        let chunk_size = self.opt_chunk_size();
+
+        for (chunk_self, chunk_out) in self.chunks(chunk_size).zip(out.chunks_mut(chunk_size)) {
+            chunk_self
+                .par_iter()
+                .zip(chunk_out.par_iter_mut())
+                .for_each(|(x, y)| *y = (*x).placeholder(min, max));
+        }
+    }
+
+    /// Restricts the values of `self` to `[min, max]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max` or, for floats, if either bound is NaN.
+    pub fn operation(&self, min: type0, max: type1) -> Tensor<T, S, Contiguous, P::Layout, P>
+    where
+        S: StaticShape,
+        P: StaticAllocationPolicy<T, S>,
+    {
+        let mut out = Tensor::default();
+
+        self.unchecked(min, max, &mut out);
+
+        out
+    }
+
+    /// Restricts the values of `self` to `[min, max]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max` or, for floats, if either bound is NaN.
+    pub fn dynamic(&self, min: type0, max: type1) -> Tensor<T, S, Contiguous, P::Layout, P>
+    where
+        P: DynamicAllocationPolicy<T>,
+    {
+        let mut out = Tensor::alloc(self.shape());
+
+        self.unchecked(min, max, &mut out);
+
+        out
+    }
+}
+
 #[expand_operations(
     mul_add<T=f64>,
     mul_add<T=f32>,
+    mul_add<T=f16>,
+    mul_add<T=bf16>,
 )]
 impl<T, S, C, L, P> Tensor<T, S, C, L, P>
 where
@@ -577,22 +848,328 @@ where
     }
 }
 
+#[expand_operations(
+    eq<T: Send + Sync + PartialEq> as eq_elem,
+    ne<T: Send + Sync + PartialEq> as ne_elem,
+    lt<T: Send + Sync + PartialOrd>,
+    le<T: Send + Sync + PartialOrd>,
+    gt<T: Send + Sync + PartialOrd>,
+    ge<T: Send + Sync + PartialOrd>,
+)]
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    L: for<'a> Layout<'a, T>,
+{
+    #[inline]
+    fn unchecked<Srhs, Crhs, Lrhs, Prhs, Sout, Lout>(
+        &self,
+        other: &Tensor<T, Srhs, Crhs, Lrhs, Prhs>,
+        out: &mut Tensor<bool, Sout, Contiguous, Lout, P>,
+    ) where
+        Lrhs: for<'a> Layout<'a, T>,
+        Lout: for<'a> LayoutMut<'a, bool>,
+    {
+        let chunk_size = self.opt_chunk_size().min(other.opt_chunk_size());
+
+        for ((chunk_self, chunk_other), chunk_out) in self
+            .chunks(chunk_size)
+            .zip(other.chunks(chunk_size))
+            .zip(out.chunks_mut(chunk_size))
+        {
+            chunk_self
+                .par_iter()
+                .zip(chunk_other.par_iter())
+                .zip(chunk_out.par_iter_mut())
+                .for_each(|((x, y), z)| *z = x.placeholder(y));
+        }
+    }
+
+    pub fn operation<Crhs, Lrhs, Prhs>(
+        &self,
+        other: &Tensor<T, S, Crhs, Lrhs, Prhs>,
+    ) -> Tensor<bool, S, Contiguous, <P as StaticAllocationPolicy<bool, S>>::Layout, P>
+    where
+        S: StaticShape,
+        P: StaticAllocationPolicy<bool, S>,
+        Lrhs: for<'a> Layout<'a, T>,
+    {
+        let mut out = Tensor::default();
+        self.unchecked(other, &mut out);
+
+        out
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn coerce<Srhs, Crhs, Lrhs, Prhs>(
+        &self,
+        other: &Tensor<T, Srhs, Crhs, Lrhs, Prhs>,
+    ) -> Tensor<
+        bool,
+        <S as ReprShape<bool, Srhs>>::Output,
+        Contiguous,
+        <P as StaticAllocationPolicy<bool, <S as ReprShape<bool, Srhs>>::Output>>::Layout,
+        P,
+    >
+    where
+        S: Same<Srhs> + ReprShape<bool, Srhs>,
+        <S as Same<Srhs>>::Output: TRUE,
+        P: StaticAllocationPolicy<bool, <S as ReprShape<bool, Srhs>>::Output>,
+        Lrhs: for<'a> Layout<'a, T>,
+    {
+        let self_shape = self.shape();
+        let other_shape = other.shape();
+        assert_eq!(
+            self_shape,
+            other_shape,
+            "Tensors must have same shape, got {:?} and {:?}. The use of static shapes (compile-time-known type-level shapes) is strongly recommended.",
+            self_shape,
+            other_shape
+        );
+
+        let mut out = Tensor::default();
+
+        self.unchecked(other, &mut out);
+
+        out
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn dynamic<Srhs, Crhs, Lrhs, Prhs>(
+        &self,
+        other: &Tensor<T, Srhs, Crhs, Lrhs, Prhs>,
+    ) -> Tensor<
+        bool,
+        <S as ReprShapeDyn<bool, Srhs>>::Output,
+        Contiguous,
+        <P as DynamicAllocationPolicy<bool>>::Layout,
+        P,
+    >
+    where
+        S: Same<Srhs> + ReprShapeDyn<bool, Srhs>,
+        <S as Same<Srhs>>::Output: TRUE,
+        P: DynamicAllocationPolicy<bool>,
+        Lrhs: for<'a> Layout<'a, T>,
+    {
+        let self_shape = self.shape();
+        let other_shape = other.shape();
+        assert_eq!(
+            self_shape,
+            other_shape,
+            "Tensors must have same shape, got {:?} and {:?}. The use of static shapes (compile-time-known type-level shapes) is strongly recommended.",
+            self_shape,
+            other_shape
+        );
+
+        let mut out = Tensor::alloc(self_shape);
+
+        self.unchecked(other, &mut out);
+
+        out
+    }
+}
+
+#[expand_operations(
+    eq<T: Send + Sync + PartialEq>(T) as scal_eq,
+    ne<T: Send + Sync + PartialEq>(T) as scal_ne,
+    lt<T: Send + Sync + PartialOrd>(T) as scal_lt,
+    le<T: Send + Sync + PartialOrd>(T) as scal_le,
+    gt<T: Send + Sync + PartialOrd>(T) as scal_gt,
+    ge<T: Send + Sync + PartialOrd>(T) as scal_ge,
+)]
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    L: for<'a> Layout<'a, T>,
+{
+    #[inline]
+    fn unchecked<Lout>(&self, param: type0, out: &mut Tensor<bool, S, Contiguous, Lout, P>)
+    where
+        Lout: for<'a> LayoutMut<'a, bool>,
+    {
+        let chunk_size = self.opt_chunk_size();
+
+        for (chunk_self, chunk_out) in self.chunks(chunk_size).zip(out.chunks_mut(chunk_size)) {
+            chunk_self
+                .par_iter()
+                .zip(chunk_out.par_iter_mut())
+                .for_each(|(x, y)| *y = x.placeholder(&param));
+        }
+    }
+
+    pub fn operation(
+        &self,
+        param: type0,
+    ) -> Tensor<bool, S, Contiguous, <P as StaticAllocationPolicy<bool, S>>::Layout, P>
+    where
+        S: StaticShape,
+        P: StaticAllocationPolicy<bool, S>,
+    {
+        let mut out = Tensor::default();
+
+        self.unchecked(param, &mut out);
+
+        out
+    }
+
+    pub fn dynamic(
+        &self,
+        param: type0,
+    ) -> Tensor<bool, S, Contiguous, <P as DynamicAllocationPolicy<bool>>::Layout, P>
+    where
+        P: DynamicAllocationPolicy<bool>,
+    {
+        let mut out = Tensor::alloc(self.shape());
+
+        self.unchecked(param, &mut out);
+
+        out
+    }
+}
+
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    T: Send + Sync + Copy,
+    L: for<'a> Layout<'a, T>,
+{
+    #[inline]
+    pub(crate) fn where_unchecked<Cm, Lm, Pm, Crhs, Lrhs, Prhs, Lout>(
+        &self,
+        mask: &Tensor<bool, S, Cm, Lm, Pm>,
+        other: &Tensor<T, S, Crhs, Lrhs, Prhs>,
+        out: &mut Tensor<T, S, Contiguous, Lout, P>,
+    ) where
+        Lm: for<'a> Layout<'a, bool>,
+        Lrhs: for<'a> Layout<'a, T>,
+        Lout: for<'a> LayoutMut<'a, T>,
+    {
+        let chunk_size = self
+            .opt_chunk_size()
+            .min(mask.opt_chunk_size())
+            .min(other.opt_chunk_size());
+
+        for (((chunk_self, chunk_mask), chunk_other), chunk_out) in self
+            .chunks(chunk_size)
+            .zip(mask.chunks(chunk_size))
+            .zip(other.chunks(chunk_size))
+            .zip(out.chunks_mut(chunk_size))
+        {
+            chunk_self
+                .par_iter()
+                .zip(chunk_mask.par_iter())
+                .zip(chunk_other.par_iter())
+                .zip(chunk_out.par_iter_mut())
+                .for_each(|(((x, m), y), z)| *z = if *m { *x } else { *y });
+        }
+    }
+
+    /// Selects the elements of `self` where `mask` is true and the elements
+    /// of `other` elsewhere. The three operands are broadcast to the shape `Z`
+    /// they all broadcast to, e.g. (2, 1), (1, 3) and (1,) to (2, 3).
+    #[allow(clippy::type_complexity)]
+    pub fn where_<Z, Zm, Sm, Cm, Lm, Pm, Srhs, Crhs, Lrhs, Prhs>(
+        &self,
+        mask: &Tensor<bool, Sm, Cm, Lm, Pm>,
+        other: &Tensor<T, Srhs, Crhs, Lrhs, Prhs>,
+    ) -> Tensor<T, Z, Contiguous, <P as StaticAllocationPolicy<T, Z>>::Layout, P>
+    where
+        S: StaticShape + BroadcastShape<Sm, Output = Zm> + Broadcast<Z>,
+        <S as Broadcast<Z>>::Output: TRUE,
+        Zm: BroadcastShape<Srhs, Output = Z>,
+        Sm: StaticShape + Broadcast<Z>,
+        <Sm as Broadcast<Z>>::Output: TRUE,
+        Srhs: StaticShape + Broadcast<Z>,
+        <Srhs as Broadcast<Z>>::Output: TRUE,
+        Z: StaticShape,
+        Lm: for<'a> Layout<'a, bool>,
+        Lrhs: for<'a> Layout<'a, T>,
+        P: StaticAllocationPolicy<T, Z>,
+    {
+        let mut out = Tensor::default();
+        self.broadcast::<Z>()
+            .where_unchecked(&mask.broadcast(), &other.broadcast(), &mut out);
+
+        out
+    }
+
+    /// Selects the elements of `self` where `mask` is true and the elements
+    /// of `other` elsewhere with shapes checked at runtime.
+    /// The three operands are broadcast to the shape they all broadcast to,
+    /// `Z` being the corresponding type-level shape.
+    ///
+    /// # Panics
+    /// If the shapes of the operands do not broadcast to a common shape.
+    #[allow(clippy::type_complexity)]
+    pub fn where_dynamic<Z, Sm, Cm, Lm, Pm, Srhs, Crhs, Lrhs, Prhs>(
+        &self,
+        mask: &Tensor<bool, Sm, Cm, Lm, Pm>,
+        other: &Tensor<T, Srhs, Crhs, Lrhs, Prhs>,
+    ) -> Tensor<T, Z, Contiguous, <P as DynamicAllocationPolicy<T>>::Layout, P>
+    where
+        S: Broadcast<Z>,
+        <S as Broadcast<Z>>::Output: TRUE,
+        Sm: Broadcast<Z>,
+        <Sm as Broadcast<Z>>::Output: TRUE,
+        Srhs: Broadcast<Z>,
+        <Srhs as Broadcast<Z>>::Output: TRUE,
+        Lm: for<'a> Layout<'a, bool>,
+        Lrhs: for<'a> Layout<'a, T>,
+        P: DynamicAllocationPolicy<T>,
+    {
+        let shape = broadcast_shapes(&[self.shape(), mask.shape(), other.shape()]);
+        let mut out = Tensor::alloc(shape.clone());
+        self.broadcast_dynamic::<Z>(shape.clone()).where_unchecked(
+            &mask.broadcast_dynamic(shape.clone()),
+            &other.broadcast_dynamic(shape),
+            &mut out,
+        );
+
+        out
+    }
+}
+
+/// Computes at runtime the shape `shapes` all broadcast to.
+/// Shapes are aligned on their last axes and missing leading axes
+/// are considered to be 1.
+///
+/// # Panics
+/// If dimensions on an axis are neither equal nor 1.
+fn broadcast_shapes(shapes: &[Vec<usize>]) -> Vec<usize> {
+    let len = shapes.iter().map(|x| x.len()).max().unwrap_or(0);
+    let mut out = vec![1; len];
+    for shape in shapes {
+        for (x, y) in out.iter_mut().rev().zip(shape.iter().rev()) {
+            assert!(
+                *x == 1 || *y == 1 || *x == *y,
+                "Cannot broadcast shapes {:?}.",
+                shapes
+            );
+            if *y != 1 {
+                *x = *y;
+            }
+        }
+    }
+
+    out
+}
+
 #[expand_operations(
     add_assign<T: Send + Sync + Copy + AddAssign> as add,
     sub_assign<T: Send + Sync + Copy + SubAssign> as sub,
     mul_assign<T: Send + Sync + Copy + MulAssign> as mul,
     div_assign<T: Send + Sync + Copy + DivAssign> as div,
     rem_assign<T: Send + Sync + Copy + RemAssign> as rem,
+    bitand_assign<T=bool> as and,
+    bitor_assign<T=bool> as or,
+    bitxor_assign<T=bool> as xor,
 )]
 impl<T, S, C, L, P> Tensor<T, S, C, L, P>
 where
-    L: for<'a> LayoutMut<'a, T>,
+    L: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>,
 {
     fn unchecked_<Crhs, Lrhs, Prhs>(&mut self, other: &Tensor<T, S, Crhs, Lrhs, Prhs>)
     where
         Lrhs: for<'a> Layout<'a, T>,
     {
-        let chunk_size = other.opt_chunk_size();
+        let chunk_size = self.opt_chunk_size().min(other.opt_chunk_size());
 
         for (chunk_self, chunk_other) in self.chunks_mut(chunk_size).zip(other.chunks(chunk_size)) {
             chunk_self
@@ -639,6 +1216,14 @@ where
     max<T=f32>,
     min<T=f32>,
     rem_euclid<T=f32>,
+    atan2<T=f16>,
+    copysign<T=f16>,
+    max<T=f16>,
+    min<T=f16>,
+    atan2<T=bf16>,
+    copysign<T=bf16>,
+    max<T=bf16>,
+    min<T=bf16>,
     div_euclid<T=u128>,
     rem_euclid<T=u128>,
     div_euclid<T=u64>,
@@ -662,13 +1247,13 @@ where
 )]
 impl<T, S, C, L, P> Tensor<T, S, C, L, P>
 where
-    L: for<'a> LayoutMut<'a, T>,
+    L: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>,
 {
     fn unchecked_<Crhs, Lrhs, Prhs>(&mut self, other: &Tensor<T, S, Crhs, Lrhs, Prhs>)
     where
         Lrhs: for<'a> Layout<'a, T>,
     {
-        let chunk_size = other.opt_chunk_size();
+        let chunk_size = self.opt_chunk_size().min(other.opt_chunk_size());
 
         for (chunk_self, chunk_other) in self.chunks_mut(chunk_size).zip(other.chunks(chunk_size)) {
             chunk_self
@@ -719,6 +1304,14 @@ where
     powf<T=f32>(f32),
     rem_euclid<T=f32>(f32) as scal_rem_euclid,
     powi<T=f32>(i32),
+    max<T=f16>(f16) as scal_max,
+    min<T=f16>(f16) as scal_min,
+    powf<T=f16>(f16),
+    powi<T=f16>(i32),
+    max<T=bf16>(bf16) as scal_max,
+    min<T=bf16>(bf16) as scal_min,
+    powf<T=bf16>(bf16),
+    powi<T=bf16>(i32),
     div_euclid<T=u128>(u128) as scal_div_euclid,
     rem_euclid<T=u128>(u128) as scal_rem_euclid,
     div_euclid<T=u64>(u64) as scal_div_euclid,
@@ -814,6 +1407,96 @@ where
     recip<T=f32>,
     to_degrees<T=f32>,
     to_radians<T=f32>,
+    exp<T=f16>,
+    exp2<T=f16>,
+    exp_m1<T=f16>,
+    ln<T=f16>,
+    ln_1p<T=f16>,
+    log2<T=f16>,
+    log10<T=f16>,
+    sin<T=f16>,
+    cos<T=f16>,
+    tan<T=f16>,
+    sinh<T=f16>,
+    cosh<T=f16>,
+    tanh<T=f16>,
+    asin<T=f16>,
+    acos<T=f16>,
+    atan<T=f16>,
+    asinh<T=f16>,
+    acosh<T=f16>,
+    atanh<T=f16>,
+    sqrt<T=f16>,
+    cbrt<T=f16>,
+    abs<T=f16>,
+    signum<T=f16>,
+    ceil<T=f16>,
+    floor<T=f16>,
+    round<T=f16>,
+    recip<T=f16>,
+    to_degrees<T=f16>,
+    to_radians<T=f16>,
+    exp<T=bf16>,
+    exp2<T=bf16>,
+    exp_m1<T=bf16>,
+    ln<T=bf16>,
+    ln_1p<T=bf16>,
+    log2<T=bf16>,
+    log10<T=bf16>,
+    sin<T=bf16>,
+    cos<T=bf16>,
+    tan<T=bf16>,
+    sinh<T=bf16>,
+    cosh<T=bf16>,
+    tanh<T=bf16>,
+    asin<T=bf16>,
+    acos<T=bf16>,
+    atan<T=bf16>,
+    asinh<T=bf16>,
+    acosh<T=bf16>,
+    atanh<T=bf16>,
+    sqrt<T=bf16>,
+    cbrt<T=bf16>,
+    abs<T=bf16>,
+    signum<T=bf16>,
+    ceil<T=bf16>,
+    floor<T=bf16>,
+    round<T=bf16>,
+    recip<T=bf16>,
+    to_degrees<T=bf16>,
+    to_radians<T=bf16>,
+    conj<T=Complex64>,
+    exp<T=Complex64>,
+    ln<T=Complex64>,
+    sqrt<T=Complex64>,
+    sin<T=Complex64>,
+    cos<T=Complex64>,
+    tan<T=Complex64>,
+    sinh<T=Complex64>,
+    cosh<T=Complex64>,
+    tanh<T=Complex64>,
+    asin<T=Complex64>,
+    acos<T=Complex64>,
+    atan<T=Complex64>,
+    asinh<T=Complex64>,
+    acosh<T=Complex64>,
+    atanh<T=Complex64>,
+    conj<T=Complex32>,
+    exp<T=Complex32>,
+    ln<T=Complex32>,
+    sqrt<T=Complex32>,
+    sin<T=Complex32>,
+    cos<T=Complex32>,
+    tan<T=Complex32>,
+    sinh<T=Complex32>,
+    cosh<T=Complex32>,
+    tanh<T=Complex32>,
+    asin<T=Complex32>,
+    acos<T=Complex32>,
+    atan<T=Complex32>,
+    asinh<T=Complex32>,
+    acosh<T=Complex32>,
+    atanh<T=Complex32>,
     abs<T=i128>,
     signum<T=i128>,
     abs<T=i64>,
@@ -824,6 +1507,7 @@ where
     signum<T=i16>,
     abs<T=i8>,
     signum<T=i8>,
+    not<T=bool>,
     inv<T: Send + Sync + Copy + Ring + Div<Output = T>>,
 )]
 impl<T, S, C, L, P> Tensor<T, S, C, L, P>
@@ -842,6 +1526,8 @@ where
 #[expand_operations(
     mul_add<T=f64>(f64, f64) as scal_mul_add,
     mul_add<T=f32>(f32, f32) as scal_mul_add,
+    mul_add<T=f16>(f16, f16) as scal_mul_add,
+    mul_add<T=bf16>(bf16, bf16) as scal_mul_add,
 )]
 impl<T, S, C, L, P> Tensor<T, S, C, L, P>
 where
@@ -858,13 +1544,57 @@ where
     }
 }
 
+#[expand_operations(
+    clamp<T=f64>(f64, f64),
+    clamp<T=f32>(f32, f32),
+    clamp<T=f16>(f16, f16),
+    clamp<T=bf16>(bf16, bf16),
+    clamp<T=u128>(u128, u128),
+    clamp<T=u64>(u64, u64),
+    clamp<T=u32>(u32, u32),
+    clamp<T=u16>(u16, u16),
+    clamp<T=u8>(u8, u8),
+    clamp<T=i128>(i128, i128),
+    clamp<T=i64>(i64, i64),
+    clamp<T=i32>(i32, i32),
+    clamp<T=i16>(i16, i16),
+    clamp<T=i8>(i8, i8),
+)]
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    L: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>,
+{
+    /// Restricts the values of `self` to `[min, max]` in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max` or, for floats, if either bound is NaN.
+    pub fn operation_(&mut self, min: type0, max: type1) {
+        assert!(
+            min <= max,
+            "Clamp bounds must be ordered, got {:?} and {:?}.",
+            min,
+            max
+        );
+        let chunk_size = self.opt_chunk_size();
+
+        for chunk_self in self.chunks_mut(chunk_size) {
+            chunk_self
+                .par_iter_mut()
+                .for_each(|x| *x = (*x).placeholder(min, max));
+        }
+    }
+}
+
 #[expand_operations(
     mul_add<T=f64>,
     mul_add<T=f32>,
+    mul_add<T=f16>,
+    mul_add<T=bf16>,
 )]
 impl<T, S, C, L, P> Tensor<T, S, C, L, P>
 where
-    L: for<'a> LayoutMut<'a, T>,
+    L: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>,
 {
     #[inline]
     fn unchecked_<Srhs1, Crhs1, Lrhs1, Prhs1, Srhs2, Crhs2, Lrhs2, Prhs2>(
@@ -875,7 +1605,10 @@ where
         Lrhs1: for<'a> Layout<'a, T>,
         Lrhs2: for<'a> Layout<'a, T>,
     {
-        let chunk_size = other1.opt_chunk_size().min(other2.opt_chunk_size());
+        let chunk_size = self
+            .opt_chunk_size()
+            .min(other1.opt_chunk_size())
+            .min(other2.opt_chunk_size());
 
         for ((chunk_self, chunk_other1), chunk_other2) in self
             .chunks_mut(chunk_size)
@@ -932,3 +1665,94 @@ where
         self.unchecked_(other1, other2);
     }
 }
+
+#[expand_operations(
+    add<T: Send + Sync + Copy + Add<Output=T>> in Add,
+    sub<T: Send + Sync + Copy + Sub<Output=T>> in Sub,
+    mul<T: Send + Sync + Copy + Mul<Output=T>> in Mul,
+    div<T: Send + Sync + Copy + Div<Output=T>> in Div,
+)]
+impl<'a, T, S, C, L, P, Crhs, Lrhs, Prhs> ImplTrait<&'a Tensor<T, S, Crhs, Lrhs, Prhs>>
+    for &Tensor<T, S, C, L, P>
+where
+    S: StaticShape,
+    L: for<'b> Layout<'b, T>,
+    P: StaticAllocationPolicy<T, S>,
+    Lrhs: for<'b> Layout<'b, T>,
+{
+    type Output = Tensor<T, S, Contiguous, P::Layout, P>;
+    fn operation(self, other: &'a Tensor<T, S, Crhs, Lrhs, Prhs>) -> Self::Output {
+        self.placeholder(other)
+    }
+}
+
+#[expand_operations(
+    scal_add<T: Send + Sync + Copy + Add<Output=T>> as add in Add,
+    scal_sub<T: Send + Sync + Copy + Sub<Output=T>> as sub in Sub,
+    scal_mul<T: Send + Sync + Copy + Mul<Output=T>> as mul in Mul,
+    scal_div<T: Send + Sync + Copy + Div<Output=T>> as div in Div,
+)]
+impl<T, S, C, L, P> ImplTrait<T> for &Tensor<T, S, C, L, P>
+where
+    S: StaticShape,
+    L: for<'b> Layout<'b, T>,
+    P: StaticAllocationPolicy<T, S>,
+{
+    type Output = Tensor<T, S, Contiguous, P::Layout, P>;
+    fn operation(self, param: T) -> Self::Output {
+        self.placeholder(param)
+    }
+}
+
+impl<T, S, C, L, P> Neg for &Tensor<T, S, C, L, P>
+where
+    T: Send + Sync + Copy + Ring + Neg<Output = T> + Mul<Output = T>,
+    S: StaticShape,
+    L: for<'b> Layout<'b, T>,
+    P: StaticAllocationPolicy<T, S>,
+{
+    type Output = Tensor<T, S, Contiguous, P::Layout, P>;
+    fn neg(self) -> Self::Output {
+        self.scal_mul(-T::ONE)
+    }
+}
+
+#[expand_operations(
+    add_<T: Send + Sync + Copy + AddAssign> as add in AddAssign,
+    sub_<T: Send + Sync + Copy + SubAssign> as sub in SubAssign,
+    mul_<T: Send + Sync + Copy + MulAssign> as mul in MulAssign,
+    div_<T: Send + Sync + Copy + DivAssign> as div in DivAssign,
+)]
+impl<'a, T, S, C, L, P, Crhs, Lrhs, Prhs> ImplTrait<&'a Tensor<T, S, Crhs, Lrhs, Prhs>>
+    for Tensor<T, S, C, L, P>
+where
+    S: StaticShape,
+    L: for<'b> Layout<'b, T> + for<'b> LayoutMut<'b, T>,
+    Lrhs: for<'b> Layout<'b, T>,
+{
+    fn operation_assign(&mut self, other: &'a Tensor<T, S, Crhs, Lrhs, Prhs>) {
+        self.placeholder(other);
+    }
+}
+
+/// Writes `f(x)` in `out` for each element `x` of `input` in logical order.
+pub(crate) fn convert<T, U, S, C, L, P, Lout, F>(
+    input: &Tensor<T, S, C, L, P>,
+    out: &mut Tensor<U, S, Contiguous, Lout, P>,
+    f: F,
+) where
+    T: Send + Sync + Copy,
+    U: Send + Sync,
+    L: for<'a> Layout<'a, T>,
+    Lout: for<'a> LayoutMut<'a, U>,
+    F: Fn(T) -> U + Send + Sync,
+{
+    let chunk_size = input.opt_chunk_size();
+
+    for (chunk_in, chunk_out) in input.chunks(chunk_size).zip(out.chunks_mut(chunk_size)) {
+        chunk_in
+            .par_iter()
+            .zip(chunk_out.par_iter_mut())
+            .for_each(|(x, y)| *y = f(*x));
+    }
+}
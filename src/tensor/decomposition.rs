@@ -0,0 +1,1012 @@
+//! `decomposition` contains matrix factorizations and the operations
+//! that derive from them: linear system solving, inversion and determinant.
+//! It is backed by the LAPACK routines bundled with openblas
+//! through the `Lapack` trait that is implemented for `f64` and `f32`
+//! and is therefore only available with the `blas` feature.
+//!
+//! Operations are available for square matrices of type-level shape
+//! `Shape2D<N, N>` which guarantees squareness at compile time, except
+//! for the singular value decomposition and the operations built on top
+//! of it (`pinv` and `matrix_rank`) that accept any `Shape2D<M, N>`.
+//! The number of singular values `min(M, N)` is computed at the type level
+//! by the `MinDim` shape operator.
+//!
+//! Operations that are not defined for some inputs return a `Result`
+//! with a `LinalgError`: `solve` and `inverse` fail on singular matrices,
+//! `cholesky` fails on matrices that are not positive definite and
+//! iterative algorithms (`svd`, `eigh`) fail if they do not converge.
+//!
+//! LAPACK works with column-major matrices whereas tensors are row-major.
+//! The data of a row-major matrix being the data of its transpose in
+//! column-major order, operations that are invariant by transposition
+//! (determinant, inversion) are directly applied to a copy of the data
+//! and other operations transpose their inputs and outputs.
+//!
+//! Note that the matrix inverse is called `inverse` because `inv`
+//! already is the element-wise multiplicative inverse.
+
+use super::allocation_policy::StaticAllocationPolicy;
+use super::layout::Layout;
+use super::shape::{MinDim, Shape1D, Shape2D};
+use super::tensor::Tensor;
+use super::transpose_policy::Contiguous;
+use crate::ring::Ring;
+use std::error::Error;
+use std::fmt;
+use std::ops::{AddAssign, Mul, Neg};
+use std::os::raw::c_char;
+use typenum::Unsigned;
+
+/// Matrix allocated with the allocation policy `P`.
+pub type Matrix<T, M, N, P> = Tensor<
+    T,
+    Shape2D<M, N>,
+    Contiguous,
+    <P as StaticAllocationPolicy<T, Shape2D<M, N>>>::Layout,
+    P,
+>;
+
+/// Vector allocated with the allocation policy `P`.
+pub type Vector<T, N, P> =
+    Tensor<T, Shape1D<N>, Contiguous, <P as StaticAllocationPolicy<T, Shape1D<N>>>::Layout, P>;
+
+/// Number of singular values of a `M` x `N` matrix: `min(M, N)`.
+pub type MinOf<M, N> = <Shape2D<M, N> as MinDim>::Output;
+
+/// `U`, singular values and `V^T` returned by `svd`.
+pub type SvdFactors<T, M, N, P> = (
+    Matrix<T, M, M, P>,
+    Vector<T, MinOf<M, N>, P>,
+    Matrix<T, N, N, P>,
+);
+
+/// `U`, singular values and `V^T` returned by `svd_thin`.
+pub type ThinSvdFactors<T, M, N, P> = (
+    Matrix<T, M, MinOf<M, N>, P>,
+    Vector<T, MinOf<M, N>, P>,
+    Matrix<T, MinOf<M, N>, N, P>,
+);
+
+/// Eigenvalues and eigenvectors returned by `eigh`.
+pub type EighFactors<T, N, P> = (Vector<T, N, P>, Matrix<T, N, N, P>);
+
+/// `P`, `L` and `U` factors returned by `lu`.
+pub type LuFactors<T, N, P> = (Matrix<T, N, N, P>, Matrix<T, N, N, P>, Matrix<T, N, N, P>);
+
+/// `Q` and `R` factors returned by `qr`.
+pub type QrFactors<T, N, P> = (Matrix<T, N, N, P>, Matrix<T, N, N, P>);
+
+/// Errors returned by operations that are not defined for their input.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LinalgError {
+    /// The matrix is singular: the diagonal element at the given index
+    /// of the `U` factor of its LU factorization is exactly zero.
+    Singular(usize),
+    /// The matrix is not positive definite: its leading minor
+    /// of the given order is not positive.
+    NotPositiveDefinite(usize),
+    /// An iterative algorithm did not converge, the given number
+    /// of intermediate values did not converge to zero.
+    NoConvergence(usize),
+}
+
+impl fmt::Display for LinalgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinalgError::Singular(index) => write!(
+                f,
+                "Matrix is singular, U[{0}, {0}] is zero in its LU factorization.",
+                index
+            ),
+            LinalgError::NotPositiveDefinite(order) => write!(
+                f,
+                "Matrix is not positive definite, its leading minor of order {} is not positive.",
+                order
+            ),
+            LinalgError::NoConvergence(count) => write!(
+                f,
+                "Decomposition did not converge, {} intermediate values did not converge to zero.",
+                count
+            ),
+        }
+    }
+}
+
+impl Error for LinalgError {}
+
+/// Scalar types supported by LAPACK.
+///
+/// Methods are thin wrappers around the LAPACK routines of the same name
+/// (prefixed by `d` or `s`) and work with column-major matrices whose
+/// leading dimension is their number of rows. They return LAPACK's `info`.
+/// Passing `-1` as `lwork` queries the optimal workspace size
+/// that is written in `work[0]`.
+///
+/// Slices are passed to Fortran code as raw pointers, wrappers
+/// therefore panic if they are too small for the given dimensions.
+pub trait Lapack:
+    Ring
+    + Copy
+    + Default
+    + PartialEq
+    + Mul<Output = Self>
+    + AddAssign
+    + Neg<Output = Self>
+    + Into<f64>
+    + 'static
+{
+    /// LU factorization with partial pivoting.
+    fn getrf(m: i32, n: i32, a: &mut [Self], ipiv: &mut [i32]) -> i32;
+    /// Inverse from an LU factorization.
+    fn getri(n: i32, a: &mut [Self], ipiv: &[i32], work: &mut [Self], lwork: i32) -> i32;
+    /// Solves a linear system from an LU factorization,
+    /// `trans` is `b'N'` or `b'T'` to solve with the transpose.
+    fn getrs(trans: u8, n: i32, nrhs: i32, a: &[Self], ipiv: &[i32], b: &mut [Self]) -> i32;
+    /// Cholesky factorization, `uplo` is `b'U'` or `b'L'`.
+    fn potrf(uplo: u8, n: i32, a: &mut [Self]) -> i32;
+    /// QR factorization with Householder reflectors.
+    fn geqrf(
+        m: i32,
+        n: i32,
+        a: &mut [Self],
+        tau: &mut [Self],
+        work: &mut [Self],
+        lwork: i32,
+    ) -> i32;
+    /// Generates Q from the reflectors returned by `geqrf`.
+    fn orgqr(
+        m: i32,
+        n: i32,
+        k: i32,
+        a: &mut [Self],
+        tau: &[Self],
+        work: &mut [Self],
+        lwork: i32,
+    ) -> i32;
+    /// Singular value decomposition with a divide and conquer algorithm,
+    /// `jobz` is `b'A'` to compute all the columns of `U` and rows of `V^T`
+    /// or `b'S'` to compute only the first `min(m, n)` ones.
+    #[allow(clippy::too_many_arguments)]
+    fn gesdd(
+        jobz: u8,
+        m: i32,
+        n: i32,
+        a: &mut [Self],
+        s: &mut [Self],
+        u: &mut [Self],
+        ldu: i32,
+        vt: &mut [Self],
+        ldvt: i32,
+        work: &mut [Self],
+        lwork: i32,
+        iwork: &mut [i32],
+    ) -> i32;
+    /// Eigenvalues in ascending order and, if `jobz` is `b'V'`, eigenvectors
+    /// of a symmetric matrix of which only the `uplo` triangle is read.
+    fn syev(
+        jobz: u8,
+        uplo: u8,
+        n: i32,
+        a: &mut [Self],
+        w: &mut [Self],
+        work: &mut [Self],
+        lwork: i32,
+    ) -> i32;
+    /// Machine epsilon.
+    fn epsilon() -> Self;
+    /// Conversion from `f64`.
+    fn from_f64(x: f64) -> Self;
+}
+
+/// Panics if a slice passed to LAPACK is smaller than required.
+pub(crate) fn check_len(name: &str, len: usize, required: i32) {
+    assert!(
+        len >= required.max(1) as usize,
+        "LAPACK argument `{}` should have at least {} elements, got {}.",
+        name,
+        required,
+        len
+    );
+}
+
+macro_rules! impl_lapack {
+    (
+        $t:ty,
+        $getrf:ident,
+        $getri:ident,
+        $getrs:ident,
+        $potrf:ident,
+        $geqrf:ident,
+        $orgqr:ident,
+        $gesdd:ident,
+        $syev:ident
+    ) => {
+        extern "C" {
+            fn $getrf(
+                m: *const i32,
+                n: *const i32,
+                a: *mut $t,
+                lda: *const i32,
+                ipiv: *mut i32,
+                info: *mut i32,
+            );
+            fn $getri(
+                n: *const i32,
+                a: *mut $t,
+                lda: *const i32,
+                ipiv: *const i32,
+                work: *mut $t,
+                lwork: *const i32,
+                info: *mut i32,
+            );
+            fn $getrs(
+                trans: *const c_char,
+                n: *const i32,
+                nrhs: *const i32,
+                a: *const $t,
+                lda: *const i32,
+                ipiv: *const i32,
+                b: *mut $t,
+                ldb: *const i32,
+                info: *mut i32,
+            );
+            fn $potrf(
+                uplo: *const c_char,
+                n: *const i32,
+                a: *mut $t,
+                lda: *const i32,
+                info: *mut i32,
+            );
+            fn $geqrf(
+                m: *const i32,
+                n: *const i32,
+                a: *mut $t,
+                lda: *const i32,
+                tau: *mut $t,
+                work: *mut $t,
+                lwork: *const i32,
+                info: *mut i32,
+            );
+            fn $orgqr(
+                m: *const i32,
+                n: *const i32,
+                k: *const i32,
+                a: *mut $t,
+                lda: *const i32,
+                tau: *const $t,
+                work: *mut $t,
+                lwork: *const i32,
+                info: *mut i32,
+            );
+            fn $gesdd(
+                jobz: *const c_char,
+                m: *const i32,
+                n: *const i32,
+                a: *mut $t,
+                lda: *const i32,
+                s: *mut $t,
+                u: *mut $t,
+                ldu: *const i32,
+                vt: *mut $t,
+                ldvt: *const i32,
+                work: *mut $t,
+                lwork: *const i32,
+                iwork: *mut i32,
+                info: *mut i32,
+            );
+            fn $syev(
+                jobz: *const c_char,
+                uplo: *const c_char,
+                n: *const i32,
+                a: *mut $t,
+                lda: *const i32,
+                w: *mut $t,
+                work: *mut $t,
+                lwork: *const i32,
+                info: *mut i32,
+            );
+        }
+
+        impl Lapack for $t {
+            fn getrf(m: i32, n: i32, a: &mut [Self], ipiv: &mut [i32]) -> i32 {
+                check_len("a", a.len(), m * n);
+                check_len("ipiv", ipiv.len(), m.min(n));
+                let mut info = 0;
+                unsafe { $getrf(&m, &n, a.as_mut_ptr(), &m, ipiv.as_mut_ptr(), &mut info) };
+                info
+            }
+
+            fn getri(n: i32, a: &mut [Self], ipiv: &[i32], work: &mut [Self], lwork: i32) -> i32 {
+                check_len("a", a.len(), n * n);
+                check_len("ipiv", ipiv.len(), n);
+                check_len("work", work.len(), lwork);
+                let mut info = 0;
+                unsafe {
+                    $getri(
+                        &n,
+                        a.as_mut_ptr(),
+                        &n,
+                        ipiv.as_ptr(),
+                        work.as_mut_ptr(),
+                        &lwork,
+                        &mut info,
+                    )
+                };
+                info
+            }
+
+            fn getrs(
+                trans: u8,
+                n: i32,
+                nrhs: i32,
+                a: &[Self],
+                ipiv: &[i32],
+                b: &mut [Self],
+            ) -> i32 {
+                check_len("a", a.len(), n * n);
+                check_len("ipiv", ipiv.len(), n);
+                check_len("b", b.len(), n * nrhs);
+                let mut info = 0;
+                let trans = trans as c_char;
+                unsafe {
+                    $getrs(
+                        &trans,
+                        &n,
+                        &nrhs,
+                        a.as_ptr(),
+                        &n,
+                        ipiv.as_ptr(),
+                        b.as_mut_ptr(),
+                        &n,
+                        &mut info,
+                    )
+                };
+                info
+            }
+
+            fn potrf(uplo: u8, n: i32, a: &mut [Self]) -> i32 {
+                check_len("a", a.len(), n * n);
+                let mut info = 0;
+                let uplo = uplo as c_char;
+                unsafe { $potrf(&uplo, &n, a.as_mut_ptr(), &n, &mut info) };
+                info
+            }
+
+            fn geqrf(
+                m: i32,
+                n: i32,
+                a: &mut [Self],
+                tau: &mut [Self],
+                work: &mut [Self],
+                lwork: i32,
+            ) -> i32 {
+                check_len("a", a.len(), m * n);
+                check_len("tau", tau.len(), m.min(n));
+                check_len("work", work.len(), lwork);
+                let mut info = 0;
+                unsafe {
+                    $geqrf(
+                        &m,
+                        &n,
+                        a.as_mut_ptr(),
+                        &m,
+                        tau.as_mut_ptr(),
+                        work.as_mut_ptr(),
+                        &lwork,
+                        &mut info,
+                    )
+                };
+                info
+            }
+
+            fn orgqr(
+                m: i32,
+                n: i32,
+                k: i32,
+                a: &mut [Self],
+                tau: &[Self],
+                work: &mut [Self],
+                lwork: i32,
+            ) -> i32 {
+                check_len("a", a.len(), m * n);
+                check_len("tau", tau.len(), k);
+                check_len("work", work.len(), lwork);
+                let mut info = 0;
+                unsafe {
+                    $orgqr(
+                        &m,
+                        &n,
+                        &k,
+                        a.as_mut_ptr(),
+                        &m,
+                        tau.as_ptr(),
+                        work.as_mut_ptr(),
+                        &lwork,
+                        &mut info,
+                    )
+                };
+                info
+            }
+
+            fn gesdd(
+                jobz: u8,
+                m: i32,
+                n: i32,
+                a: &mut [Self],
+                s: &mut [Self],
+                u: &mut [Self],
+                ldu: i32,
+                vt: &mut [Self],
+                ldvt: i32,
+                work: &mut [Self],
+                lwork: i32,
+                iwork: &mut [i32],
+            ) -> i32 {
+                let k = m.min(n);
+                let (u_cols, vt_rows) = match jobz {
+                    b'A' => (m, n),
+                    b'S' => (k, k),
+                    _ => (0, 0),
+                };
+                assert!(
+                    u_cols == 0 || (ldu >= m && ldvt >= vt_rows),
+                    "Leading dimensions of `u` and `vt` are too small."
+                );
+                check_len("a", a.len(), m * n);
+                check_len("s", s.len(), k);
+                // `u` and `vt` are not referenced when singular vectors are not computed.
+                if u_cols != 0 {
+                    check_len("u", u.len(), ldu * u_cols);
+                    check_len("vt", vt.len(), ldvt * n);
+                }
+                check_len("work", work.len(), lwork);
+                check_len("iwork", iwork.len(), 8 * k);
+                let mut info = 0;
+                let jobz = jobz as c_char;
+                unsafe {
+                    $gesdd(
+                        &jobz,
+                        &m,
+                        &n,
+                        a.as_mut_ptr(),
+                        &m,
+                        s.as_mut_ptr(),
+                        u.as_mut_ptr(),
+                        &ldu,
+                        vt.as_mut_ptr(),
+                        &ldvt,
+                        work.as_mut_ptr(),
+                        &lwork,
+                        iwork.as_mut_ptr(),
+                        &mut info,
+                    )
+                };
+                info
+            }
+
+            fn syev(
+                jobz: u8,
+                uplo: u8,
+                n: i32,
+                a: &mut [Self],
+                w: &mut [Self],
+                work: &mut [Self],
+                lwork: i32,
+            ) -> i32 {
+                check_len("a", a.len(), n * n);
+                check_len("w", w.len(), n);
+                check_len("work", work.len(), lwork);
+                let mut info = 0;
+                let (jobz, uplo) = (jobz as c_char, uplo as c_char);
+                unsafe {
+                    $syev(
+                        &jobz,
+                        &uplo,
+                        &n,
+                        a.as_mut_ptr(),
+                        &n,
+                        w.as_mut_ptr(),
+                        work.as_mut_ptr(),
+                        &lwork,
+                        &mut info,
+                    )
+                };
+                info
+            }
+
+            fn epsilon() -> Self {
+                <$t>::EPSILON
+            }
+
+            fn from_f64(x: f64) -> Self {
+                x as $t
+            }
+        }
+    };
+}
+
+impl_lapack!(f64, dgetrf_, dgetri_, dgetrs_, dpotrf_, dgeqrf_, dorgqr_, dgesdd_, dsyev_);
+impl_lapack!(f32, sgetrf_, sgetri_, sgetrs_, spotrf_, sgeqrf_, sorgqr_, sgesdd_, ssyev_);
+
+/// Turns LAPACK's `info` into a `Result`, negative values
+/// denote illegal arguments and are bugs.
+pub(crate) fn check_info(info: i32, error: fn(usize) -> LinalgError) -> Result<(), LinalgError> {
+    assert!(
+        info >= 0,
+        "Illegal value for argument {} of LAPACK routine.",
+        -info
+    );
+    if info > 0 {
+        Err(error(info as usize))
+    } else {
+        Ok(())
+    }
+}
+
+/// Allocates the optimal workspace returned by a workspace query.
+pub(crate) fn workspace<T: Lapack>(query: impl FnOnce(&mut [T]) -> i32) -> Vec<T> {
+    let mut size = [T::ZERO];
+    check_info(query(&mut size), |_| unreachable!()).unwrap();
+    let size: f64 = size[0].into();
+
+    vec![T::ZERO; (size as usize).max(1)]
+}
+
+/// Copies the data of a tensor in logical row-major order.
+pub(crate) fn row_major<T, S, C, L, P>(tensor: &Tensor<T, S, C, L, P>) -> Vec<T>
+where
+    T: Copy,
+    L: for<'a> Layout<'a, T>,
+{
+    let chunk_size = tensor.opt_chunk_size();
+    tensor.chunks(chunk_size).flatten().copied().collect()
+}
+
+/// Transposes the row-major `rows` x `cols` matrix `data`,
+/// which also converts it to column-major order.
+pub(crate) fn transpose<T: Copy>(data: &[T], rows: usize, cols: usize) -> Vec<T> {
+    let mut out = Vec::with_capacity(data.len());
+    for j in 0..cols {
+        out.extend((0..rows).map(|i| data[i * cols + j]));
+    }
+
+    out
+}
+
+/// Computes the determinant of the `n` x `n` matrix `a`
+/// from its LU factorization.
+fn det_raw<T: Lapack>(mut a: Vec<T>, n: usize) -> T {
+    let mut ipiv = vec![0; n];
+    let info = T::getrf(n as i32, n as i32, &mut a, &mut ipiv);
+    if check_info(info, LinalgError::Singular).is_err() {
+        return T::ZERO;
+    }
+
+    ipiv.iter().enumerate().fold(T::ONE, |acc, (i, pivot)| {
+        let x = acc * a[i * n + i];
+        if *pivot as usize == i + 1 {
+            x
+        } else {
+            -x
+        }
+    })
+}
+
+/// Allocates a tensor with the given row-major data.
+pub(crate) fn from_row_major<T, S, P>(data: &[T]) -> Tensor<T, S, Contiguous, P::Layout, P>
+where
+    T: Copy,
+    P: StaticAllocationPolicy<T, S>,
+{
+    let mut out: Tensor<T, S, Contiguous, P::Layout, P> = Tensor::default();
+    out.copy_from_slice(data);
+
+    out
+}
+
+impl<T, N, C, L, P> Tensor<T, Shape2D<N, N>, C, L, P>
+where
+    T: Lapack,
+    N: Unsigned,
+    L: for<'a> Layout<'a, T>,
+{
+    /// Solves the linear system `self * X = other` for `X`.
+    ///
+    /// Returns an error if `self` is singular.
+    pub fn solve<K, Crhs, Lrhs, Prhs>(
+        &self,
+        other: &Tensor<T, Shape2D<N, K>, Crhs, Lrhs, Prhs>,
+    ) -> Result<Matrix<T, N, K, P>, LinalgError>
+    where
+        K: Unsigned,
+        Lrhs: for<'a> Layout<'a, T>,
+        P: StaticAllocationPolicy<T, Shape2D<N, K>>,
+    {
+        let mut a = row_major(self);
+        let mut ipiv = vec![0; N::USIZE];
+        check_info(T::getrf(N::I32, N::I32, &mut a, &mut ipiv), |i| {
+            LinalgError::Singular(i - 1)
+        })?;
+
+        // `a` holds the factorization of the transpose of `self`
+        // hence the transposed solve.
+        let mut b = transpose(&row_major(other), N::USIZE, K::USIZE);
+        check_info(
+            T::getrs(b'T', N::I32, K::I32, &a, &ipiv, &mut b),
+            |_| unreachable!(),
+        )?;
+
+        Ok(from_row_major(&transpose(&b, K::USIZE, N::USIZE)))
+    }
+
+    /// Computes the inverse of the matrix.
+    ///
+    /// Returns an error if `self` is singular.
+    pub fn inverse(&self) -> Result<Matrix<T, N, N, P>, LinalgError>
+    where
+        P: StaticAllocationPolicy<T, Shape2D<N, N>>,
+    {
+        // The inverse of the transpose is the transpose of the inverse,
+        // row-major data can be used as is.
+        let mut a = row_major(self);
+        let mut ipiv = vec![0; N::USIZE];
+        check_info(T::getrf(N::I32, N::I32, &mut a, &mut ipiv), |i| {
+            LinalgError::Singular(i - 1)
+        })?;
+
+        let mut work = workspace(|work| T::getri(N::I32, &mut a, &ipiv, work, -1));
+        let lwork = work.len() as i32;
+        check_info(T::getri(N::I32, &mut a, &ipiv, &mut work, lwork), |i| {
+            LinalgError::Singular(i - 1)
+        })?;
+
+        Ok(from_row_major(&a))
+    }
+
+    /// Computes the determinant of the matrix from its LU factorization.
+    pub fn det(&self) -> T {
+        // The determinant is invariant by transposition.
+        det_raw(row_major(self), N::USIZE)
+    }
+
+    /// Computes the adjugate of the matrix, the transpose of its cofactor
+    /// matrix, such that `self * adjugate = det(self) * I`.
+    ///
+    /// Contrary to `inverse`, it is defined for singular matrices.
+    /// It is computed from the singular value decomposition
+    /// `self = U * diag(S) * V^T` as `det(U) * det(V) * V * diag(C) * U^T`
+    /// where `C[i]` is the product of the singular values but `S[i]`.
+    ///
+    /// Returns an error if the decomposition does not converge.
+    pub fn adjugate(&self) -> Result<Matrix<T, N, N, P>, LinalgError>
+    where
+        Shape2D<N, N>: MinDim,
+        P: StaticAllocationPolicy<T, Shape2D<N, N>>,
+    {
+        let n = N::USIZE;
+        let svd = self.svd_raw(b'A')?;
+        let sign = det_raw(svd.u.clone(), n) * det_raw(svd.vt.clone(), n);
+
+        let mut out = vec![T::ZERO; n * n];
+        for l in 0..n {
+            let c = svd
+                .s
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != l)
+                .fold(sign, |acc, (_, x)| acc * *x);
+            for i in 0..n {
+                let x = svd.vt[l * n + i] * c;
+                for j in 0..n {
+                    out[i * n + j] += x * svd.u[j * n + l];
+                }
+            }
+        }
+
+        Ok(from_row_major(&out))
+    }
+
+    /// Computes the Cholesky factorization of a symmetric positive definite
+    /// matrix and returns the lower triangular matrix `L` such that
+    /// `self = L * L^T`.
+    ///
+    /// Only the lower triangular part of `self` is read.
+    /// Returns an error if `self` is not positive definite.
+    pub fn cholesky(&self) -> Result<Matrix<T, N, N, P>, LinalgError>
+    where
+        P: StaticAllocationPolicy<T, Shape2D<N, N>>,
+    {
+        // The upper triangular factor of the column-major transpose
+        // is the lower triangular factor in row-major order.
+        let mut a = row_major(self);
+        check_info(
+            T::potrf(b'U', N::I32, &mut a),
+            LinalgError::NotPositiveDefinite,
+        )?;
+
+        let n = N::USIZE;
+        for i in 0..n {
+            for x in a[i * n + i + 1..(i + 1) * n].iter_mut() {
+                *x = T::ZERO;
+            }
+        }
+
+        Ok(from_row_major(&a))
+    }
+
+    /// Computes the LU factorization with partial pivoting of the matrix
+    /// and returns the permutation matrix `P`, the unit lower triangular
+    /// matrix `L` and the upper triangular matrix `U` such that
+    /// `self = P * L * U`.
+    ///
+    /// The factorization exists for singular matrices,
+    /// in which case `U` has zeros on its diagonal.
+    pub fn lu(&self) -> LuFactors<T, N, P>
+    where
+        P: StaticAllocationPolicy<T, Shape2D<N, N>>,
+    {
+        let n = N::USIZE;
+        let mut a = transpose(&row_major(self), n, n);
+        let mut ipiv = vec![0; n];
+        let info = T::getrf(N::I32, N::I32, &mut a, &mut ipiv);
+        // Singularity does not prevent the factorization.
+        check_info(info, LinalgError::Singular).ok();
+
+        let mut l = vec![T::ZERO; n * n];
+        let mut u = vec![T::ZERO; n * n];
+        for i in 0..n {
+            for j in 0..n {
+                let x = a[j * n + i];
+                if i > j {
+                    l[i * n + j] = x;
+                } else {
+                    u[i * n + j] = x;
+                }
+            }
+            l[i * n + i] = T::ONE;
+        }
+
+        // Rows are interchanged in order, `permutation[i]` is the original
+        // index of the row found at index `i` after interchanges.
+        let mut permutation: Vec<usize> = (0..n).collect();
+        for (i, pivot) in ipiv.iter().enumerate() {
+            permutation.swap(i, *pivot as usize - 1);
+        }
+        let mut p = vec![T::ZERO; n * n];
+        for (i, row) in permutation.into_iter().enumerate() {
+            p[row * n + i] = T::ONE;
+        }
+
+        (from_row_major(&p), from_row_major(&l), from_row_major(&u))
+    }
+
+    /// Computes the QR factorization of the matrix and returns the orthogonal
+    /// matrix `Q` and the upper triangular matrix `R` such that `self = Q * R`.
+    pub fn qr(&self) -> QrFactors<T, N, P>
+    where
+        P: StaticAllocationPolicy<T, Shape2D<N, N>>,
+    {
+        let n = N::USIZE;
+        let mut a = transpose(&row_major(self), n, n);
+        let mut tau = vec![T::ZERO; n];
+        let mut work = workspace(|work| T::geqrf(N::I32, N::I32, &mut a, &mut tau, work, -1));
+        let lwork = work.len() as i32;
+        check_info(
+            T::geqrf(N::I32, N::I32, &mut a, &mut tau, &mut work, lwork),
+            |_| unreachable!(),
+        )
+        .unwrap();
+
+        let mut r = vec![T::ZERO; n * n];
+        for i in 0..n {
+            for j in i..n {
+                r[i * n + j] = a[j * n + i];
+            }
+        }
+
+        let mut work = workspace(|work| T::orgqr(N::I32, N::I32, N::I32, &mut a, &tau, work, -1));
+        let lwork = work.len() as i32;
+        check_info(
+            T::orgqr(N::I32, N::I32, N::I32, &mut a, &tau, &mut work, lwork),
+            |_| unreachable!(),
+        )
+        .unwrap();
+
+        (from_row_major(&transpose(&a, n, n)), from_row_major(&r))
+    }
+
+    /// Computes the eigenvalues in ascending order and the eigenvectors
+    /// of a symmetric matrix. The eigenvector of the `i`-th eigenvalue
+    /// is the `i`-th column of the returned matrix.
+    ///
+    /// Only the lower triangular part of `self` is read.
+    /// Returns an error if the algorithm does not converge.
+    pub fn eigh(&self) -> Result<EighFactors<T, N, P>, LinalgError>
+    where
+        P: StaticAllocationPolicy<T, Shape1D<N>> + StaticAllocationPolicy<T, Shape2D<N, N>>,
+    {
+        // The upper triangle of the column-major transpose
+        // is the lower triangle in row-major order.
+        let n = N::USIZE;
+        let mut a = row_major(self);
+        let mut w = vec![T::ZERO; n];
+        let mut work = workspace(|work| T::syev(b'V', b'U', N::I32, &mut a, &mut w, work, -1));
+        let lwork = work.len() as i32;
+        check_info(
+            T::syev(b'V', b'U', N::I32, &mut a, &mut w, &mut work, lwork),
+            LinalgError::NoConvergence,
+        )?;
+
+        // Eigenvectors are the columns of the column-major output.
+        Ok((from_row_major(&w), from_row_major(&transpose(&a, n, n))))
+    }
+}
+
+/// Singular value decomposition of a `M` x `N` matrix in row-major order.
+struct Svd<T> {
+    u: Vec<T>,
+    s: Vec<T>,
+    vt: Vec<T>,
+}
+
+impl<T, M, N, C, L, P> Tensor<T, Shape2D<M, N>, C, L, P>
+where
+    T: Lapack,
+    M: Unsigned,
+    N: Unsigned,
+    Shape2D<M, N>: MinDim,
+    L: for<'a> Layout<'a, T>,
+{
+    /// Calls `gesdd` and converts its outputs to row-major order.
+    fn svd_raw(&self, jobz: u8) -> Result<Svd<T>, LinalgError> {
+        let (m, n, k) = (M::USIZE, N::USIZE, MinOf::<M, N>::USIZE);
+        let (u_cols, vt_rows) = match jobz {
+            b'A' => (m, n),
+            b'S' => (k, k),
+            _ => (0, 0),
+        };
+        let mut a = transpose(&row_major(self), m, n);
+        let mut s = vec![T::ZERO; k];
+        let mut u = vec![T::ZERO; m * u_cols];
+        let mut vt = vec![T::ZERO; vt_rows * n];
+        let mut iwork = vec![0; 8 * k];
+        let (ldu, ldvt) = (M::I32, vt_rows.max(1) as i32);
+
+        let mut work = workspace(|work| {
+            T::gesdd(
+                jobz,
+                M::I32,
+                N::I32,
+                &mut a,
+                &mut s,
+                &mut u,
+                ldu,
+                &mut vt,
+                ldvt,
+                work,
+                -1,
+                &mut iwork,
+            )
+        });
+        let lwork = work.len() as i32;
+        check_info(
+            T::gesdd(
+                jobz,
+                M::I32,
+                N::I32,
+                &mut a,
+                &mut s,
+                &mut u,
+                ldu,
+                &mut vt,
+                ldvt,
+                &mut work,
+                lwork,
+                &mut iwork,
+            ),
+            LinalgError::NoConvergence,
+        )?;
+
+        // Column-major data is the row-major data of the transpose.
+        Ok(Svd {
+            u: transpose(&u, u_cols, m),
+            s,
+            vt: transpose(&vt, n, vt_rows),
+        })
+    }
+
+    /// Default tolerance under which singular values are considered zero:
+    /// the largest singular value times `max(M, N)` times the machine epsilon.
+    fn tolerance(s: &[T]) -> f64 {
+        let max = s.first().map_or(0.0, |x| (*x).into());
+        max * M::USIZE.max(N::USIZE) as f64 * T::epsilon().into()
+    }
+
+    /// Computes the full singular value decomposition of the matrix
+    /// and returns the orthogonal matrix `U`, the singular values
+    /// in descending order and the orthogonal matrix `V^T` such that
+    /// `self = U * diag(S) * V^T` where `diag(S)` is `M` x `N`.
+    ///
+    /// Returns an error if the algorithm does not converge.
+    pub fn svd(&self) -> Result<SvdFactors<T, M, N, P>, LinalgError>
+    where
+        P: StaticAllocationPolicy<T, Shape2D<M, M>>
+            + StaticAllocationPolicy<T, Shape1D<MinOf<M, N>>>
+            + StaticAllocationPolicy<T, Shape2D<N, N>>,
+    {
+        let svd = self.svd_raw(b'A')?;
+
+        Ok((
+            from_row_major(&svd.u),
+            from_row_major(&svd.s),
+            from_row_major(&svd.vt),
+        ))
+    }
+
+    /// Computes the thin singular value decomposition of the matrix where
+    /// `U` is `M` x `min(M, N)` and `V^T` is `min(M, N)` x `N` such that
+    /// `self = U * diag(S) * V^T` with a square `diag(S)`.
+    ///
+    /// Returns an error if the algorithm does not converge.
+    pub fn svd_thin(&self) -> Result<ThinSvdFactors<T, M, N, P>, LinalgError>
+    where
+        P: StaticAllocationPolicy<T, Shape2D<M, MinOf<M, N>>>
+            + StaticAllocationPolicy<T, Shape1D<MinOf<M, N>>>
+            + StaticAllocationPolicy<T, Shape2D<MinOf<M, N>, N>>,
+    {
+        let svd = self.svd_raw(b'S')?;
+
+        Ok((
+            from_row_major(&svd.u),
+            from_row_major(&svd.s),
+            from_row_major(&svd.vt),
+        ))
+    }
+
+    /// Computes the Moore-Penrose pseudo-inverse of the matrix from
+    /// its singular value decomposition. Singular values under
+    /// `max(S) * max(M, N) * epsilon` are considered zero.
+    ///
+    /// Returns an error if the decomposition does not converge.
+    pub fn pinv(&self) -> Result<Matrix<T, N, M, P>, LinalgError>
+    where
+        P: StaticAllocationPolicy<T, Shape2D<N, M>>,
+    {
+        let (m, n) = (M::USIZE, N::USIZE);
+        let svd = self.svd_raw(b'S')?;
+        let tolerance = Self::tolerance(&svd.s);
+
+        // pinv = V * diag(S)^+ * U^T
+        let mut out = vec![T::ZERO; n * m];
+        for (l, s) in svd.s.iter().enumerate() {
+            let s: f64 = (*s).into();
+            if s <= tolerance {
+                continue;
+            }
+            let inv = T::from_f64(1.0 / s);
+            for j in 0..n {
+                let x = svd.vt[l * n + j] * inv;
+                for i in 0..m {
+                    out[j * m + i] += x * svd.u[i * svd.s.len() + l];
+                }
+            }
+        }
+
+        Ok(from_row_major(&out))
+    }
+
+    /// Computes the rank of the matrix as the number of singular values
+    /// above `max(S) * max(M, N) * epsilon`.
+    ///
+    /// Returns an error if the decomposition does not converge.
+    pub fn matrix_rank(&self) -> Result<usize, LinalgError> {
+        let svd = self.svd_raw(b'N')?;
+        let tolerance = Self::tolerance(&svd.s);
+
+        Ok(svd
+            .s
+            .iter()
+            .filter(|s| Into::<f64>::into(**s) > tolerance)
+            .count())
+    }
+}
@@ -0,0 +1,142 @@
+//! `display` implements `std::fmt::Display` for tensors.
+//!
+//! Tensors are printed NumPy-style with nested brackets by walking the
+//! logical shape with the strides of the layout, which means that views
+//! only print the elements they actually contain. Elements are right-aligned
+//! on the widest one and the precision flag of the formatter is applied to
+//! each of them so that `{:.3}` prints floats with three decimals.
+//!
+//! Tensors with more than `THRESHOLD` elements are summarized: only the
+//! first and last `EDGE_ITEMS` indices of each axis are printed and the
+//! others are replaced by an ellipsis.
+
+use super::layout::Layout;
+use super::tensor::Tensor;
+use std::fmt;
+use std::ops::Deref;
+
+/// Number of elements above which tensors are summarized.
+pub const THRESHOLD: usize = 1000;
+/// Number of indices printed at the beginning and at the end of each
+/// axis of summarized tensors.
+pub const EDGE_ITEMS: usize = 3;
+
+/// Walks the logical layout of a tensor on top of its raw data.
+struct Printer<'a, T> {
+    data: &'a [T],
+    shape: Vec<usize>,
+    strides: Vec<usize>,
+    summarize: bool,
+    precision: Option<usize>,
+}
+
+impl<'a, T> Printer<'a, T>
+where
+    T: fmt::Display,
+{
+    /// Returns the indices to print along an axis of length `len`,
+    /// `None` standing for an ellipsis.
+    fn indices(&self, len: usize) -> Vec<Option<usize>> {
+        if self.summarize && len > 2 * EDGE_ITEMS {
+            (0..EDGE_ITEMS)
+                .map(Some)
+                .chain(std::iter::once(None))
+                .chain((len - EDGE_ITEMS..len).map(Some))
+                .collect()
+        } else {
+            (0..len).map(Some).collect()
+        }
+    }
+
+    fn element(&self, offset: usize) -> String {
+        match self.precision {
+            Some(precision) => format!("{:.*}", precision, self.data[offset]),
+            None => format!("{}", self.data[offset]),
+        }
+    }
+
+    /// Computes the width of the widest printed element.
+    fn width(&self, axis: usize, offset: usize) -> usize {
+        if axis == self.shape.len() {
+            return self.element(offset).chars().count();
+        }
+
+        self.indices(self.shape[axis])
+            .into_iter()
+            .flatten()
+            .map(|i| self.width(axis + 1, offset + i * self.strides[axis]))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn write(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        axis: usize,
+        offset: usize,
+        width: usize,
+        indent: usize,
+    ) -> fmt::Result {
+        if axis == self.shape.len() {
+            return write!(f, "{:>width$}", self.element(offset), width = width);
+        }
+
+        // Sub-arrays are separated by as many new lines as their number
+        // of dimensions minus one and aligned on the opening bracket.
+        let separator = if axis + 1 == self.shape.len() {
+            ", ".to_string()
+        } else {
+            format!(
+                ",{}{}",
+                "\n".repeat(self.shape.len() - axis - 1),
+                " ".repeat(indent + axis + 1)
+            )
+        };
+
+        write!(f, "[")?;
+        for (n, index) in self.indices(self.shape[axis]).into_iter().enumerate() {
+            if n > 0 {
+                write!(f, "{}", separator)?;
+            }
+            match index {
+                Some(i) => {
+                    self.write(f, axis + 1, offset + i * self.strides[axis], width, indent)?
+                }
+                None => write!(f, "...")?,
+            }
+        }
+        write!(f, "]")
+    }
+}
+
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    T: fmt::Display,
+    L: for<'a> Layout<'a, T> + Deref<Target = [T]>,
+{
+    /// Formats the tensor assuming that the first line is already
+    /// indented by `indent` characters so that following lines
+    /// can be aligned.
+    pub(crate) fn fmt_with_indent(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        let printer = Printer {
+            data: self,
+            shape: self.shape(),
+            strides: self.strides(),
+            summarize: self.num_elements() > THRESHOLD,
+            precision: f.precision(),
+        };
+        let width = printer.width(0, 0);
+
+        printer.write(f, 0, 0, width, indent)
+    }
+}
+
+impl<T, S, C, L, P> fmt::Display for Tensor<T, S, C, L, P>
+where
+    T: fmt::Display,
+    L: for<'a> Layout<'a, T> + Deref<Target = [T]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with_indent(f, 0)
+    }
+}
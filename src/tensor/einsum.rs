@@ -0,0 +1,379 @@
+//! `einsum` contains the runtime support of the `einsum!` procedural macro
+//! from the `melange_macros` crate that computes Einstein summations such as
+//! `einsum!("bij,bjk->bik", &a, &b)`.
+//!
+//! The macro parses the subscripts and checks at compile time that the
+//! ranks of the operands match them and that dimensions sharing a label are
+//! equal. It then calls one of the two contraction functions of this module:
+//! * `contract_dot` when the summation is a batched matrix product of two
+//!   operands: they are permuted so that batch, free and contracted axes are
+//!   grouped and multiplied with one `gemm` per batch, backed by BLAS for
+//!   `f64` and `f32` with the `blas` feature,
+//! * `contract` otherwise, a loop parallelized over the elements of the output
+//!   that also supports repeated labels (diagonals) and labels that only
+//!   appear in the output (broadcasting).
+//!
+//! Operands are read in logical order so views of any transpose policy are
+//! supported. Results are contiguous and use the allocation policy of the
+//! first operand.
+
+#[cfg(feature = "blas")]
+extern crate cblas;
+
+use super::gemm;
+use super::layout::Layout;
+use super::shape::intrinsic_strides_in_place;
+use super::tensor::Tensor;
+use super::transpose_policy::Transpose;
+use crate::ring::Ring;
+use half::{bf16, f16};
+use num_complex::{Complex32, Complex64};
+use rayon::prelude::*;
+use std::ops::{Add, AddAssign, Mul};
+
+/// Element types that einsum can contract.
+pub trait Contraction:
+    Ring + Copy + Send + Sync + PartialEq + Add<Output = Self> + Mul<Output = Self> + AddAssign
+{
+    /// Computes `c = a * b` where `a` is `m` x `k`, `b` is `k` x `n`
+    /// and `c` is `m` x `n`, all contiguous and row-major.
+    fn gemm(m: usize, n: usize, k: usize, a: &[Self], b: &[Self], c: &mut [Self]);
+}
+
+macro_rules! impl_contraction {
+    ($($t:ty),*) => {
+        $(
+            impl Contraction for $t {
+                fn gemm(m: usize, n: usize, k: usize, a: &[Self], b: &[Self], c: &mut [Self]) {
+                    gemm::gemm(
+                        Transpose::None,
+                        Transpose::None,
+                        m,
+                        n,
+                        k,
+                        Self::ONE,
+                        a,
+                        k,
+                        b,
+                        n,
+                        Self::ZERO,
+                        c,
+                        n,
+                    );
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "blas")]
+macro_rules! impl_contraction_blas {
+    ($($t:ty, $gemm:ident);*) => {
+        $(
+            impl Contraction for $t {
+                fn gemm(m: usize, n: usize, k: usize, a: &[Self], b: &[Self], c: &mut [Self]) {
+                    if m == 0 || n == 0 {
+                        return;
+                    }
+                    unsafe {
+                        cblas::$gemm(
+                            cblas::Layout::RowMajor,
+                            cblas::Transpose::None,
+                            cblas::Transpose::None,
+                            m as i32,
+                            n as i32,
+                            k as i32,
+                            1.0,
+                            a,
+                            k.max(1) as i32,
+                            b,
+                            n as i32,
+                            0.0,
+                            c,
+                            n as i32,
+                        );
+                    }
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "blas")]
+impl_contraction_blas!(f64, dgemm; f32, sgemm);
+#[cfg(not(feature = "blas"))]
+impl_contraction!(f64, f32);
+impl_contraction!(f16, bf16, Complex64, Complex32, i64, i32, u64, u32);
+
+/// Operand of an Einstein summation: the elements
+/// of a tensor in logical order along with its shape.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operand<T> {
+    data: Vec<T>,
+    shape: Vec<usize>,
+}
+
+impl<T: Copy> Operand<T> {
+    /// Copies the elements of `tensor` in logical order.
+    pub fn new<S, C, L, P>(tensor: &Tensor<T, S, C, L, P>) -> Self
+    where
+        L: for<'a> Layout<'a, T>,
+    {
+        Operand {
+            data: tensor.logical_vec(),
+            shape: tensor.shape(),
+        }
+    }
+
+    /// Returns the elements of the operand.
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Returns the shape of the operand.
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+}
+
+/// Returns the dimension of `label` among the axes of the operands.
+fn label_size<T>(label: u8, labels: &[&[u8]], operands: &[&Operand<T>]) -> usize {
+    labels
+        .iter()
+        .zip(operands.iter())
+        .find_map(|(labels, operand)| {
+            labels
+                .iter()
+                .position(|l| *l == label)
+                .map(|axis| operand.shape[axis])
+        })
+        .unwrap()
+}
+
+/// Copies the elements of `operand`, whose axes are `labels`,
+/// in the order of the axes given by `target`, a permutation of `labels`.
+fn permute<T: Copy + Send + Sync>(operand: &Operand<T>, labels: &[u8], target: &[u8]) -> Vec<T> {
+    if labels == target {
+        return operand.data.clone();
+    }
+
+    let strides = intrinsic_strides_in_place(operand.shape.clone());
+    let axes: Vec<usize> = target
+        .iter()
+        .map(|l| labels.iter().position(|x| x == l).unwrap())
+        .collect();
+    let target_shape: Vec<usize> = axes.iter().map(|axis| operand.shape[*axis]).collect();
+    let target_strides = intrinsic_strides_in_place(target_shape);
+
+    (0..operand.data.len())
+        .into_par_iter()
+        .map(|mut i| {
+            let mut source = 0;
+            for (axis, target_stride) in axes.iter().zip(target_strides.iter()) {
+                source += i / target_stride * strides[*axis];
+                i %= target_stride;
+            }
+
+            operand.data[source]
+        })
+        .collect()
+}
+
+/// Computes the summation of two operands that is a batched matrix product.
+/// Labels must not be repeated and each label of an operand must appear in
+/// the other operand or in the output.
+pub fn contract_dot<T: Contraction>(
+    labels: [&[u8]; 2],
+    operands: [&Operand<T>; 2],
+    out_labels: &[u8],
+    out: &mut [T],
+) {
+    let [a_labels, b_labels] = labels;
+    let size = |group: &[u8]| -> usize {
+        group
+            .iter()
+            .map(|l| label_size(*l, &labels, &operands))
+            .product()
+    };
+
+    let batch: Vec<u8> = out_labels
+        .iter()
+        .copied()
+        .filter(|l| a_labels.contains(l) && b_labels.contains(l))
+        .collect();
+    let free_a: Vec<u8> = a_labels
+        .iter()
+        .copied()
+        .filter(|l| out_labels.contains(l) && !b_labels.contains(l))
+        .collect();
+    let free_b: Vec<u8> = b_labels
+        .iter()
+        .copied()
+        .filter(|l| out_labels.contains(l) && !a_labels.contains(l))
+        .collect();
+    let contracted: Vec<u8> = a_labels
+        .iter()
+        .copied()
+        .filter(|l| !out_labels.contains(l))
+        .collect();
+    let (num_batches, m, n, k) = (
+        size(&batch),
+        size(&free_a),
+        size(&free_b),
+        size(&contracted),
+    );
+
+    let a = permute(
+        operands[0],
+        a_labels,
+        &[&batch[..], &free_a, &contracted].concat(),
+    );
+    let b = permute(
+        operands[1],
+        b_labels,
+        &[&batch[..], &contracted, &free_b].concat(),
+    );
+
+    let mut c = vec![T::ZERO; num_batches * m * n];
+    for i in 0..num_batches {
+        T::gemm(
+            m,
+            n,
+            k,
+            &a[i * m * k..(i + 1) * m * k],
+            &b[i * k * n..(i + 1) * k * n],
+            &mut c[i * m * n..(i + 1) * m * n],
+        );
+    }
+
+    let c_labels = [&batch[..], &free_a, &free_b].concat();
+    let c_shape = c_labels
+        .iter()
+        .map(|l| label_size(*l, &labels, &operands))
+        .collect();
+    let c = Operand {
+        data: c,
+        shape: c_shape,
+    };
+    out.copy_from_slice(&permute(&c, &c_labels, out_labels));
+}
+
+/// Computes any summation of the operands into `out` of shape `out_shape`.
+/// Repeated labels in an operand select its diagonal and repeated labels in
+/// the output write to its diagonal, other elements being zero. Labels that
+/// only appear in the output broadcast the result along their axis.
+pub fn contract<T: Contraction>(
+    labels: &[&[u8]],
+    operands: &[&Operand<T>],
+    out_labels: &[u8],
+    out_shape: &[usize],
+    out: &mut [T],
+) {
+    let mut outer: Vec<(u8, usize)> = Vec::new();
+    for (label, size) in out_labels.iter().zip(out_shape.iter()) {
+        if !outer.iter().any(|(l, _)| l == label) {
+            outer.push((*label, *size));
+        }
+    }
+    let mut inner: Vec<(u8, usize)> = Vec::new();
+    for (labels, operand) in labels.iter().zip(operands.iter()) {
+        for (label, size) in labels.iter().zip(operand.shape.iter()) {
+            match outer.iter().chain(inner.iter()).find(|(l, _)| l == label) {
+                Some((_, s)) => assert_eq!(
+                    s, size,
+                    "Dimensions of label '{}' do not match.",
+                    *label as char
+                ),
+                None => inner.push((*label, *size)),
+            }
+        }
+    }
+
+    // Strides of repeated labels add up to address diagonals.
+    let label_strides = |group: &[(u8, usize)], labels: &[u8], shape: &[usize]| -> Vec<usize> {
+        let strides = intrinsic_strides_in_place(shape.to_vec());
+        group
+            .iter()
+            .map(|(label, _)| {
+                labels
+                    .iter()
+                    .zip(strides.iter())
+                    .filter(|(l, _)| *l == label)
+                    .map(|(_, s)| s)
+                    .sum()
+            })
+            .collect()
+    };
+    let out_strides = label_strides(&outer, out_labels, out_shape);
+    let operand_strides: Vec<(Vec<usize>, Vec<usize>)> = labels
+        .iter()
+        .zip(operands.iter())
+        .map(|(labels, operand)| {
+            (
+                label_strides(&outer, labels, &operand.shape),
+                label_strides(&inner, labels, &operand.shape),
+            )
+        })
+        .collect();
+
+    let outer_count: usize = outer.iter().map(|(_, s)| s).product();
+    let inner_count: usize = inner.iter().map(|(_, s)| s).product();
+    // Outputs the offsets of the coordinates at flat index `i` of `group`.
+    let offsets = |mut i: usize, group: &[(u8, usize)], strides: &[&[usize]]| -> Vec<usize> {
+        let mut offsets = vec![0; strides.len()];
+        for (axis, (_, size)) in group.iter().enumerate().rev() {
+            let coordinate = i % size;
+            i /= size;
+            for (offset, strides) in offsets.iter_mut().zip(strides.iter()) {
+                *offset += coordinate * strides[axis];
+            }
+        }
+
+        offsets
+    };
+
+    let mut outer_strides: Vec<&[usize]> = vec![&out_strides];
+    outer_strides.extend(operand_strides.iter().map(|(o, _)| &o[..]));
+    let inner_strides: Vec<&[usize]> = operand_strides.iter().map(|(_, i)| &i[..]).collect();
+
+    let sums: Vec<(usize, T)> = (0..outer_count)
+        .into_par_iter()
+        .map(|i| {
+            let outer_offsets = offsets(i, &outer, &outer_strides);
+            let mut acc = T::ZERO;
+            for j in 0..inner_count {
+                let inner_offsets = offsets(j, &inner, &inner_strides);
+                let mut product = T::ONE;
+                for ((operand, outer_offset), inner_offset) in operands
+                    .iter()
+                    .zip(outer_offsets[1..].iter())
+                    .zip(inner_offsets.iter())
+                {
+                    product = product * operand.data[outer_offset + inner_offset];
+                }
+                acc += product;
+            }
+
+            (outer_offsets[0], acc)
+        })
+        .collect();
+
+    out.iter_mut().for_each(|x| *x = T::ZERO);
+    for (offset, sum) in sums {
+        out[offset] = sum;
+    }
+}
+
+/// Items used by the code generated by `einsum!`.
+#[doc(hidden)]
+pub mod macro_support {
+    pub use super::{contract, contract_dot, Contraction, Operand};
+    pub use crate::backprop::variable::Variable;
+    pub use crate::tensor::allocation_policy::StaticAllocationPolicy;
+    pub use crate::tensor::layout::{Layout, LayoutMut};
+    pub use crate::tensor::shape::{At, StaticShape, TRUE};
+    pub use crate::tensor::tensor::Tensor;
+    pub use crate::tensor::transpose_policy::Contiguous;
+    pub use typenum::consts::*;
+    pub use typenum::{ATerm, Eq, IsEqual, Len, TArr};
+}
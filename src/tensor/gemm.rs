@@ -0,0 +1,234 @@
+//! `gemm` contains pure-Rust implementations of the BLAS routines used by
+//! `linear_algebra`: `gemm`, `gemv`, `dot`, `axpy` and `scal`. They back
+//! the dot products when the `blas` feature is disabled and work for any
+//! element type implementing `Ring`, not only for floats.
+//!
+//! Matrices are row-major and transposition follows the semantics of
+//! `BLASPolicy::BLAS_TRANSPOSE`: a transposed operand is stored as its
+//! transpose with the given leading dimension. As in BLAS, `out` is not
+//! read when `beta` is zero.
+//!
+//! `gemm` is cache-blocked: blocks of `B` are packed once and shared by
+//! all threads while blocks of rows of `A` and `C` are processed in parallel
+//! with rayon. `gemm_i8` multiplies `i8` matrices and accumulates in `i32`
+//! to avoid overflows in quantized inference.
+
+use super::transpose_policy::Transpose;
+use crate::ring::Ring;
+use rayon::prelude::*;
+use std::ops::{AddAssign, Mul};
+
+/// Number of rows of `A` and `C` in a block.
+const MC: usize = 64;
+/// Number of columns of `A` and rows of `B` in a block.
+const KC: usize = 256;
+/// Number of columns of `B` and `C` in a block.
+const NC: usize = 512;
+
+/// Scales `c` by `beta`, setting it to zero without reading it if `beta` is zero.
+fn scale<T>(c: &mut [T], beta: T)
+where
+    T: Ring + Copy + PartialEq + Mul<Output = T>,
+{
+    if beta == T::ZERO {
+        c.iter_mut().for_each(|x| *x = T::ZERO);
+    } else if beta != T::ONE {
+        c.iter_mut().for_each(|x| *x = *x * beta);
+    }
+}
+
+/// Returns element `(i, j)` of the logical matrix stored
+/// in `data` with leading dimension `ld` and policy `trans`.
+#[inline]
+fn at<T: Copy>(data: &[T], trans: Transpose, ld: usize, i: usize, j: usize) -> T {
+    if trans == Transpose::None {
+        data[i * ld + j]
+    } else {
+        data[j * ld + i]
+    }
+}
+
+/// Computes `c = alpha * op(a) * op(b) + beta * c` where `op(a)` is `m` x `k`,
+/// `op(b)` is `k` x `n` and `c` is `m` x `n`.
+#[allow(clippy::too_many_arguments)]
+pub fn gemm<T>(
+    trans_a: Transpose,
+    trans_b: Transpose,
+    m: usize,
+    n: usize,
+    k: usize,
+    alpha: T,
+    a: &[T],
+    lda: usize,
+    b: &[T],
+    ldb: usize,
+    beta: T,
+    c: &mut [T],
+    ldc: usize,
+) where
+    T: Ring + Copy + Send + Sync + PartialEq + Mul<Output = T> + AddAssign,
+{
+    if m == 0 || n == 0 {
+        return;
+    }
+    let c = &mut c[..(m - 1) * ldc + n];
+    c.par_chunks_mut(ldc)
+        .for_each(|row| scale(&mut row[..n], beta));
+    if k == 0 || alpha == T::ZERO {
+        return;
+    }
+
+    let mut b_pack = Vec::with_capacity(KC.min(k) * NC.min(n));
+    for p0 in (0..k).step_by(KC) {
+        let kc = KC.min(k - p0);
+        for j0 in (0..n).step_by(NC) {
+            let nc = NC.min(n - j0);
+            b_pack.clear();
+            for p in p0..p0 + kc {
+                b_pack.extend((j0..j0 + nc).map(|j| at(b, trans_b, ldb, p, j)));
+            }
+            let b_pack = &b_pack;
+
+            c.par_chunks_mut(MC * ldc)
+                .enumerate()
+                .for_each(|(block, c_block)| {
+                    let i0 = block * MC;
+                    let mc = MC.min(m - i0);
+
+                    // `alpha` is folded into the packed block of `A`.
+                    let mut a_pack = Vec::with_capacity(mc * kc);
+                    for i in i0..i0 + mc {
+                        a_pack.extend((p0..p0 + kc).map(|p| alpha * at(a, trans_a, lda, i, p)));
+                    }
+
+                    for (i, a_row) in a_pack.chunks_exact(kc).enumerate() {
+                        let c_row = &mut c_block[i * ldc + j0..i * ldc + j0 + nc];
+                        for (x, b_row) in a_row.iter().zip(b_pack.chunks_exact(nc)) {
+                            for (y, z) in c_row.iter_mut().zip(b_row) {
+                                *y += *x * *z;
+                            }
+                        }
+                    }
+                });
+        }
+    }
+}
+
+/// Computes `y = alpha * op(a) * x + beta * y` where `a` is the `m` x `n`
+/// matrix in storage, before `op` is applied.
+#[allow(clippy::too_many_arguments)]
+pub fn gemv<T>(
+    trans: Transpose,
+    m: usize,
+    n: usize,
+    alpha: T,
+    a: &[T],
+    lda: usize,
+    x: &[T],
+    incx: usize,
+    beta: T,
+    y: &mut [T],
+    incy: usize,
+) where
+    T: Ring + Copy + Send + Sync + PartialEq + Mul<Output = T> + AddAssign,
+{
+    let (rows, cols) = if trans == Transpose::None {
+        (m, n)
+    } else {
+        (n, m)
+    };
+    if rows == 0 {
+        return;
+    }
+
+    y[..(rows - 1) * incy + 1]
+        .par_chunks_mut(incy)
+        .enumerate()
+        .for_each(|(i, chunk)| {
+            let y = &mut chunk[0];
+            let mut acc = T::ZERO;
+            for j in 0..cols {
+                acc += at(a, trans, lda, i, j) * x[j * incx];
+            }
+            if beta == T::ZERO {
+                *y = alpha * acc;
+            } else {
+                let mut out = *y * beta;
+                out += alpha * acc;
+                *y = out;
+            }
+        });
+}
+
+/// Computes the dot product of the vectors `x` and `y` of length `n`.
+pub fn dot<T>(n: usize, x: &[T], incx: usize, y: &[T], incy: usize) -> T
+where
+    T: Ring + Copy + Mul<Output = T> + AddAssign,
+{
+    let mut acc = T::ZERO;
+    for i in 0..n {
+        acc += x[i * incx] * y[i * incy];
+    }
+
+    acc
+}
+
+/// Computes `y = alpha * x + y` for vectors of length `n`.
+pub fn axpy<T>(n: usize, alpha: T, x: &[T], incx: usize, y: &mut [T], incy: usize)
+where
+    T: Copy + Mul<Output = T> + AddAssign,
+{
+    for i in 0..n {
+        y[i * incy] += alpha * x[i * incx];
+    }
+}
+
+/// Computes `x = alpha * x` for a vector of length `n`.
+pub fn scal<T>(n: usize, alpha: T, x: &mut [T], incx: usize)
+where
+    T: Copy + Mul<Output = T>,
+{
+    for i in 0..n {
+        x[i * incx] = alpha * x[i * incx];
+    }
+}
+
+/// Computes `c = op(a) * op(b)` where `a` and `b` are `i8` matrices
+/// and `c` is an `i32` matrix, in parallel over the rows of `c`.
+#[allow(clippy::too_many_arguments)]
+pub fn gemm_i8(
+    trans_a: Transpose,
+    trans_b: Transpose,
+    m: usize,
+    n: usize,
+    k: usize,
+    a: &[i8],
+    lda: usize,
+    b: &[i8],
+    ldb: usize,
+    c: &mut [i32],
+    ldc: usize,
+) {
+    if m == 0 || n == 0 {
+        return;
+    }
+
+    // Contiguous rows of `B` let the inner loop widen and vectorize.
+    let b_pack: Vec<i8> = (0..k)
+        .flat_map(|p| (0..n).map(move |j| at(b, trans_b, ldb, p, j)))
+        .collect();
+
+    c[..(m - 1) * ldc + n]
+        .par_chunks_mut(ldc)
+        .enumerate()
+        .for_each(|(i, c_row)| {
+            let c_row = &mut c_row[..n];
+            c_row.iter_mut().for_each(|x| *x = 0);
+            for (p, b_row) in b_pack.chunks_exact(n).enumerate() {
+                let x = i32::from(at(a, trans_a, lda, i, p));
+                for (y, z) in c_row.iter_mut().zip(b_row) {
+                    *y += x * i32::from(*z);
+                }
+            }
+        });
+}
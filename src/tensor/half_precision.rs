@@ -0,0 +1,92 @@
+//! `half_precision` contains conversions between `f32` tensors and tensors
+//! of the 16-bit float types `f16` and `bf16` from the `half` crate.
+//!
+//! Half-precision tensors halve the memory footprint of their storage and
+//! support the same core operations as `f32` tensors. Matrix products are
+//! accumulated in `f32` and rounded once, see `linear_algebra`.
+//!
+//! Conversions follow the allocation policy of `self`. `f16` has a smaller
+//! range than `f32`: values that are too large become infinite.
+
+use super::allocation_policy::{DynamicAllocationPolicy, StaticAllocationPolicy};
+use super::core_ops::convert;
+use super::layout::Layout;
+use super::shape::StaticShape;
+use super::tensor::Tensor;
+use super::transpose_policy::Contiguous;
+use half::{bf16, f16};
+
+macro_rules! impl_half_precision {
+    ($t:ident, $to_half:ident, $to_half_dynamic:ident) => {
+        impl<S, C, L, P> Tensor<$t, S, C, L, P>
+        where
+            L: for<'a> Layout<'a, $t>,
+        {
+            /// Converts the tensor to `f32`.
+            pub fn to_f32(
+                &self,
+            ) -> Tensor<f32, S, Contiguous, <P as StaticAllocationPolicy<f32, S>>::Layout, P>
+            where
+                S: StaticShape,
+                P: StaticAllocationPolicy<f32, S>,
+            {
+                let mut out: Tensor<f32, S, Contiguous, _, P> = Tensor::default();
+                convert(self, &mut out, $t::to_f32);
+
+                out
+            }
+
+            /// Converts the tensor to `f32` with a shape known at runtime.
+            pub fn to_f32_dynamic(
+                &self,
+            ) -> Tensor<f32, S, Contiguous, <P as DynamicAllocationPolicy<f32>>::Layout, P>
+            where
+                P: DynamicAllocationPolicy<f32>,
+            {
+                let mut out: Tensor<f32, S, Contiguous, _, P> = Tensor::alloc(self.shape());
+                convert(self, &mut out, $t::to_f32);
+
+                out
+            }
+        }
+
+        impl<S, C, L, P> Tensor<f32, S, C, L, P>
+        where
+            L: for<'a> Layout<'a, f32>,
+        {
+            #[doc = concat!("Converts the tensor to `", stringify!($t), "` with rounding to nearest.")]
+            pub fn $to_half(
+                &self,
+            ) -> Tensor<$t, S, Contiguous, <P as StaticAllocationPolicy<$t, S>>::Layout, P>
+            where
+                S: StaticShape,
+                P: StaticAllocationPolicy<$t, S>,
+            {
+                let mut out: Tensor<$t, S, Contiguous, _, P> = Tensor::default();
+                convert(self, &mut out, $t::from_f32);
+
+                out
+            }
+
+            #[doc = concat!(
+                "Converts the tensor to `",
+                stringify!($t),
+                "` with rounding to nearest and a shape known at runtime."
+            )]
+            pub fn $to_half_dynamic(
+                &self,
+            ) -> Tensor<$t, S, Contiguous, <P as DynamicAllocationPolicy<$t>>::Layout, P>
+            where
+                P: DynamicAllocationPolicy<$t>,
+            {
+                let mut out: Tensor<$t, S, Contiguous, _, P> = Tensor::alloc(self.shape());
+                convert(self, &mut out, $t::from_f32);
+
+                out
+            }
+        }
+    };
+}
+
+impl_half_precision!(f16, to_f16, to_f16_dynamic);
+impl_half_precision!(bf16, to_bf16, to_bf16_dynamic);
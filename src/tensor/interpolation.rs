@@ -0,0 +1,334 @@
+//! `interpolation` contains the operations that resize the spatial axes,
+//! i.e. all axes but the first two (batch and channels), of 3D and 4D
+//! tensors, as required by segmentation decoders or super-resolution models.
+//!
+//! `interpolate` resizes to a target shape while `upsample` multiplies
+//! the spatial dimensions by type-level scale factors. Both follow the
+//! conventions of PyTorch: with `align_corners`, the corner elements of
+//! the input and of the output are aligned, otherwise their edges are.
+//! Bicubic interpolation uses the coefficient `-0.75` and replicates the
+//! edges of the input.
+//!
+//! All modes are separable: each output element is a weighted sum of
+//! input elements whose weights are the products of 1D weights computed
+//! on each spatial axis. `interpolate_adjoint` distributes each element
+//! back with the same weights, which is used to compute gradients.
+//! Operations are parallelized over the batch and channel axes.
+
+use super::allocation_policy::{DynamicAllocationPolicy, StaticAllocationPolicy};
+use super::layout::{Layout, LayoutMut};
+use super::shape::{Resize, StaticShape, Upsample, TRUE};
+use super::tensor::Tensor;
+use super::transpose_policy::Contiguous;
+use num_traits::Float;
+use rayon::prelude::*;
+
+/// Interpolation modes. `Linear` applies to 3D tensors,
+/// `Bilinear` and `Bicubic` to 4D tensors and `Nearest` to both.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Interpolation {
+    Nearest,
+    Linear,
+    Bilinear,
+    Bicubic,
+}
+
+/// 1D kernel applied on each spatial axis.
+#[derive(Clone, Copy)]
+enum Kernel {
+    Nearest,
+    Linear,
+    Cubic,
+}
+
+impl Interpolation {
+    fn kernel(self, spatial_axes: usize) -> Kernel {
+        match (self, spatial_axes) {
+            (Interpolation::Nearest, 1) | (Interpolation::Nearest, 2) => Kernel::Nearest,
+            (Interpolation::Linear, 1) | (Interpolation::Bilinear, 2) => Kernel::Linear,
+            (Interpolation::Bicubic, 2) => Kernel::Cubic,
+            _ => panic!(
+                "{:?} interpolation is not available for tensors of order {}.",
+                self,
+                spatial_axes + 2
+            ),
+        }
+    }
+}
+
+/// Cubic convolution kernel with coefficient `A`.
+fn cubic(x: f64) -> f64 {
+    const A: f64 = -0.75;
+    let x = x.abs();
+    if x <= 1.0 {
+        ((A + 2.0) * x - (A + 3.0)) * x * x + 1.0
+    } else if x < 2.0 {
+        ((A * x - 5.0 * A) * x + 8.0 * A) * x - 4.0 * A
+    } else {
+        0.0
+    }
+}
+
+/// Computes, for each coordinate of an axis of length `out_len`, the
+/// coordinates of the axis of length `in_len` it is interpolated from
+/// along with their weights.
+fn axis_taps<T: Float>(
+    in_len: usize,
+    out_len: usize,
+    kernel: Kernel,
+    align_corners: bool,
+) -> Vec<Vec<(usize, T)>> {
+    let scale = in_len as f64 / out_len as f64;
+    let source = |i: usize| {
+        if align_corners {
+            if out_len > 1 {
+                i as f64 * (in_len as f64 - 1.0) / (out_len as f64 - 1.0)
+            } else {
+                0.0
+            }
+        } else {
+            (i as f64 + 0.5) * scale - 0.5
+        }
+    };
+    let clamp = |i: isize| i.max(0).min(in_len as isize - 1) as usize;
+    let weight = |w: f64| T::from(w).unwrap();
+
+    (0..out_len)
+        .map(|i| match kernel {
+            Kernel::Nearest => {
+                vec![(((i as f64 * scale) as usize).min(in_len - 1), T::one())]
+            }
+            Kernel::Linear => {
+                let x = source(i).max(0.0);
+                let x0 = x.floor();
+                let t = x - x0;
+                let x0 = x0 as isize;
+                vec![(clamp(x0), weight(1.0 - t)), (clamp(x0 + 1), weight(t))]
+            }
+            Kernel::Cubic => {
+                let x = source(i);
+                let x0 = x.floor();
+                let t = x - x0;
+                let x0 = x0 as isize;
+                (-1..3)
+                    .map(|k| (clamp(x0 + k), weight(cubic(t - k as f64))))
+                    .collect()
+            }
+        })
+        .collect()
+}
+
+/// Computes the taps of the two innermost axes of a spatial plane.
+/// 3D tensors are considered as planes with a single row.
+#[allow(clippy::type_complexity)]
+fn plane_taps<T: Float>(
+    shape: &[usize],
+    out_shape: &[usize],
+    mode: Interpolation,
+    align_corners: bool,
+) -> (Vec<Vec<(usize, T)>>, Vec<Vec<(usize, T)>>) {
+    assert!(
+        shape.len() == 3 || shape.len() == 4,
+        "Interpolation requires a 3D or 4D tensor. Got shape {:?}.",
+        shape
+    );
+    let kernel = mode.kernel(shape.len() - 2);
+    let len = shape.len();
+    let rows = if len == 4 {
+        axis_taps(shape[2], out_shape[2], kernel, align_corners)
+    } else {
+        vec![vec![(0, T::one())]]
+    };
+
+    (
+        rows,
+        axis_taps(shape[len - 1], out_shape[len - 1], kernel, align_corners),
+    )
+}
+
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    T: Float + Send + Sync,
+    L: for<'a> Layout<'a, T>,
+{
+    fn interpolate_unchecked<Sout, Lout>(
+        &self,
+        out_shape: Vec<usize>,
+        mode: Interpolation,
+        align_corners: bool,
+        out: &mut Tensor<T, Sout, Contiguous, Lout, P>,
+    ) where
+        Lout: for<'a> LayoutMut<'a, T>,
+    {
+        let shape = self.shape();
+        let plane_in: usize = shape.iter().skip(2).product();
+        let plane_out: usize = out_shape.iter().skip(2).product();
+        if plane_in == 0 || plane_out == 0 {
+            return;
+        }
+        let (rows, cols) = plane_taps::<T>(&shape, &out_shape, mode, align_corners);
+        let width = shape[shape.len() - 1];
+        let data = self.logical_vec();
+
+        out.par_chunks_mut(plane_out)
+            .zip(data.par_chunks(plane_in))
+            .for_each(|(plane_o, plane_i)| {
+                for (row_o, row_taps) in plane_o.chunks_mut(cols.len()).zip(rows.iter()) {
+                    for (o, col_taps) in row_o.iter_mut().zip(cols.iter()) {
+                        let mut acc = T::zero();
+                        for (y, wy) in row_taps.iter() {
+                            for (x, wx) in col_taps.iter() {
+                                acc = acc + *wy * *wx * plane_i[y * width + x];
+                            }
+                        }
+                        *o = acc;
+                    }
+                }
+            });
+    }
+
+    /// Accumulates each element of `self`, considered as interpolated from
+    /// a tensor of shape `shape`, in the elements of `out` it was interpolated
+    /// from with the same weights.
+    pub(crate) fn interpolate_adjoint<Sout, Lout>(
+        &self,
+        shape: Vec<usize>,
+        mode: Interpolation,
+        align_corners: bool,
+        out: &mut Tensor<T, Sout, Contiguous, Lout, P>,
+    ) where
+        Lout: for<'a> LayoutMut<'a, T>,
+    {
+        let grad_shape = self.shape();
+        let plane_in: usize = shape.iter().skip(2).product();
+        let plane_out: usize = grad_shape.iter().skip(2).product();
+        if plane_in == 0 || plane_out == 0 {
+            return;
+        }
+        let (rows, cols) = plane_taps::<T>(&shape, &grad_shape, mode, align_corners);
+        let width = shape[shape.len() - 1];
+        let grad = self.logical_vec();
+
+        out.par_chunks_mut(plane_in)
+            .zip(grad.par_chunks(plane_out))
+            .for_each(|(plane_i, plane_o)| {
+                for (row_o, row_taps) in plane_o.chunks(cols.len()).zip(rows.iter()) {
+                    for (o, col_taps) in row_o.iter().zip(cols.iter()) {
+                        for (y, wy) in row_taps.iter() {
+                            for (x, wx) in col_taps.iter() {
+                                let i = &mut plane_i[y * width + x];
+                                *i = *i + *wy * *wx * *o;
+                            }
+                        }
+                    }
+                }
+            });
+    }
+
+    /// Resizes the spatial axes of `self` to those of `Z` with the given `mode`.
+    pub fn interpolate<Z>(
+        &self,
+        mode: Interpolation,
+        align_corners: bool,
+    ) -> Tensor<T, Z, Contiguous, <P as StaticAllocationPolicy<T, Z>>::Layout, P>
+    where
+        S: Resize<Z>,
+        <S as Resize<Z>>::Output: TRUE,
+        Z: StaticShape,
+        P: StaticAllocationPolicy<T, Z>,
+    {
+        let mut out: Tensor<T, Z, Contiguous, _, P> = Tensor::default();
+        self.interpolate_unchecked(Z::to_vec(), mode, align_corners, &mut out);
+
+        out
+    }
+
+    /// Resizes the spatial axes of `self` to those of `shape` with the given `mode`
+    /// with shapes known at runtime.
+    pub fn interpolate_dynamic<Z>(
+        &self,
+        shape: Vec<usize>,
+        mode: Interpolation,
+        align_corners: bool,
+    ) -> Tensor<T, Z, Contiguous, <P as DynamicAllocationPolicy<T>>::Layout, P>
+    where
+        S: Resize<Z>,
+        <S as Resize<Z>>::Output: TRUE,
+        P: DynamicAllocationPolicy<T>,
+    {
+        let self_shape = self.shape();
+        assert!(
+            shape.len() == self_shape.len() && shape[..2] == self_shape[..2],
+            "Cannot interpolate a tensor of shape {:?} to shape {:?}.",
+            self_shape,
+            shape
+        );
+
+        let mut out: Tensor<T, Z, Contiguous, _, P> = Tensor::alloc(shape.clone());
+        self.interpolate_unchecked(shape, mode, align_corners, &mut out);
+
+        out
+    }
+
+    /// Multiplies the spatial dimensions of `self` by the
+    /// scale factors `Factors` with the given `mode`.
+    #[allow(clippy::type_complexity)]
+    pub fn upsample<Factors>(
+        &self,
+        mode: Interpolation,
+        align_corners: bool,
+    ) -> Tensor<
+        T,
+        <S as Upsample<Factors>>::Output,
+        Contiguous,
+        <P as StaticAllocationPolicy<T, <S as Upsample<Factors>>::Output>>::Layout,
+        P,
+    >
+    where
+        S: Upsample<Factors>,
+        <S as Upsample<Factors>>::Output: StaticShape,
+        P: StaticAllocationPolicy<T, <S as Upsample<Factors>>::Output>,
+    {
+        let mut out: Tensor<T, <S as Upsample<Factors>>::Output, Contiguous, _, P> =
+            Tensor::default();
+        self.interpolate_unchecked(
+            <S as Upsample<Factors>>::Output::to_vec(),
+            mode,
+            align_corners,
+            &mut out,
+        );
+
+        out
+    }
+
+    /// Multiplies the spatial dimensions of `self` by the scale factors
+    /// `Factors` with the given `mode` with a shape known at runtime.
+    #[allow(clippy::type_complexity)]
+    pub fn upsample_dynamic<Factors>(
+        &self,
+        mode: Interpolation,
+        align_corners: bool,
+    ) -> Tensor<
+        T,
+        <S as Upsample<Factors>>::Output,
+        Contiguous,
+        <P as DynamicAllocationPolicy<T>>::Layout,
+        P,
+    >
+    where
+        S: Upsample<Factors>,
+        Factors: StaticShape,
+        P: DynamicAllocationPolicy<T>,
+    {
+        let mut shape = self.shape();
+        for (dim, factor) in shape[2..].iter_mut().zip(Factors::to_vec()) {
+            *dim *= factor;
+        }
+
+        let mut out: Tensor<T, <S as Upsample<Factors>>::Output, Contiguous, _, P> =
+            Tensor::alloc(shape.clone());
+        self.interpolate_unchecked(shape, mode, align_corners, &mut out);
+
+        out
+    }
+}
@@ -1,26 +1,153 @@
 //! `linear_algebra` contains algebra-specific operations.
 //! It is currently limited to vector/vector, matrix/vector,
-//! and matrix/matrix dot products. It is entirely backed by
-//! openblas through C bindings.
+//! and matrix/matrix dot products and to the level 1 routines
+//! `axpy` and `scal`. It is backed by openblas through C bindings
+//! with the `blas` feature and by the pure-Rust kernels of `gemm` otherwise.
+//!
+//! Dot products come in two flavors: `dot` and `dotv` allocate their
+//! output whereas `dot_into` and `dotv_into` accumulate into an existing
+//! contiguous tensor `out` as `out = alpha * self * other + beta * out`.
+//!
+//! `dot` is also available for the half-precision types `f16` and `bf16`:
+//! operands are converted to `f32`, multiplied with `sgemm` and the result
+//! is rounded once, so that accumulation does not lose precision.
+//!
+//! Complex matrices are multiplied with `zgemm` and `cgemm`. Transposition
+//! does not conjugate, use `conj` from `core_ops` for the adjoint.
+//!
+//! BLAS only supports floats, `matmul` is the matrix product of any `Ring`
+//! such as integers and always uses the pure-Rust kernel. `matmul_i32`
+//! multiplies `i8` matrices with `i32` accumulation for quantized inference.
 //!
 //! To avoid code duplication, this module relies on the
 //! `expand_operations` procedural macro from the `melange_macro` crate.
 //!
+//! Outer, tensor and Kronecker products are found in `tensordot`.
+//!
 //! Note that only 1 dimmensional tensors are considered vectors
 //! and that only two dimmensional tensors are considered matrices.
 
+#[cfg(feature = "blas")]
 extern crate cblas;
+#[cfg(feature = "blas")]
 extern crate openblas_src;
 
 use super::allocation_policy::{DynamicAllocationPolicy, StaticAllocationPolicy};
-use super::layout::Layout;
-use super::shape::{Shape1D, Shape2D, TRUE};
+use super::gemm;
+use super::layout::{Layout, LayoutMut};
+use super::shape::{Shape1D, Shape2D, StaticShape, TRUE};
 use super::tensor::Tensor;
-use super::transpose_policy::{BLASPolicy, Contiguous};
-use cblas::{ddot, dgemm, dgemv, sdot, sgemm, sgemv};
+use super::transpose_policy::{BLASPolicy, Contiguous, Transpose};
+use crate::ring::Ring;
+use cblas::{cgemm, daxpy, ddot, dgemm, dgemv, dscal, saxpy, sdot, sgemm, sgemv, sscal, zgemm};
+use half::{bf16, f16};
 use melange_macros::expand_operations;
+use num_complex::{Complex32, Complex64};
+use rayon::prelude::*;
+use std::ops::{AddAssign, Mul};
 use typenum::{Eq, IsEqual, Unsigned};
 
+/// Drop-in replacement for the subset of the `cblas` crate used
+/// in this module, backed by the pure-Rust kernels of `gemm`.
+#[cfg(not(feature = "blas"))]
+#[allow(clippy::too_many_arguments, clippy::missing_safety_doc)]
+mod cblas {
+    use super::super::gemm;
+    pub use super::super::transpose_policy::Transpose;
+
+    /// Only row-major matrices are supported.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum Layout {
+        RowMajor,
+    }
+
+    macro_rules! impl_cblas {
+        ($t:ty, $gemm:ident) => {
+            pub unsafe fn $gemm(
+                _layout: Layout,
+                transa: Transpose,
+                transb: Transpose,
+                m: i32,
+                n: i32,
+                k: i32,
+                alpha: $t,
+                a: &[$t],
+                lda: i32,
+                b: &[$t],
+                ldb: i32,
+                beta: $t,
+                c: &mut [$t],
+                ldc: i32,
+            ) {
+                gemm::gemm(
+                    transa,
+                    transb,
+                    m as usize,
+                    n as usize,
+                    k as usize,
+                    alpha,
+                    a,
+                    lda as usize,
+                    b,
+                    ldb as usize,
+                    beta,
+                    c,
+                    ldc as usize,
+                )
+            }
+        };
+        ($t:ty, $gemm:ident, $gemv:ident, $dot:ident, $axpy:ident, $scal:ident) => {
+            impl_cblas!($t, $gemm);
+
+            pub unsafe fn $gemv(
+                _layout: Layout,
+                transa: Transpose,
+                m: i32,
+                n: i32,
+                alpha: $t,
+                a: &[$t],
+                lda: i32,
+                x: &[$t],
+                incx: i32,
+                beta: $t,
+                y: &mut [$t],
+                incy: i32,
+            ) {
+                gemm::gemv(
+                    transa,
+                    m as usize,
+                    n as usize,
+                    alpha,
+                    a,
+                    lda as usize,
+                    x,
+                    incx as usize,
+                    beta,
+                    y,
+                    incy as usize,
+                )
+            }
+
+            pub unsafe fn $dot(n: i32, x: &[$t], incx: i32, y: &[$t], incy: i32) -> $t {
+                gemm::dot(n as usize, x, incx as usize, y, incy as usize)
+            }
+
+            pub unsafe fn $axpy(n: i32, alpha: $t, x: &[$t], incx: i32, y: &mut [$t], incy: i32) {
+                gemm::axpy(n as usize, alpha, x, incx as usize, y, incy as usize)
+            }
+
+            pub unsafe fn $scal(n: i32, alpha: $t, x: &mut [$t], incx: i32) {
+                gemm::scal(n as usize, alpha, x, incx as usize)
+            }
+        };
+    }
+
+    impl_cblas!(f64, dgemm, dgemv, ddot, daxpy, dscal);
+    impl_cblas!(f32, sgemm, sgemv, sdot, saxpy, sscal);
+    impl_cblas!(num_complex::Complex64, zgemm);
+    impl_cblas!(num_complex::Complex32, cgemm);
+}
+
 #[expand_operations(
     dgemm<T=f64> as dot,
     sgemm<T=f32> as dot,
@@ -54,9 +181,9 @@ where
                 K::I32,
                 1.0,
                 self,
-                K::I32,
+                leading_dimension::<C>(M::I32, K::I32),
                 other,
-                N::I32,
+                leading_dimension::<Crhs>(K::I32, N::I32),
                 1.0,
                 &mut out,
                 N::I32,
@@ -98,9 +225,9 @@ where
                 self_shape[1] as i32,
                 1.0,
                 self,
-                self_shape[1] as i32,
+                leading_dimension::<C>(M::I32, self_shape[1] as i32),
                 other,
-                N::I32,
+                leading_dimension::<Crhs>(self_shape[1] as i32, N::I32),
                 1.0,
                 &mut out,
                 N::I32,
@@ -142,9 +269,9 @@ where
                 self_shape[1] as i32,
                 1.0,
                 self,
-                self_shape[1] as i32,
+                leading_dimension::<C>(self_shape[0] as i32, self_shape[1] as i32),
                 other,
-                other_shape[1] as i32,
+                leading_dimension::<Crhs>(other_shape[0] as i32, other_shape[1] as i32),
                 1.0,
                 &mut out,
                 other_shape[1] as i32,
@@ -175,16 +302,22 @@ where
         P: StaticAllocationPolicy<T, Shape1D<M>>,
     {
         let mut out: Tensor<T, Shape1D<M>, Contiguous, P::Layout, P> = Tensor::default();
+        // BLAS dimensions are the ones of the matrix in storage.
+        let (rows, cols) = if C::BLAS_TRANSPOSE == Transpose::None {
+            (M::I32, N::I32)
+        } else {
+            (N::I32, M::I32)
+        };
 
         unsafe {
             placeholder(
                 cblas::Layout::RowMajor,
                 C::BLAS_TRANSPOSE,
-                M::I32,
-                N::I32,
+                rows,
+                cols,
                 1.0,
                 self,
-                N::I32,
+                cols,
                 other,
                 1,
                 1.0,
@@ -215,16 +348,21 @@ where
             self_shape[1], other_shape[0], self_shape, other_shape,
         );
         let mut out: Tensor<T, Shape1D<M>, Contiguous, P::Layout, P> = Tensor::default();
+        let (rows, cols) = if C::BLAS_TRANSPOSE == Transpose::None {
+            (M::I32, self_shape[1] as i32)
+        } else {
+            (self_shape[1] as i32, M::I32)
+        };
 
         unsafe {
             placeholder(
                 cblas::Layout::RowMajor,
                 C::BLAS_TRANSPOSE,
-                M::I32,
-                self_shape[1] as i32,
+                rows,
+                cols,
                 1.0,
                 self,
-                self_shape[1] as i32,
+                cols,
                 other,
                 1,
                 1.0,
@@ -256,16 +394,21 @@ where
 
         let mut out: Tensor<T, Shape1D<M>, Contiguous, P::Layout, P> =
             Tensor::alloc(vec![self_shape[0]]);
+        let (rows, cols) = if C::BLAS_TRANSPOSE == Transpose::None {
+            (self_shape[0] as i32, self_shape[1] as i32)
+        } else {
+            (self_shape[1] as i32, self_shape[0] as i32)
+        };
 
         unsafe {
             placeholder(
                 cblas::Layout::RowMajor,
                 C::BLAS_TRANSPOSE,
-                self_shape[0] as i32,
-                self_shape[1] as i32,
+                rows,
+                cols,
                 1.0,
                 self,
-                self_shape[1] as i32,
+                cols,
                 other,
                 1,
                 1.0,
@@ -315,3 +458,652 @@ where
         unsafe { placeholder(self_shape[0] as i32, self, 1, other, 1) }
     }
 }
+
+/// Returns the leading dimension of the row-major storage
+/// of a logical `rows` x `cols` matrix with transpose policy `C`.
+fn leading_dimension<C: BLASPolicy>(rows: i32, cols: i32) -> i32 {
+    if C::BLAS_TRANSPOSE == Transpose::None {
+        cols
+    } else {
+        rows
+    }
+}
+
+#[expand_operations(
+    dgemm<T=f64> as dot_into,
+    sgemm<T=f32> as dot_into,
+)]
+impl<T, M, K, C, L, P> Tensor<T, Shape2D<M, K>, C, L, P>
+where
+    L: for<'a> Layout<'a, T>,
+    C: BLASPolicy,
+{
+    /// Computes `out = alpha * self * other + beta * out` in place.
+    pub fn operation<N, Crhs, Lrhs, Prhs, Lout, Pout>(
+        &self,
+        other: &Tensor<T, Shape2D<K, N>, Crhs, Lrhs, Prhs>,
+        out: &mut Tensor<T, Shape2D<M, N>, Contiguous, Lout, Pout>,
+        alpha: T,
+        beta: T,
+    ) where
+        M: Unsigned,
+        N: Unsigned,
+        K: Unsigned,
+        Lrhs: for<'a> Layout<'a, T>,
+        Crhs: BLASPolicy,
+        Lout: for<'a> LayoutMut<'a, T>,
+    {
+        unsafe {
+            placeholder(
+                cblas::Layout::RowMajor,
+                C::BLAS_TRANSPOSE,
+                Crhs::BLAS_TRANSPOSE,
+                M::I32,
+                N::I32,
+                K::I32,
+                alpha,
+                self,
+                leading_dimension::<C>(M::I32, K::I32),
+                other,
+                leading_dimension::<Crhs>(K::I32, N::I32),
+                beta,
+                out,
+                N::I32,
+            );
+        }
+    }
+
+    /// Computes `out = alpha * self * other + beta * out` in place,
+    /// checking the shapes at runtime.
+    pub fn dynamic<Krhs, N, Crhs, Lrhs, Prhs, Lout, Pout>(
+        &self,
+        other: &Tensor<T, Shape2D<Krhs, N>, Crhs, Lrhs, Prhs>,
+        out: &mut Tensor<T, Shape2D<M, N>, Contiguous, Lout, Pout>,
+        alpha: T,
+        beta: T,
+    ) where
+        K: IsEqual<Krhs>,
+        Eq<K, Krhs>: TRUE,
+        Lrhs: for<'a> Layout<'a, T>,
+        Crhs: BLASPolicy,
+        Lout: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>,
+    {
+        let self_shape = self.shape();
+        let other_shape = other.shape();
+        let out_shape = out.shape();
+        assert_eq!(
+            self_shape[1], other_shape[0],
+            "Contracted dimmensions {} and {} must be equal, got shapes {:?} and {:?}.",
+            self_shape[1], other_shape[0], self_shape, other_shape,
+        );
+        assert_eq!(
+            out_shape,
+            vec![self_shape[0], other_shape[1]],
+            "`out` must have shape {:?}, got {:?}.",
+            vec![self_shape[0], other_shape[1]],
+            out_shape,
+        );
+        let (m, n, k) = (
+            self_shape[0] as i32,
+            other_shape[1] as i32,
+            self_shape[1] as i32,
+        );
+
+        unsafe {
+            placeholder(
+                cblas::Layout::RowMajor,
+                C::BLAS_TRANSPOSE,
+                Crhs::BLAS_TRANSPOSE,
+                m,
+                n,
+                k,
+                alpha,
+                self,
+                leading_dimension::<C>(m, k),
+                other,
+                leading_dimension::<Crhs>(k, n),
+                beta,
+                out,
+                n,
+            );
+        }
+    }
+}
+
+#[expand_operations(
+    dgemv<T=f64> as dotv_into,
+    sgemv<T=f32> as dotv_into,
+)]
+impl<T, M, N, C, L, P> Tensor<T, Shape2D<M, N>, C, L, P>
+where
+    L: for<'a> Layout<'a, T>,
+    C: BLASPolicy,
+{
+    /// Computes `out = alpha * self * other + beta * out` in place.
+    pub fn operation<Crhs, Lrhs, Prhs, Lout, Pout>(
+        &self,
+        other: &Tensor<T, Shape1D<N>, Crhs, Lrhs, Prhs>,
+        out: &mut Tensor<T, Shape1D<M>, Contiguous, Lout, Pout>,
+        alpha: T,
+        beta: T,
+    ) where
+        M: Unsigned,
+        N: Unsigned,
+        Lrhs: for<'a> Layout<'a, T>,
+        Lout: for<'a> LayoutMut<'a, T>,
+    {
+        // BLAS dimensions are the ones of the matrix in storage.
+        let (rows, cols) = if C::BLAS_TRANSPOSE == Transpose::None {
+            (M::I32, N::I32)
+        } else {
+            (N::I32, M::I32)
+        };
+
+        unsafe {
+            placeholder(
+                cblas::Layout::RowMajor,
+                C::BLAS_TRANSPOSE,
+                rows,
+                cols,
+                alpha,
+                self,
+                cols,
+                other,
+                1,
+                beta,
+                out,
+                1,
+            );
+        }
+    }
+
+    /// Computes `out = alpha * self * other + beta * out` in place,
+    /// checking the shapes at runtime.
+    pub fn dynamic<Nrhs, Crhs, Lrhs, Prhs, Lout, Pout>(
+        &self,
+        other: &Tensor<T, Shape1D<Nrhs>, Crhs, Lrhs, Prhs>,
+        out: &mut Tensor<T, Shape1D<M>, Contiguous, Lout, Pout>,
+        alpha: T,
+        beta: T,
+    ) where
+        N: IsEqual<Nrhs>,
+        Eq<N, Nrhs>: TRUE,
+        Lrhs: for<'a> Layout<'a, T>,
+        Lout: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>,
+    {
+        let self_shape = self.shape();
+        let other_shape = other.shape();
+        let out_shape = out.shape();
+        assert_eq!(
+            self_shape[1], other_shape[0],
+            "Contracted dimmensions {} and {} must be equal, got shapes {:?} and {:?}.",
+            self_shape[1], other_shape[0], self_shape, other_shape,
+        );
+        assert_eq!(
+            out_shape[0],
+            self_shape[0],
+            "`out` must have shape {:?}, got {:?}.",
+            vec![self_shape[0]],
+            out_shape,
+        );
+        let (rows, cols) = if C::BLAS_TRANSPOSE == Transpose::None {
+            (self_shape[0] as i32, self_shape[1] as i32)
+        } else {
+            (self_shape[1] as i32, self_shape[0] as i32)
+        };
+
+        unsafe {
+            placeholder(
+                cblas::Layout::RowMajor,
+                C::BLAS_TRANSPOSE,
+                rows,
+                cols,
+                alpha,
+                self,
+                cols,
+                other,
+                1,
+                beta,
+                out,
+                1,
+            );
+        }
+    }
+}
+
+#[expand_operations(
+    daxpy<T=f64> as axpy,
+    saxpy<T=f32> as axpy,
+)]
+impl<T, S, L, P> Tensor<T, S, Contiguous, L, P>
+where
+    L: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>,
+{
+    /// Computes `self = alpha * x + self` in place.
+    pub fn operation_<Lrhs, Prhs>(&mut self, alpha: T, x: &Tensor<T, S, Contiguous, Lrhs, Prhs>)
+    where
+        S: StaticShape,
+        Lrhs: for<'a> Layout<'a, T>,
+    {
+        unsafe { placeholder(S::NUM_ELEMENTS as i32, alpha, x, 1, self, 1) }
+    }
+
+    /// Computes `self = alpha * x + self` in place,
+    /// checking the shapes at runtime.
+    pub fn dynamic_<Lrhs, Prhs>(&mut self, alpha: T, x: &Tensor<T, S, Contiguous, Lrhs, Prhs>)
+    where
+        Lrhs: for<'a> Layout<'a, T>,
+    {
+        assert_eq!(
+            self.shape(),
+            x.shape(),
+            "`self` and `x` must have the same shape. Got {:?} and {:?}.",
+            self.shape(),
+            x.shape()
+        );
+
+        unsafe { placeholder(self.num_elements() as i32, alpha, x, 1, self, 1) }
+    }
+}
+
+#[expand_operations(
+    dscal<T=f64> as scal,
+    sscal<T=f32> as scal,
+)]
+impl<T, S, L, P> Tensor<T, S, Contiguous, L, P>
+where
+    L: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>,
+{
+    /// Computes `self = alpha * self` in place.
+    pub fn operation_(&mut self, alpha: T) {
+        let n = self.num_elements() as i32;
+        unsafe { placeholder(n, alpha, self, 1) }
+    }
+}
+
+/// Checks that the contracted dimensions of matrices with shapes
+/// `self_shape` and `other_shape` are equal.
+fn check_contracted(self_shape: &[usize], other_shape: &[usize]) {
+    assert_eq!(
+        self_shape[1], other_shape[0],
+        "Contracted dimmensions {} and {} must be equal, got shapes {:?} and {:?}.",
+        self_shape[1], other_shape[0], self_shape, other_shape,
+    );
+}
+
+impl<T, M, K, C, L, P> Tensor<T, Shape2D<M, K>, C, L, P>
+where
+    T: Ring + Copy + Send + Sync + PartialEq + Mul<Output = T> + AddAssign,
+    L: for<'a> Layout<'a, T>,
+    C: BLASPolicy,
+{
+    /// Matrix product for any `Ring`, computed by the pure-Rust kernel
+    /// in parallel over blocks of output rows.
+    pub fn matmul<N, Crhs, Lrhs, Prhs>(
+        &self,
+        other: &Tensor<T, Shape2D<K, N>, Crhs, Lrhs, Prhs>,
+    ) -> Tensor<T, Shape2D<M, N>, Contiguous, P::Layout, P>
+    where
+        M: Unsigned,
+        N: Unsigned,
+        K: Unsigned,
+        Lrhs: for<'a> Layout<'a, T>,
+        Crhs: BLASPolicy,
+        P: StaticAllocationPolicy<T, Shape2D<M, N>>,
+    {
+        let mut out: Tensor<T, Shape2D<M, N>, Contiguous, P::Layout, P> = Tensor::default();
+        let (m, n, k) = (M::USIZE, N::USIZE, K::USIZE);
+
+        gemm::gemm(
+            C::BLAS_TRANSPOSE,
+            Crhs::BLAS_TRANSPOSE,
+            m,
+            n,
+            k,
+            T::ONE,
+            self,
+            leading_dimension::<C>(m as i32, k as i32) as usize,
+            other,
+            leading_dimension::<Crhs>(k as i32, n as i32) as usize,
+            T::ZERO,
+            &mut out,
+            n,
+        );
+
+        out
+    }
+
+    /// Matrix product for any `Ring` with shapes checked at runtime.
+    pub fn matmul_dynamic<Krhs, N, Crhs, Lrhs, Prhs>(
+        &self,
+        other: &Tensor<T, Shape2D<Krhs, N>, Crhs, Lrhs, Prhs>,
+    ) -> Tensor<T, Shape2D<M, N>, Contiguous, P::Layout, P>
+    where
+        K: IsEqual<Krhs>,
+        Eq<K, Krhs>: TRUE,
+        Lrhs: for<'a> Layout<'a, T>,
+        Crhs: BLASPolicy,
+        P: DynamicAllocationPolicy<T>,
+    {
+        let self_shape = self.shape();
+        let other_shape = other.shape();
+        check_contracted(&self_shape, &other_shape);
+        let (m, n, k) = (self_shape[0], other_shape[1], self_shape[1]);
+        let mut out: Tensor<T, Shape2D<M, N>, Contiguous, P::Layout, P> = Tensor::alloc(vec![m, n]);
+
+        gemm::gemm(
+            C::BLAS_TRANSPOSE,
+            Crhs::BLAS_TRANSPOSE,
+            m,
+            n,
+            k,
+            T::ONE,
+            self,
+            leading_dimension::<C>(m as i32, k as i32) as usize,
+            other,
+            leading_dimension::<Crhs>(k as i32, n as i32) as usize,
+            T::ZERO,
+            &mut out,
+            n,
+        );
+
+        out
+    }
+}
+
+impl<M, K, C, L, P> Tensor<i8, Shape2D<M, K>, C, L, P>
+where
+    L: for<'a> Layout<'a, i8>,
+    C: BLASPolicy,
+{
+    /// Matrix product of `i8` matrices accumulated in `i32`.
+    #[allow(clippy::type_complexity)]
+    pub fn matmul_i32<N, Crhs, Lrhs, Prhs>(
+        &self,
+        other: &Tensor<i8, Shape2D<K, N>, Crhs, Lrhs, Prhs>,
+    ) -> Tensor<
+        i32,
+        Shape2D<M, N>,
+        Contiguous,
+        <P as StaticAllocationPolicy<i32, Shape2D<M, N>>>::Layout,
+        P,
+    >
+    where
+        M: Unsigned,
+        N: Unsigned,
+        K: Unsigned,
+        Lrhs: for<'a> Layout<'a, i8>,
+        Crhs: BLASPolicy,
+        P: StaticAllocationPolicy<i32, Shape2D<M, N>>,
+    {
+        let mut out: Tensor<
+            i32,
+            Shape2D<M, N>,
+            Contiguous,
+            <P as StaticAllocationPolicy<i32, Shape2D<M, N>>>::Layout,
+            P,
+        > = Tensor::default();
+        let (m, n, k) = (M::USIZE, N::USIZE, K::USIZE);
+
+        gemm::gemm_i8(
+            C::BLAS_TRANSPOSE,
+            Crhs::BLAS_TRANSPOSE,
+            m,
+            n,
+            k,
+            self,
+            leading_dimension::<C>(m as i32, k as i32) as usize,
+            other,
+            leading_dimension::<Crhs>(k as i32, n as i32) as usize,
+            &mut out,
+            n,
+        );
+
+        out
+    }
+
+    /// Matrix product of `i8` matrices accumulated in `i32`
+    /// with shapes checked at runtime.
+    pub fn matmul_i32_dynamic<Krhs, N, Crhs, Lrhs, Prhs>(
+        &self,
+        other: &Tensor<i8, Shape2D<Krhs, N>, Crhs, Lrhs, Prhs>,
+    ) -> Tensor<i32, Shape2D<M, N>, Contiguous, <P as DynamicAllocationPolicy<i32>>::Layout, P>
+    where
+        K: IsEqual<Krhs>,
+        Eq<K, Krhs>: TRUE,
+        Lrhs: for<'a> Layout<'a, i8>,
+        Crhs: BLASPolicy,
+        P: DynamicAllocationPolicy<i32>,
+    {
+        let self_shape = self.shape();
+        let other_shape = other.shape();
+        check_contracted(&self_shape, &other_shape);
+        let (m, n, k) = (self_shape[0], other_shape[1], self_shape[1]);
+        let mut out: Tensor<
+            i32,
+            Shape2D<M, N>,
+            Contiguous,
+            <P as DynamicAllocationPolicy<i32>>::Layout,
+            P,
+        > = Tensor::alloc(vec![m, n]);
+
+        gemm::gemm_i8(
+            C::BLAS_TRANSPOSE,
+            Crhs::BLAS_TRANSPOSE,
+            m,
+            n,
+            k,
+            self,
+            leading_dimension::<C>(m as i32, k as i32) as usize,
+            other,
+            leading_dimension::<Crhs>(k as i32, n as i32) as usize,
+            &mut out,
+            n,
+        );
+
+        out
+    }
+}
+
+/// Computes the `m` x `n` product of half-precision matrices in `f32`
+/// and writes the rounded result in `out`.
+#[allow(clippy::too_many_arguments)]
+fn half_gemm<H, Ca, Cb>(
+    m: usize,
+    n: usize,
+    k: usize,
+    a: &[H],
+    b: &[H],
+    out: &mut [H],
+    to_f32: fn(H) -> f32,
+    from_f32: fn(f32) -> H,
+) where
+    H: Copy + Send + Sync,
+    Ca: BLASPolicy,
+    Cb: BLASPolicy,
+{
+    // Storage order is preserved so that transpose policies still apply.
+    let a: Vec<f32> = a.par_iter().map(|x| to_f32(*x)).collect();
+    let b: Vec<f32> = b.par_iter().map(|x| to_f32(*x)).collect();
+    let mut c = vec![0.0; m * n];
+
+    unsafe {
+        sgemm(
+            cblas::Layout::RowMajor,
+            Ca::BLAS_TRANSPOSE,
+            Cb::BLAS_TRANSPOSE,
+            m as i32,
+            n as i32,
+            k as i32,
+            1.0,
+            &a,
+            leading_dimension::<Ca>(m as i32, k as i32),
+            &b,
+            leading_dimension::<Cb>(k as i32, n as i32),
+            0.0,
+            &mut c,
+            n as i32,
+        );
+    }
+
+    out.par_iter_mut()
+        .zip(c.par_iter())
+        .for_each(|(y, x)| *y = from_f32(*x));
+}
+
+macro_rules! impl_half_dot {
+    ($t:ident) => {
+        impl<M, K, C, L, P> Tensor<$t, Shape2D<M, K>, C, L, P>
+        where
+            L: for<'a> Layout<'a, $t>,
+            C: BLASPolicy,
+        {
+            /// Matrix product accumulated in `f32`.
+            pub fn dot<N, Crhs, Lrhs, Prhs>(
+                &self,
+                other: &Tensor<$t, Shape2D<K, N>, Crhs, Lrhs, Prhs>,
+            ) -> Tensor<$t, Shape2D<M, N>, Contiguous, P::Layout, P>
+            where
+                M: Unsigned,
+                N: Unsigned,
+                K: Unsigned,
+                Lrhs: for<'a> Layout<'a, $t>,
+                Crhs: BLASPolicy,
+                P: StaticAllocationPolicy<$t, Shape2D<M, N>>,
+            {
+                let mut out: Tensor<$t, Shape2D<M, N>, Contiguous, P::Layout, P> =
+                    Tensor::default();
+                half_gemm::<_, C, Crhs>(
+                    M::USIZE,
+                    N::USIZE,
+                    K::USIZE,
+                    self,
+                    other,
+                    &mut out,
+                    $t::to_f32,
+                    $t::from_f32,
+                );
+
+                out
+            }
+
+            /// Matrix product accumulated in `f32` with shapes checked at runtime.
+            pub fn dot_dynamic<Krhs, N, Crhs, Lrhs, Prhs>(
+                &self,
+                other: &Tensor<$t, Shape2D<Krhs, N>, Crhs, Lrhs, Prhs>,
+            ) -> Tensor<$t, Shape2D<M, N>, Contiguous, P::Layout, P>
+            where
+                K: IsEqual<Krhs>,
+                Eq<K, Krhs>: TRUE,
+                Lrhs: for<'a> Layout<'a, $t>,
+                Crhs: BLASPolicy,
+                P: DynamicAllocationPolicy<$t>,
+            {
+                let self_shape = self.shape();
+                let other_shape = other.shape();
+                check_contracted(&self_shape, &other_shape);
+                let (m, n, k) = (self_shape[0], other_shape[1], self_shape[1]);
+                let mut out: Tensor<$t, Shape2D<M, N>, Contiguous, P::Layout, P> =
+                    Tensor::alloc(vec![m, n]);
+                half_gemm::<_, C, Crhs>(m, n, k, self, other, &mut out, $t::to_f32, $t::from_f32);
+
+                out
+            }
+        }
+    };
+}
+
+impl_half_dot!(f16);
+impl_half_dot!(bf16);
+
+#[expand_operations(
+    zgemm<T=Complex64> as dot,
+    cgemm<T=Complex32> as dot,
+)]
+impl<T, M, K, C, L, P> Tensor<T, Shape2D<M, K>, C, L, P>
+where
+    L: for<'a> Layout<'a, T>,
+    C: BLASPolicy,
+{
+    /// Complex matrix product.
+    pub fn operation<N, Crhs, Lrhs, Prhs>(
+        &self,
+        other: &Tensor<T, Shape2D<K, N>, Crhs, Lrhs, Prhs>,
+    ) -> Tensor<T, Shape2D<M, N>, Contiguous, P::Layout, P>
+    where
+        M: Unsigned,
+        N: Unsigned,
+        K: Unsigned,
+        Lrhs: for<'a> Layout<'a, T>,
+        Crhs: BLASPolicy,
+        P: StaticAllocationPolicy<T, Shape2D<M, N>>,
+    {
+        let mut out: Tensor<T, Shape2D<M, N>, Contiguous, P::Layout, P> = Tensor::default();
+
+        unsafe {
+            placeholder(
+                cblas::Layout::RowMajor,
+                C::BLAS_TRANSPOSE,
+                Crhs::BLAS_TRANSPOSE,
+                M::I32,
+                N::I32,
+                K::I32,
+                <T as Ring>::ONE,
+                self,
+                leading_dimension::<C>(M::I32, K::I32),
+                other,
+                leading_dimension::<Crhs>(K::I32, N::I32),
+                <T as Ring>::ZERO,
+                &mut out,
+                N::I32,
+            );
+        }
+
+        out
+    }
+
+    /// Complex matrix product with shapes checked at runtime.
+    pub fn dynamic<Krhs, N, Crhs, Lrhs, Prhs>(
+        &self,
+        other: &Tensor<T, Shape2D<Krhs, N>, Crhs, Lrhs, Prhs>,
+    ) -> Tensor<T, Shape2D<M, N>, Contiguous, P::Layout, P>
+    where
+        K: IsEqual<Krhs>,
+        Eq<K, Krhs>: TRUE,
+        Lrhs: for<'a> Layout<'a, T>,
+        Crhs: BLASPolicy,
+        P: DynamicAllocationPolicy<T>,
+    {
+        let self_shape = self.shape();
+        let other_shape = other.shape();
+        check_contracted(&self_shape, &other_shape);
+        let (m, n, k) = (
+            self_shape[0] as i32,
+            other_shape[1] as i32,
+            self_shape[1] as i32,
+        );
+        let mut out: Tensor<T, Shape2D<M, N>, Contiguous, P::Layout, P> =
+            Tensor::alloc(vec![m as usize, n as usize]);
+
+        unsafe {
+            placeholder(
+                cblas::Layout::RowMajor,
+                C::BLAS_TRANSPOSE,
+                Crhs::BLAS_TRANSPOSE,
+                m,
+                n,
+                k,
+                <T as Ring>::ONE,
+                self,
+                leading_dimension::<C>(m, k),
+                other,
+                leading_dimension::<Crhs>(k, n),
+                <T as Ring>::ZERO,
+                &mut out,
+                n,
+            );
+        }
+
+        out
+    }
+}
@@ -6,15 +6,31 @@
 //! crate.
 
 pub mod allocation_policy;
+pub mod cast;
+pub mod complex;
 pub mod core_ops;
+#[cfg(feature = "blas")]
+pub mod decomposition;
+pub mod display;
+pub mod einsum;
+pub mod gemm;
+pub mod half_precision;
 pub mod heap_layout;
+pub mod interpolation;
 pub mod layout;
 pub mod linear_algebra;
+pub mod pad;
 pub mod prelude;
 pub mod reduction;
+pub mod scan;
+#[cfg(feature = "serde")]
+pub mod serialization;
 pub mod shape;
+pub mod sort;
 pub mod slice_layout;
+pub mod slice_layout_mut;
 pub mod stack_layout;
 pub mod static_heap_layout;
 pub mod tensor;
+pub mod tensordot;
 pub mod transpose_policy;
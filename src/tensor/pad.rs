@@ -0,0 +1,226 @@
+//! `pad` contains the operations that extend a tensor with
+//! elements before and after each axis, as required by convolutions
+//! with "same" padding or batches of sequences of different lengths.
+//!
+//! The amounts of padding are given as `TArr` of type-level unsigned
+//! integers `Before` and `After`, which can contain zeros, of the same
+//! length as the shape of `self`. The shape of
+//! the result is computed by the `Pad` type operator.
+//!
+//! Padded elements are filled according to a `PadMode`. Each element
+//! of the result is read from a source element of `self` or is the
+//! constant value. `pad_adjoint` performs the reverse mapping and
+//! accumulates each element to its source, which is used to compute
+//! gradients.
+
+use super::allocation_policy::{DynamicAllocationPolicy, StaticAllocationPolicy};
+use super::layout::{Layout, LayoutMut};
+use super::shape::{intrinsic_strides_in_place, Pad, Padding, StaticShape};
+use super::tensor::Tensor;
+use super::transpose_policy::Contiguous;
+use rayon::prelude::*;
+use std::ops::AddAssign;
+
+/// Ways of filling padded elements. Taking `[1, 2, 3]`
+/// padded with 2 elements on each side as an example:
+/// * `Constant(0)` gives `[0, 0, 1, 2, 3, 0, 0]`,
+/// * `Reflect` gives `[3, 2, 1, 2, 3, 2, 1]` (edges are not repeated),
+/// * `Replicate` gives `[1, 1, 1, 2, 3, 3, 3]`,
+/// * `Circular` gives `[2, 3, 1, 2, 3, 1, 2]`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PadMode<T> {
+    Constant(T),
+    Reflect,
+    Replicate,
+    Circular,
+}
+
+impl<T> PadMode<T> {
+    /// Outputs the coordinate in `0..len` that the coordinate `i`
+    /// of an axis of length `len` is read from, `None` for constant padding.
+    fn source(&self, i: isize, len: usize) -> Option<usize> {
+        let len_i = len as isize;
+        if 0 <= i && i < len_i {
+            return Some(i as usize);
+        }
+
+        match self {
+            PadMode::Constant(_) => None,
+            PadMode::Replicate => Some(if i < 0 { 0 } else { len - 1 }),
+            PadMode::Circular => Some(i.rem_euclid(len_i) as usize),
+            PadMode::Reflect if len == 1 => Some(0),
+            PadMode::Reflect => {
+                let period = 2 * (len_i - 1);
+                let i = i.rem_euclid(period);
+                Some(if i < len_i { i } else { period - i } as usize)
+            }
+        }
+    }
+}
+
+/// Maps each coordinate of each axis of the padded shape
+/// to its source coordinate in the original shape.
+fn source_maps<T>(
+    shape: &[usize],
+    before: &[usize],
+    padded_shape: &[usize],
+    mode: &PadMode<T>,
+) -> Vec<Vec<Option<usize>>> {
+    shape
+        .iter()
+        .zip(before.iter())
+        .zip(padded_shape.iter())
+        .map(|((len, before), padded_len)| {
+            assert!(
+                *len > 0 || *padded_len == 0 || matches!(mode, PadMode::Constant(_)),
+                "Cannot pad an empty axis with a non-constant mode."
+            );
+            (0..*padded_len)
+                .map(|i| mode.source(i as isize - *before as isize, *len))
+                .collect()
+        })
+        .collect()
+}
+
+/// Outputs the flat index in `shape` of the source of the element at flat index
+/// `index` in the padded shape, `None` for constant padding.
+#[inline]
+fn source_index(
+    mut index: usize,
+    maps: &[Vec<Option<usize>>],
+    strides: &[usize],
+    padded_strides: &[usize],
+) -> Option<usize> {
+    let mut source = 0;
+    for ((map, stride), padded_stride) in maps.iter().zip(strides.iter()).zip(padded_strides.iter())
+    {
+        source += map[index / padded_stride]? * stride;
+        index %= padded_stride;
+    }
+
+    Some(source)
+}
+
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    T: Send + Sync + Copy,
+    L: for<'a> Layout<'a, T>,
+{
+    fn pad_unchecked<Sout, Lout>(
+        &self,
+        before: &[usize],
+        padded_shape: Vec<usize>,
+        mode: PadMode<T>,
+        out: &mut Tensor<T, Sout, Contiguous, Lout, P>,
+    ) where
+        Lout: for<'a> LayoutMut<'a, T>,
+    {
+        let shape = self.shape();
+        let maps = source_maps(&shape, before, &padded_shape, &mode);
+        let strides = intrinsic_strides_in_place(shape);
+        let padded_strides = intrinsic_strides_in_place(padded_shape);
+        let data = self.logical_vec();
+
+        out.par_iter_mut().enumerate().for_each(|(i, x)| {
+            *x = match source_index(i, &maps, &strides, &padded_strides) {
+                Some(source) => data[source],
+                None => match mode {
+                    PadMode::Constant(value) => value,
+                    _ => unreachable!(),
+                },
+            }
+        });
+    }
+
+    /// Accumulates each element of `self`, considered as padded with `before`
+    /// and `mode`, in its source element of `out`. Elements that come from
+    /// constant padding are discarded.
+    pub(crate) fn pad_adjoint<Sout, Lout>(
+        &self,
+        before: &[usize],
+        shape: Vec<usize>,
+        mode: PadMode<T>,
+        out: &mut Tensor<T, Sout, Contiguous, Lout, P>,
+    ) where
+        T: AddAssign,
+        Lout: for<'a> LayoutMut<'a, T>,
+    {
+        let padded_shape: Vec<usize> = self.shape();
+        let maps = source_maps(&shape, before, &padded_shape, &mode);
+        let strides = intrinsic_strides_in_place(shape);
+        let padded_strides = intrinsic_strides_in_place(padded_shape);
+
+        for (i, x) in self.logical_vec().into_iter().enumerate() {
+            if let Some(source) = source_index(i, &maps, &strides, &padded_strides) {
+                out[source] += x;
+            }
+        }
+    }
+
+    /// Extends each axis of `self` with the dimensions of `Before`
+    /// and `After` on the same axis, filled according to `mode`.
+    #[allow(clippy::type_complexity)]
+    pub fn pad<Before, After>(
+        &self,
+        mode: PadMode<T>,
+    ) -> Tensor<
+        T,
+        <S as Pad<Before, After>>::Output,
+        Contiguous,
+        <P as StaticAllocationPolicy<T, <S as Pad<Before, After>>::Output>>::Layout,
+        P,
+    >
+    where
+        S: Pad<Before, After>,
+        <S as Pad<Before, After>>::Output: StaticShape,
+        Before: Padding,
+        P: StaticAllocationPolicy<T, <S as Pad<Before, After>>::Output>,
+    {
+        let mut out: Tensor<T, <S as Pad<Before, After>>::Output, Contiguous, _, P> =
+            Tensor::default();
+        self.pad_unchecked(
+            &Before::amounts(),
+            <S as Pad<Before, After>>::Output::to_vec(),
+            mode,
+            &mut out,
+        );
+
+        out
+    }
+
+    /// Extends each axis of `self` with the dimensions of `Before`
+    /// and `After` on the same axis, filled according to `mode`,
+    /// with a shape known at runtime.
+    #[allow(clippy::type_complexity)]
+    pub fn pad_dynamic<Before, After>(
+        &self,
+        mode: PadMode<T>,
+    ) -> Tensor<
+        T,
+        <S as Pad<Before, After>>::Output,
+        Contiguous,
+        <P as DynamicAllocationPolicy<T>>::Layout,
+        P,
+    >
+    where
+        S: Pad<Before, After>,
+        Before: Padding,
+        After: Padding,
+        P: DynamicAllocationPolicy<T>,
+    {
+        let before = Before::amounts();
+        let padded_shape: Vec<usize> = self
+            .shape()
+            .iter()
+            .zip(before.iter())
+            .zip(After::amounts())
+            .map(|((len, before), after)| len + before + after)
+            .collect();
+
+        let mut out: Tensor<T, <S as Pad<Before, After>>::Output, Contiguous, _, P> =
+            Tensor::alloc(padded_shape.clone());
+        self.pad_unchecked(&before, padded_shape, mode, &mut out);
+
+        out
+    }
+}
@@ -1,13 +1,20 @@
 use super::allocation_policy::{DefaultPolicy, StackFirstPolicy};
 use super::heap_layout::HeapLayout;
 use super::slice_layout::SliceLayout;
+use super::slice_layout_mut::SliceLayoutMut;
 use super::stack_layout::StackLayout;
 use super::static_heap_layout::StaticHeapLayout;
 use super::transpose_policy::{Contiguous, Strided, Transposed};
 
+#[cfg(feature = "blas")]
+pub use super::decomposition::LinalgError;
+pub use super::interpolation::Interpolation;
 pub use super::layout::*;
+pub use super::pad::PadMode;
 pub use super::shape::*;
+pub use super::sort::SortOrder;
 pub use super::tensor::Tensor;
+pub use melange_macros::einsum;
 
 /// Default static tensor stored on the heap.
 pub type StaticTensor<T, S> = Tensor<T, S, Contiguous, StaticHeapLayout<T, S>, DefaultPolicy>;
@@ -25,6 +32,17 @@ pub type TransposedSliceTensor<'a, T, S> =
 /// Strided default view.
 pub type StridedSliceTensor<'a, T, S> = Tensor<T, S, Strided, SliceLayout<'a, T>, DefaultPolicy>;
 
+/// Default mutable view tensor.
+pub type SliceMutTensor<'a, T, S> = Tensor<T, S, Contiguous, SliceLayoutMut<'a, T>, DefaultPolicy>;
+
+/// Transposed default mutable view.
+pub type TransposedSliceMutTensor<'a, T, S> =
+    Tensor<T, S, Transposed, SliceLayoutMut<'a, T>, DefaultPolicy>;
+
+/// Strided default mutable view.
+pub type StridedSliceMutTensor<'a, T, S> =
+    Tensor<T, S, Strided, SliceLayoutMut<'a, T>, DefaultPolicy>;
+
 /// Stack allocated tensor.
 pub type StackTensor<T, S> = Tensor<T, S, Contiguous, StackLayout<T, S>, StackFirstPolicy>;
 
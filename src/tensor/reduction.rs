@@ -1,7 +1,11 @@
 //! `reductions` contains mathematical operations that aply to one
 //! tensor and output a tensor with the same shape except for one
 //! dimension that is reduced to 1.
-//! This covers sum, product, max and min over a certain axis.
+//! This covers sum, product, max and min over a certain axis as well as
+//! `any` and `all` for boolean tensors.
+//!
+//! `sum_to` sums over all the axes along which a shape is broadcast to the
+//! shape of the tensor, which undoes broadcasting when computing gradients.
 //!
 //! Like core ops, these methods heavily use the chunks feature
 //! of the `Layout` trait to parallelize.
@@ -11,10 +15,14 @@
 
 use super::allocation_policy::{DynamicAllocationPolicy, StaticAllocationPolicy};
 use super::layout::{Layout, LayoutMut};
-use super::shape::{At, Reduction, ReductionOptChunckSize};
+use super::shape::{
+    intrinsic_strides_in_place, At, BroadcastTo, Reduction, ReductionOptChunckSize, StaticShape,
+    TRUE,
+};
 use super::tensor::Tensor;
 use super::transpose_policy::Contiguous;
 use rayon::prelude::*;
+use half::{bf16, f16};
 use melange_macros::expand_operations;
 use std::ops::*;
 use typenum::Unsigned;
@@ -109,6 +117,95 @@ where
     min<T=f64> as reduce_min,
     max<T=f32> as reduce_max,
     min<T=f32> as reduce_min,
+    max<T=f16> as reduce_max,
+    min<T=f16> as reduce_min,
+    max<T=bf16> as reduce_max,
+    min<T=bf16> as reduce_min,
+)]
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    L: for<'a> Layout<'a, T>,
+{
+    #[inline]
+    fn unchecked<Sout, Lout>(
+        &self,
+        chunk_size_in: usize,
+        chunk_size_out: usize,
+        mid_loop_num: usize,
+        inner_loop_num: usize,
+        out: &mut Tensor<T, Sout, Contiguous, Lout, P>,
+    ) where
+        Lout: for<'a> LayoutMut<'a, T>,
+    {
+        let mut in_iter = self.chunks(chunk_size_in);
+        for chunk_o in out.chunks_mut(chunk_size_out) {
+            for _ in 0..mid_loop_num {
+                for j in 0..inner_loop_num {
+                    let chunk_i = in_iter.next().unwrap();
+                    chunk_o
+                        .par_iter_mut()
+                        .skip(j * chunk_size_in)
+                        .zip(chunk_i.par_iter())
+                        .for_each(|(o, i)| *o = o.placeholder(*i));
+                }
+            }
+        }
+    }
+    pub fn operation<Ax>(&self) -> Tensor<T, <S as Reduction<Ax>>::Output, Contiguous, P::Layout, P>
+    where
+        S: Reduction<Ax> + ReductionOptChunckSize<T, Ax> + At<Ax>,
+        P: StaticAllocationPolicy<T, <S as Reduction<Ax>>::Output>,
+    {
+        let chunk_size_out = <<S as ReductionOptChunckSize<T, Ax>>::Output as Unsigned>::USIZE;
+        let chunk_size_in = self.opt_chunk_size().min(chunk_size_out);
+
+        let inner_loop_num = chunk_size_out / chunk_size_in;
+        let mid_loop_num = <<S as At<Ax>>::Output as Unsigned>::USIZE;
+
+        let mut out: Tensor<T, <S as Reduction<Ax>>::Output, Contiguous, P::Layout, P> =
+            Tensor::default();
+
+        self.unchecked(
+            chunk_size_in,
+            chunk_size_out,
+            mid_loop_num,
+            inner_loop_num,
+            &mut out,
+        );
+        out
+    }
+
+    pub fn dynamic<Ax>(&self) -> Tensor<T, <S as Reduction<Ax>>::Output, Contiguous, P::Layout, P>
+    where
+        S: Reduction<Ax>,
+        P: DynamicAllocationPolicy<T>,
+        Ax: Unsigned,
+    {
+        let mut shape = self.shape();
+
+        let chunk_size_out = shape.iter().skip(Ax::USIZE + 1).product();
+        let chunk_size_in = self.opt_chunk_size().min(chunk_size_out);
+
+        let inner_loop_num = chunk_size_out / chunk_size_in;
+        let mid_loop_num = shape.remove(Ax::USIZE);
+
+        let mut out: Tensor<T, <S as Reduction<Ax>>::Output, Contiguous, P::Layout, P> =
+            Tensor::alloc(shape);
+
+        self.unchecked(
+            chunk_size_in,
+            chunk_size_out,
+            mid_loop_num,
+            inner_loop_num,
+            &mut out,
+        );
+        out
+    }
+}
+
+#[expand_operations(
+    bitor<T=bool> as any,
+    bitand<T=bool> as all,
 )]
 impl<T, S, C, L, P> Tensor<T, S, C, L, P>
 where
@@ -125,6 +222,10 @@ where
     ) where
         Lout: for<'a> LayoutMut<'a, T>,
     {
+        // `false` is the identity of `|` and `true` the identity of `&`.
+        let identity = !false.placeholder(true);
+        out.iter_mut().for_each(|o| *o = identity);
+
         let mut in_iter = self.chunks(chunk_size_in);
         for chunk_o in out.chunks_mut(chunk_size_out) {
             for _ in 0..mid_loop_num {
@@ -139,6 +240,7 @@ where
             }
         }
     }
+
     pub fn operation<Ax>(&self) -> Tensor<T, <S as Reduction<Ax>>::Output, Contiguous, P::Layout, P>
     where
         S: Reduction<Ax> + ReductionOptChunckSize<T, Ax> + At<Ax>,
@@ -190,3 +292,95 @@ where
         out
     }
 }
+
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    T: Send + Sync + Copy + AddAssign,
+    L: for<'a> Layout<'a, T>,
+{
+    fn sum_to_unchecked<Sout, Lout>(&self, shape: Vec<usize>, out: &mut Tensor<T, Sout, Contiguous, Lout, P>)
+    where
+        Lout: for<'a> LayoutMut<'a, T>,
+    {
+        // Leading axes missing in `shape` and axes where it is 1
+        // while `self` is not are reduced, other axes are kept.
+        let self_shape = self.shape();
+        let leading = self_shape.len() - shape.len();
+        let (mut kept, mut reduced) = (Vec::new(), Vec::new());
+        for (axis, (dim, stride)) in self_shape
+            .iter()
+            .zip(intrinsic_strides_in_place(self_shape.clone()))
+            .enumerate()
+        {
+            if axis >= leading && shape[axis - leading] == *dim {
+                kept.push((*dim, stride));
+            } else {
+                reduced.push((*dim, stride));
+            }
+        }
+
+        let offset = |mut i: usize, group: &[(usize, usize)]| {
+            let mut offset = 0;
+            for (dim, stride) in group.iter().rev() {
+                offset += i % dim * stride;
+                i /= dim;
+            }
+
+            offset
+        };
+        let reduced_num: usize = reduced.iter().map(|(dim, _)| dim).product();
+        let data = self.logical_vec();
+
+        out.par_iter_mut().enumerate().for_each(|(i, o)| {
+            let base = offset(i, &kept);
+            for j in 0..reduced_num {
+                *o += data[base + offset(j, &reduced)];
+            }
+        });
+    }
+
+    /// Sums `self` over the axes along which `Z` is broadcast to its shape.
+    pub fn sum_to<Z>(&self) -> Tensor<T, Z, Contiguous, <P as StaticAllocationPolicy<T, Z>>::Layout, P>
+    where
+        Z: StaticShape + BroadcastTo<S>,
+        <Z as BroadcastTo<S>>::Output: TRUE,
+        P: StaticAllocationPolicy<T, Z>,
+    {
+        let mut out: Tensor<T, Z, Contiguous, <P as StaticAllocationPolicy<T, Z>>::Layout, P> =
+            Tensor::default();
+        self.sum_to_unchecked(Z::to_vec(), &mut out);
+
+        out
+    }
+
+    /// Sums `self` over the axes along which `shape` is broadcast
+    /// to its shape with shapes checked at runtime.
+    pub fn sum_to_dynamic<Z>(
+        &self,
+        shape: Vec<usize>,
+    ) -> Tensor<T, Z, Contiguous, <P as DynamicAllocationPolicy<T>>::Layout, P>
+    where
+        Z: BroadcastTo<S>,
+        <Z as BroadcastTo<S>>::Output: TRUE,
+        P: DynamicAllocationPolicy<T>,
+    {
+        let self_shape = self.shape();
+        assert!(
+            shape.len() <= self_shape.len()
+                && shape
+                    .iter()
+                    .rev()
+                    .zip(self_shape.iter().rev())
+                    .all(|(x, y)| *x == 1 || *x == *y),
+            "Cannot sum a tensor of shape {:?} to shape {:?}.",
+            self_shape,
+            shape
+        );
+
+        let mut out: Tensor<T, Z, Contiguous, <P as DynamicAllocationPolicy<T>>::Layout, P> =
+            Tensor::alloc(shape.clone());
+        self.sum_to_unchecked(shape, &mut out);
+
+        out
+    }
+}
@@ -0,0 +1,103 @@
+//! `scan` contains cumulative operations that apply to one tensor along
+//! a certain axis and output a tensor with the same shape: `cumsum`,
+//! `cumprod`, `cummax` and `cummin`. Element `k` along the axis of the
+//! output combines elements `0..=k` along the same axis of the input.
+//!
+//! Scans reuse the shape operators of `reduction` to compute the size of
+//! the contiguous groups of elements that are combined together. They are
+//! parallelized over the blocks of the axes before the scanned axis and
+//! over the elements of the axes after it.
+//!
+//! To avoid code duplication, this module relies on the
+//! `expand_operations` procedural macro from the `melange_macro` crate.
+
+use super::allocation_policy::{DynamicAllocationPolicy, StaticAllocationPolicy};
+use super::layout::{Layout, LayoutMut};
+use super::shape::{At, ReductionOptChunckSize};
+use super::tensor::Tensor;
+use super::transpose_policy::Contiguous;
+use half::{bf16, f16};
+use melange_macros::expand_operations;
+use rayon::prelude::*;
+use std::ops::*;
+use typenum::Unsigned;
+
+#[expand_operations(
+    add<T: Send + Sync + Copy + Add<Output = T>> as cumsum,
+    mul<T: Send + Sync + Copy + Mul<Output = T>> as cumprod,
+    max<T=f64> as cummax,
+    min<T=f64> as cummin,
+    max<T=f32> as cummax,
+    min<T=f32> as cummin,
+    max<T=f16> as cummax,
+    min<T=f16> as cummin,
+    max<T=bf16> as cummax,
+    min<T=bf16> as cummin,
+)]
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    L: for<'a> Layout<'a, T>,
+{
+    #[inline]
+    fn unchecked<Lout>(
+        &self,
+        chunk_size: usize,
+        axis_len: usize,
+        out: &mut Tensor<T, S, Contiguous, Lout, P>,
+    ) where
+        Lout: for<'a> LayoutMut<'a, T>,
+    {
+        let chunk_size_in = self.opt_chunk_size();
+        for (chunk_i, chunk_o) in self
+            .chunks(chunk_size_in)
+            .zip(out.chunks_mut(chunk_size_in))
+        {
+            chunk_o
+                .par_iter_mut()
+                .zip(chunk_i.par_iter())
+                .for_each(|(o, i)| *o = *i);
+        }
+
+        if chunk_size == 0 || axis_len == 0 {
+            return;
+        }
+        out.par_chunks_mut(chunk_size * axis_len).for_each(|block| {
+            for k in 1..axis_len {
+                let (previous, current) = block.split_at_mut(k * chunk_size);
+                current[..chunk_size]
+                    .par_iter_mut()
+                    .zip(previous[(k - 1) * chunk_size..].par_iter())
+                    .for_each(|(c, p)| *c = (*p).placeholder(*c));
+            }
+        });
+    }
+
+    pub fn operation<Ax>(&self) -> Tensor<T, S, Contiguous, P::Layout, P>
+    where
+        S: ReductionOptChunckSize<T, Ax> + At<Ax>,
+        P: StaticAllocationPolicy<T, S>,
+    {
+        let chunk_size = <<S as ReductionOptChunckSize<T, Ax>>::Output as Unsigned>::USIZE;
+        let axis_len = <<S as At<Ax>>::Output as Unsigned>::USIZE;
+
+        let mut out: Tensor<T, S, Contiguous, P::Layout, P> = Tensor::default();
+        self.unchecked(chunk_size, axis_len, &mut out);
+
+        out
+    }
+
+    pub fn dynamic<Ax>(&self) -> Tensor<T, S, Contiguous, P::Layout, P>
+    where
+        P: DynamicAllocationPolicy<T>,
+        Ax: Unsigned,
+    {
+        let shape = self.shape();
+        let chunk_size = shape.iter().skip(Ax::USIZE + 1).product();
+        let axis_len = shape[Ax::USIZE];
+
+        let mut out: Tensor<T, S, Contiguous, P::Layout, P> = Tensor::alloc(shape);
+        self.unchecked(chunk_size, axis_len, &mut out);
+
+        out
+    }
+}
@@ -0,0 +1,152 @@
+//! `serialization` implements `serde`'s `Serialize` and `Deserialize`
+//! traits for tensors, it is only available with the `serde` feature.
+//!
+//! Tensors are encoded as a structure with two fields: `shape`, the runtime
+//! shape of the tensor, and `data`, the flat sequence of its elements in
+//! logical row-major order. This encoding does not depend on the layout or
+//! on the transpose policy so that a tensor can be serialized from any
+//! layout, views included, and deserialized in another one.
+//!
+//! Deserialization is available for owned contiguous layouts: `HeapLayout`,
+//! whose shape must be compatible with the type-level shape that can contain
+//! `Dyn` dimensions, and `StaticHeapLayout` and `StackLayout` whose shape
+//! must be identical to the static type-level shape.
+
+use super::heap_layout::HeapLayout;
+use super::layout::Layout;
+use super::shape::{NumElements, Shape, StaticShape};
+use super::stack_layout::StackLayout;
+use super::static_heap_layout::StaticHeapLayout;
+use super::tensor::Tensor;
+use super::transpose_policy::Contiguous;
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// Wrapper that serializes the elements of a tensor as a sequence
+/// without collecting them first.
+struct Data<'a, T, S, C, L, P>(&'a Tensor<T, S, C, L, P>);
+
+impl<'a, T, S, C, L, P> Serialize for Data<'a, T, S, C, L, P>
+where
+    T: Serialize,
+    L: for<'b> Layout<'b, T>,
+{
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let chunk_size = self.0.opt_chunk_size();
+        serializer.collect_seq(self.0.chunks(chunk_size).flatten())
+    }
+}
+
+impl<T, S, C, L, P> Serialize for Tensor<T, S, C, L, P>
+where
+    T: Serialize,
+    L: for<'a> Layout<'a, T>,
+{
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let mut state = serializer.serialize_struct("Tensor", 2)?;
+        state.serialize_field("shape", &self.shape())?;
+        state.serialize_field("data", &Data(self))?;
+        state.end()
+    }
+}
+
+/// Owned intermediate representation used for deserialization.
+#[derive(serde::Deserialize)]
+#[serde(rename = "Tensor")]
+struct Repr<T> {
+    shape: Vec<usize>,
+    data: Vec<T>,
+}
+
+impl<T> Repr<T> {
+    /// Checks that the number of elements matches the shape.
+    fn check_num_elements<E: Error>(&self) -> Result<(), E> {
+        let num_elements = self
+            .shape
+            .iter()
+            .try_fold(1usize, |acc, x| acc.checked_mul(*x))
+            .ok_or_else(|| {
+                E::custom(format!(
+                    "Tensor of shape {:?} has too many elements.",
+                    self.shape
+                ))
+            })?;
+        if self.data.len() != num_elements {
+            return Err(E::custom(format!(
+                "Tensor of shape {:?} should have {} elements, got {}.",
+                self.shape,
+                num_elements,
+                self.data.len()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the shape is exactly the static shape `S`.
+    fn check_static_shape<S: StaticShape, E: Error>(&self) -> Result<(), E> {
+        if self.shape != S::to_vec() {
+            return Err(E::custom(format!(
+                "Shape {:?} is not compatible with the tensor's shape {:?}.",
+                self.shape,
+                S::to_vec()
+            )));
+        }
+
+        self.check_num_elements()
+    }
+}
+
+impl<'de, T, S, P> Deserialize<'de> for Tensor<T, S, Contiguous, HeapLayout<T>, P>
+where
+    T: Deserialize<'de> + Default + Clone,
+    S: Shape,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = Repr::<T>::deserialize(deserializer)?;
+        if !S::runtime_compat(&repr.shape) {
+            return Err(D::Error::custom(format!(
+                "Shape {:?} is not compatible with the tensor's type-level shape.",
+                repr.shape
+            )));
+        }
+        repr.check_num_elements()?;
+
+        let mut out = Self::alloc(repr.shape);
+        out.clone_from_slice(&repr.data);
+
+        Ok(out)
+    }
+}
+
+impl<'de, T, S, P> Deserialize<'de> for Tensor<T, S, Contiguous, StaticHeapLayout<T, S>, P>
+where
+    T: Deserialize<'de> + Default + Clone,
+    S: StaticShape,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = Repr::<T>::deserialize(deserializer)?;
+        repr.check_static_shape::<S, _>()?;
+
+        let mut out = Self::default();
+        out.clone_from_slice(&repr.data);
+
+        Ok(out)
+    }
+}
+
+impl<'de, T, S, P> Deserialize<'de> for Tensor<T, S, Contiguous, StackLayout<T, S>, P>
+where
+    T: Deserialize<'de> + Default + Clone,
+    S: StaticShape + NumElements<T>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = Repr::<T>::deserialize(deserializer)?;
+        repr.check_static_shape::<S, _>()?;
+
+        let mut out = Self::default();
+        out.clone_from_slice(&repr.data);
+
+        Ok(out)
+    }
+}
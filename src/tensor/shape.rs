@@ -408,6 +408,86 @@ where
     type Output = And<Or<Or<Eq<S, SRhs>, Eq<S, U1>>, Eq<SRhs, U1>>, <A as Broadcast<ARhs>>::Output>;
 }
 
+/// Binary trait operator that outputs B1 if the implementor shape
+/// can be broadcast to Rhs without changing Rhs. Unlike `Broadcast`,
+/// this is one-directional: shapes are aligned on their last axes and
+/// for all axes the dimension of the implementor is equal to the one
+/// of Rhs or is U1. The implementor may have fewer axes than Rhs.
+///
+/// This is the condition for Rhs to be summed to the implementor shape.
+pub trait BroadcastTo<Rhs> {
+    type Output;
+}
+
+impl BroadcastTo<ATerm> for ATerm {
+    type Output = B1;
+}
+
+impl<S, A> BroadcastTo<TArr<S, A>> for ATerm {
+    type Output = B1;
+}
+
+impl<S, A, SRhs, ARhs> BroadcastTo<TArr<SRhs, ARhs>> for TArr<S, A>
+where
+    S: IsEqual<SRhs> + IsEqual<U1>,
+    Eq<S, SRhs>: BitOr<Eq<S, U1>>,
+    A: BroadcastTo<ARhs>,
+    Or<Eq<S, SRhs>, Eq<S, U1>>: BitAnd<<A as BroadcastTo<ARhs>>::Output>,
+{
+    type Output = And<Or<Eq<S, SRhs>, Eq<S, U1>>, <A as BroadcastTo<ARhs>>::Output>;
+}
+
+/// Type operator that outputs the broadcast of the implementor
+/// dimension and Rhs: the dimension that is not U1 if any.
+///
+/// It is only implemented if dimensions are equal or one of them is U1.
+pub trait BroadcastDim<Rhs> {
+    type Output;
+}
+
+impl<D, DRhs> BroadcastDim<DRhs> for D
+where
+    D: IsEqual<DRhs> + IsEqual<U1>,
+    DRhs: IsEqual<U1>,
+    Eq<D, DRhs>: BitOr<Eq<D, U1>>,
+    Or<Eq<D, DRhs>, Eq<D, U1>>: BitOr<Eq<DRhs, U1>>,
+    Or<Or<Eq<D, DRhs>, Eq<D, U1>>, Eq<DRhs, U1>>: TRUE,
+    Eq<D, U1>: If<DRhs, D>,
+{
+    type Output = <Eq<D, U1> as If<DRhs, D>>::Output;
+}
+
+/// Type operator that outputs the shape both the implementor shape and
+/// Rhs broadcast to. Shapes are aligned on their last axes and missing
+/// leading axes are considered to be U1, e.g. the broadcast of (3, 1)
+/// and (2, 1, 4) is (2, 3, 4).
+///
+/// It is only implemented if all dimensions on the respective axes
+/// are compatible. It does not support `Dyn`.
+pub trait BroadcastShape<Rhs> {
+    type Output;
+}
+
+impl BroadcastShape<ATerm> for ATerm {
+    type Output = ATerm;
+}
+
+impl<D, A> BroadcastShape<TArr<D, A>> for ATerm {
+    type Output = TArr<D, A>;
+}
+
+impl<D, A> BroadcastShape<ATerm> for TArr<D, A> {
+    type Output = TArr<D, A>;
+}
+
+impl<D, A, DRhs, ARhs> BroadcastShape<TArr<DRhs, ARhs>> for TArr<D, A>
+where
+    D: BroadcastDim<DRhs>,
+    A: BroadcastShape<ARhs>,
+{
+    type Output = TArr<<D as BroadcastDim<DRhs>>::Output, <A as BroadcastShape<ARhs>>::Output>;
+}
+
 /// Marker trait implemented on static shapes that provides
 /// a type-level number of elements and its runtime counterpart.
 ///
@@ -549,6 +629,31 @@ where
     type Output = <Eq<Ax, Sub1<Length<Self>>> as If<D, <Ar as At<Ax>>::Output>>::Output;
 }
 
+/// Trait operator that replaces the dimension of the axis
+/// having the (0-starting) index Ax (a type-level unsigned integer)
+/// with N, the length of a narrowed view along that axis.
+pub trait Narrow<Ax, N> {
+    type Output;
+}
+
+impl<Ax, N> Narrow<Ax, N> for ATerm {
+    type Output = ATerm;
+}
+
+impl<Ax, N, D, Ar> Narrow<Ax, N> for TArr<D, Ar>
+where
+    Self: Len,
+    Length<Self>: Sub<B1>,
+    Ax: IsEqual<Sub1<Length<Self>>>,
+    Ar: Narrow<Ax, N>,
+    Eq<Ax, Sub1<Length<Self>>>: If<TArr<N, Ar>, TArr<D, <Ar as Narrow<Ax, N>>::Output>>,
+{
+    type Output = <Eq<Ax, Sub1<Length<Self>>> as If<
+        TArr<N, Ar>,
+        TArr<D, <Ar as Narrow<Ax, N>>::Output>,
+    >>::Output;
+}
+
 /// Type operator that inserts dimension S before the first axis.Abs
 /// This is useful because dimensions are stored in reverse order in
 /// the recursive `TArr` structure.
@@ -584,6 +689,300 @@ where
     type Output = <<A as Transpose>::Output as Insert<S>>::Output;
 }
 
+/// Type operator that outputs the smallest dimension of a 2D shape,
+/// i.e. the number of singular values of a matrix of this shape.
+pub trait MinDim: StaticShape {
+    type Output: StaticDim;
+}
+
+impl<M, N> MinDim for TArr<N, TArr<M, ATerm>>
+where
+    M: StaticDim + Min<N>,
+    N: StaticDim,
+    Minimum<M, N>: StaticDim,
+{
+    type Output = Minimum<M, N>;
+}
+
+/// Marker trait implemented on `TArr` containing type-level unsigned
+/// integers, zero included, that represent amounts of padding.
+/// Provides a conversion for runtime use.
+pub trait Padding {
+    /// Outputs a `Vec` containing the runtime version of the amounts.
+    fn amounts() -> Vec<usize>;
+}
+
+impl Padding for ATerm {
+    #[inline]
+    fn amounts() -> Vec<usize> {
+        Vec::new()
+    }
+}
+
+impl<D, A> Padding for TArr<D, A>
+where
+    A: Padding,
+    D: Unsigned,
+{
+    #[inline]
+    fn amounts() -> Vec<usize> {
+        let mut vec = A::amounts();
+        vec.push(D::USIZE);
+
+        vec
+    }
+}
+
+/// Type operator that outputs the implementor dimension extended with
+/// Before and After elements (type-level unsigned integers).
+/// `Dyn` remains `Dyn`.
+pub trait PadDim<Before, After> {
+    type Output;
+}
+
+impl<U, B, Before, After> PadDim<Before, After> for UInt<U, B>
+where
+    Self: Add<Before>,
+    Sum<Self, Before>: Add<After>,
+{
+    type Output = Sum<Sum<Self, Before>, After>;
+}
+
+impl<Before, After> PadDim<Before, After> for Dyn {
+    type Output = Dyn;
+}
+
+/// Type operator that outputs the implementor shape where each dimension
+/// is extended with the dimensions of Before and After on the same axis.
+///
+/// Note that this requires the three shapes to have the same length.
+pub trait Pad<Before, After> {
+    type Output;
+}
+
+impl Pad<ATerm, ATerm> for ATerm {
+    type Output = ATerm;
+}
+
+impl<D, A, DBefore, ABefore, DAfter, AAfter> Pad<TArr<DBefore, ABefore>, TArr<DAfter, AAfter>>
+    for TArr<D, A>
+where
+    D: PadDim<DBefore, DAfter>,
+    A: Pad<ABefore, AAfter>,
+{
+    type Output = TArr<<D as PadDim<DBefore, DAfter>>::Output, <A as Pad<ABefore, AAfter>>::Output>;
+}
+
+/// Binary type operator that outputs B1 if the implementor shape can be
+/// resized to Rhs by interpolation, i.e. both are 3D or 4D shapes with
+/// the same first two dimensions (batch and channels).
+pub trait Resize<Rhs> {
+    type Output;
+}
+
+impl<N, C, L, NRhs, CRhs, LRhs> Resize<TArr<LRhs, TArr<CRhs, TArr<NRhs, ATerm>>>>
+    for TArr<L, TArr<C, TArr<N, ATerm>>>
+where
+    N: IsEqual<NRhs>,
+    C: IsEqual<CRhs>,
+    Eq<N, NRhs>: BitAnd<Eq<C, CRhs>>,
+{
+    type Output = And<Eq<N, NRhs>, Eq<C, CRhs>>;
+}
+
+impl<N, C, H, W, NRhs, CRhs, HRhs, WRhs>
+    Resize<TArr<WRhs, TArr<HRhs, TArr<CRhs, TArr<NRhs, ATerm>>>>>
+    for TArr<W, TArr<H, TArr<C, TArr<N, ATerm>>>>
+where
+    N: IsEqual<NRhs>,
+    C: IsEqual<CRhs>,
+    Eq<N, NRhs>: BitAnd<Eq<C, CRhs>>,
+{
+    type Output = And<Eq<N, NRhs>, Eq<C, CRhs>>;
+}
+
+/// Type operator that outputs the implementor dimension multiplied
+/// by the scale factor F (a type-level unsigned integer).
+/// `Dyn` remains `Dyn`.
+pub trait UpsampleDim<F> {
+    type Output;
+}
+
+impl<U, B, F> UpsampleDim<F> for UInt<U, B>
+where
+    Self: Mul<F>,
+{
+    type Output = Prod<Self, F>;
+}
+
+impl<F> UpsampleDim<F> for Dyn {
+    type Output = Dyn;
+}
+
+/// Type operator that outputs the implementor 3D or 4D shape where the
+/// spatial dimensions, i.e. all axes but the first two, are multiplied by
+/// the respective scale factors of Factors.
+pub trait Upsample<Factors> {
+    type Output;
+}
+
+impl<N, C, L, F> Upsample<TArr<F, ATerm>> for TArr<L, TArr<C, TArr<N, ATerm>>>
+where
+    L: UpsampleDim<F>,
+{
+    type Output = TArr<<L as UpsampleDim<F>>::Output, TArr<C, TArr<N, ATerm>>>;
+}
+
+impl<N, C, H, W, FH, FW> Upsample<TArr<FW, TArr<FH, ATerm>>>
+    for TArr<W, TArr<H, TArr<C, TArr<N, ATerm>>>>
+where
+    H: UpsampleDim<FH>,
+    W: UpsampleDim<FW>,
+{
+    type Output =
+        TArr<<W as UpsampleDim<FW>>::Output, TArr<<H as UpsampleDim<FH>>::Output, TArr<C, TArr<N, ATerm>>>>;
+}
+
+/// Marker trait implemented on `TArr` containing type-level unsigned
+/// integers, zero included, that represent (0-starting) axis indices.
+/// Provides a conversion for runtime use.
+pub trait Axes {
+    /// Outputs a `Vec` containing the runtime version of the axes.
+    fn axes() -> Vec<usize>;
+}
+
+impl Axes for ATerm {
+    #[inline]
+    fn axes() -> Vec<usize> {
+        Vec::new()
+    }
+}
+
+impl<D, A> Axes for TArr<D, A>
+where
+    A: Axes,
+    D: Unsigned,
+{
+    #[inline]
+    fn axes() -> Vec<usize> {
+        let mut vec = A::axes();
+        vec.push(D::USIZE);
+
+        vec
+    }
+}
+
+/// Type operator that outputs B1 if the implementor collection
+/// of axes contains Ax (a type-level unsigned integer).
+pub trait Contains<Ax> {
+    type Output;
+}
+
+impl<Ax> Contains<Ax> for ATerm {
+    type Output = B0;
+}
+
+impl<Ax, D, A> Contains<Ax> for TArr<D, A>
+where
+    D: IsEqual<Ax>,
+    A: Contains<Ax>,
+    Eq<D, Ax>: BitOr<<A as Contains<Ax>>::Output>,
+{
+    type Output = Or<Eq<D, Ax>, <A as Contains<Ax>>::Output>;
+}
+
+/// Type operator that outputs the implementor shape without
+/// the axes whose (0-starting) indices are in Axes.
+pub trait RemoveAxes<Axes> {
+    type Output;
+}
+
+impl<Axes> RemoveAxes<Axes> for ATerm {
+    type Output = ATerm;
+}
+
+impl<Axes, D, A> RemoveAxes<Axes> for TArr<D, A>
+where
+    A: RemoveAxes<Axes> + Len,
+    Axes: Contains<Length<A>>,
+    <Axes as Contains<Length<A>>>::Output:
+        If<<A as RemoveAxes<Axes>>::Output, TArr<D, <A as RemoveAxes<Axes>>::Output>>,
+{
+    type Output = <<Axes as Contains<Length<A>>>::Output as If<
+        <A as RemoveAxes<Axes>>::Output,
+        TArr<D, <A as RemoveAxes<Axes>>::Output>,
+    >>::Output;
+}
+
+/// Type operator that outputs the shape made of the dimensions of the
+/// axes of the implementor shape whose (0-starting) indices are in Axes.
+pub trait Gather<Axes>: StaticShape {
+    type Output;
+}
+
+impl<S: StaticShape> Gather<ATerm> for S {
+    type Output = ATerm;
+}
+
+impl<S, Ax, A> Gather<TArr<Ax, A>> for S
+where
+    S: At<Ax> + Gather<A>,
+{
+    type Output = TArr<<S as At<Ax>>::Output, <S as Gather<A>>::Output>;
+}
+
+/// Type operator that outputs the axes of the implementor
+/// shape followed by the axes of Rhs.
+pub trait Concat<Rhs> {
+    type Output;
+}
+
+impl<S> Concat<ATerm> for S {
+    type Output = S;
+}
+
+impl<S, D, A> Concat<TArr<D, A>> for S
+where
+    S: Concat<A>,
+{
+    type Output = TArr<D, <S as Concat<A>>::Output>;
+}
+
+/// Type operator that outputs the shape of the contraction of the axes
+/// AxesA of the implementor shape with the axes AxesB of Rhs: the
+/// remaining axes of the implementor followed by those of Rhs.
+///
+/// It is only implemented if the contracted dimensions are equal.
+pub trait TensorDot<Rhs, AxesA, AxesB> {
+    type Output;
+}
+
+impl<S, Rhs, AxesA, AxesB> TensorDot<Rhs, AxesA, AxesB> for S
+where
+    S: Gather<AxesA> + RemoveAxes<AxesA>,
+    Rhs: Gather<AxesB> + RemoveAxes<AxesB>,
+    <S as Gather<AxesA>>::Output: Same<<Rhs as Gather<AxesB>>::Output>,
+    <<S as Gather<AxesA>>::Output as Same<<Rhs as Gather<AxesB>>::Output>>::Output: TRUE,
+    <S as RemoveAxes<AxesA>>::Output: Concat<<Rhs as RemoveAxes<AxesB>>::Output>,
+{
+    type Output =
+        <<S as RemoveAxes<AxesA>>::Output as Concat<<Rhs as RemoveAxes<AxesB>>::Output>>::Output;
+}
+
+/// Type operator that outputs the shape of the Kronecker
+/// product of two matrices of the implementor shape and Rhs.
+pub trait Kron<Rhs> {
+    type Output;
+}
+
+impl<M, N, MRhs, NRhs> Kron<TArr<NRhs, TArr<MRhs, ATerm>>> for TArr<N, TArr<M, ATerm>>
+where
+    M: Mul<MRhs>,
+    N: Mul<NRhs>,
+{
+    type Output = TArr<Prod<N, NRhs>, TArr<Prod<M, MRhs>, ATerm>>;
+}
+
 /// 1D shape alias.
 pub type Shape1D<S0> = TArr<S0, ATerm>;
 /// 2D shape alias.
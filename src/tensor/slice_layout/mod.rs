@@ -2,7 +2,7 @@ use super::layout::Layout;
 use rayon::prelude::*;
 use std::ops::Deref;
 
-mod strided_chunks;
+pub(crate) mod strided_chunks;
 use strided_chunks::StridedChunks;
 
 /// `SliceLayout` is a very flexible non-contiguous slice-backed layout.
@@ -33,18 +33,6 @@ pub struct SliceLayout<'a, T> {
 }
 
 impl<'a, T> SliceLayout<'a, T> {
-    fn linear_index(&self, position: &[usize]) -> usize {
-        position
-            .iter()
-            .rev()
-            .zip(self.strides.iter().rev())
-            .fold(0, |acc, (x, y)| acc + (x * y))
-    }
-    pub(super) fn chunk_at(&self, position: &[usize], size: usize) -> &[T] {
-        let index = self.linear_index(position);
-        &self.data[index..index + size]
-    }
-
     pub fn from_slice_unchecked(
         slice: &'a [T],
         shape: Vec<usize>,
@@ -67,7 +55,7 @@ where
     // T: 'b,
     T: 'static,
 {
-    type Iter = StridedChunks<'b, 'b, T>;
+    type Iter = StridedChunks<'b, T>;
     type View = Self;
     #[inline]
     fn shape(&self) -> Vec<usize> {
@@ -90,7 +78,7 @@ where
 
     #[inline]
     fn chunks(&'b self, chunk_size: usize) -> Self::Iter {
-        StridedChunks::new(&self, chunk_size)
+        StridedChunks::new(self.data, &self.shape, &self.strides, chunk_size)
     }
 
     #[inline]
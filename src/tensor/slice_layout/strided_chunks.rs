@@ -1,62 +1,164 @@
-use super::SliceLayout;
+use std::marker::PhantomData;
 
-/// Overlapping iterator over a `SliceLayout` that outputs `&[T]` chunks.
-pub struct StridedChunks<'a, 'b, T> {
+/// Iterator over the storage positions of the chunks of a strided
+/// layout, in logical order.
+pub(crate) struct ChunkPositions<'a> {
     counter: Vec<usize>,
     step_sizes: Vec<usize>,
     dead: bool,
-    chunk_size: usize,
-    layout: &'b SliceLayout<'a, T>,
+    shape: &'a [usize],
+    strides: &'a [usize],
 }
 
-impl<'a, 'b, T> StridedChunks<'a, 'b, T> {
-    pub fn new(layout: &'a SliceLayout<'b, T>, chunk_size: usize) -> Self {
-        let mut step_sizes = layout.shape.clone();
+impl<'a> ChunkPositions<'a> {
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is not the product of trailing dimensions
+    /// of `shape` as chunks would then not be aligned on the axes.
+    pub(crate) fn new(shape: &'a [usize], strides: &'a [usize], chunk_size: usize) -> Self {
+        let mut step_sizes = shape.to_vec();
         let mut step = chunk_size;
+        let mut aligned = true;
 
-        step_sizes
-            .iter_mut()
-            .rev()
-            .for_each(|x| if step < *x { *x = 1 } else { step /= *x });
+        step_sizes.iter_mut().rev().for_each(|x| {
+            if step < *x {
+                // Only axes that are not consumed at all can be stepped one by one.
+                aligned &= step <= 1;
+                *x = 1
+            } else {
+                let remainder = step % *x;
+                aligned &= remainder == 0;
+                step /= *x
+            }
+        });
+        assert!(
+            aligned && step <= 1,
+            "Chunk size {} is not a product of the trailing dimensions of shape {:?}.",
+            chunk_size,
+            shape
+        );
 
-        StridedChunks {
-            counter: vec![0; layout.shape.len()],
+        ChunkPositions {
+            counter: vec![0; shape.len()],
             dead: false,
             step_sizes,
-            chunk_size,
-            layout,
+            shape,
+            strides,
         }
     }
 }
 
-impl<'a, 'b, T> Iterator for StridedChunks<'a, 'b, T>
-where
-    'b: 'a,
-{
-    type Item = &'a [T];
+impl<'a> Iterator for ChunkPositions<'a> {
+    type Item = usize;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.dead {
             return None;
         }
-        let chunk = self.layout.chunk_at(&self.counter, self.chunk_size);
+        let position = self
+            .counter
+            .iter()
+            .zip(self.strides.iter())
+            .fold(0, |acc, (x, y)| acc + (x * y));
 
         for ((digit, step_size), bound) in self
             .counter
             .iter_mut()
             .zip(self.step_sizes.iter())
-            .zip(self.layout.shape.iter())
+            .zip(self.shape.iter())
             .rev()
         {
             if *digit + step_size >= *bound {
                 *digit = 0;
             } else {
                 *digit += step_size;
-                return Some(chunk);
+                return Some(position);
             }
         }
 
         self.dead = true;
-        Some(chunk)
+        Some(position)
+    }
+}
+
+/// Overlapping iterator over a strided layout that outputs `&[T]` chunks.
+pub struct StridedChunks<'a, T> {
+    positions: ChunkPositions<'a>,
+    chunk_size: usize,
+    data: &'a [T],
+}
+
+impl<'a, T> StridedChunks<'a, T> {
+    pub(crate) fn new(
+        data: &'a [T],
+        shape: &'a [usize],
+        strides: &'a [usize],
+        chunk_size: usize,
+    ) -> Self {
+        StridedChunks {
+            positions: ChunkPositions::new(shape, strides, chunk_size),
+            chunk_size,
+            data,
+        }
+    }
+}
+
+impl<'a, T> Iterator for StridedChunks<'a, T> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.positions.next()?;
+        Some(&self.data[index..index + self.chunk_size])
+    }
+}
+
+/// Iterator over a strided layout that outputs disjoint `&mut [T]` chunks.
+///
+/// Chunks are disjoint as long as distinct logical positions map
+/// to distinct storage positions (i.e. there is no broadcasting),
+/// the chunk size is a product of trailing dimensions, which is checked
+/// when positions are computed, and it does not exceed the optimal
+/// chunk size, which is checked by `SliceLayoutMut::chunks_mut`.
+pub struct StridedChunksMut<'a, T> {
+    positions: ChunkPositions<'a>,
+    chunk_size: usize,
+    data: *mut T,
+    len: usize,
+    _phantom: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T> StridedChunksMut<'a, T> {
+    pub(crate) fn new(
+        data: &'a mut [T],
+        shape: &'a [usize],
+        strides: &'a [usize],
+        chunk_size: usize,
+    ) -> Self {
+        StridedChunksMut {
+            positions: ChunkPositions::new(shape, strides, chunk_size),
+            chunk_size,
+            len: data.len(),
+            data: data.as_mut_ptr(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for StridedChunksMut<'a, T> {
+    type Item = &'a mut [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.positions.next()?;
+        assert!(
+            index + self.chunk_size <= self.len,
+            "Chunk out of bounds of the underlying slice."
+        );
+
+        // SAFETY: the chunk lies within the borrowed slice. Chunks span
+        // whole trailing axes within a contiguous part of the layout, so
+        // each one covers exactly the storage of its own logical elements
+        // and distinct logical elements of a mutable (hence not broadcast)
+        // layout have distinct storage positions: chunks do not overlap.
+        Some(unsafe { std::slice::from_raw_parts_mut(self.data.add(index), self.chunk_size) })
     }
 }
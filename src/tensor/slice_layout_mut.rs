@@ -0,0 +1,123 @@
+use super::layout::{Layout, LayoutMut};
+use super::slice_layout::strided_chunks::{StridedChunks, StridedChunksMut};
+use super::slice_layout::SliceLayout;
+use std::ops::{Deref, DerefMut};
+
+/// `SliceLayoutMut` is the mutable counterpart of `SliceLayout`.
+/// It enables the creation of tensors that are mutable views on
+/// sub-regions of other tensors (e.g. one row of a matrix or one
+/// element of a preallocated batch) so that in-place operations
+/// can write into them.
+///
+/// `SliceLayoutMut` stores the same information as `SliceLayout`:
+/// * the shape
+/// * the actual strides (i.e. product of intrinsic and extrinsic strides)
+/// * the number of elements
+/// * the optimal chunk size (i.e. largest contiguous data pieces)
+///
+/// Contrary to `SliceLayout`, it cannot be broadcast: each element of
+/// the view must have its own place in storage so that `chunks_mut`
+/// outputs disjoint chunks.
+#[derive(Debug)]
+pub struct SliceLayoutMut<'a, T> {
+    data: &'a mut [T],
+    shape: Vec<usize>,
+    strides: Vec<usize>,
+    num_elements: usize,
+    opt_chunk_size: usize,
+}
+
+impl<'a, T> SliceLayoutMut<'a, T> {
+    /// Builds a mutable view on `slice`. Callers must ensure that distinct
+    /// positions in `shape` are mapped to distinct elements by `strides`.
+    pub(crate) fn from_slice_unchecked(
+        slice: &'a mut [T],
+        shape: Vec<usize>,
+        strides: Vec<usize>,
+        num_elements: usize,
+        opt_chunk_size: usize,
+    ) -> Self {
+        SliceLayoutMut {
+            data: slice,
+            shape,
+            strides,
+            num_elements,
+            opt_chunk_size,
+        }
+    }
+}
+
+impl<'a, 'b, T> Layout<'b, T> for SliceLayoutMut<'a, T>
+where
+    T: 'static,
+{
+    type Iter = StridedChunks<'b, T>;
+    type View = SliceLayout<'b, T>;
+
+    #[inline]
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+
+    #[inline]
+    fn strides(&self) -> Vec<usize> {
+        self.strides.clone()
+    }
+
+    #[inline]
+    fn opt_chunk_size(&self) -> usize {
+        self.opt_chunk_size
+    }
+
+    #[inline]
+    fn num_elements(&self) -> usize {
+        self.num_elements
+    }
+
+    #[inline]
+    fn chunks(&'b self, chunk_size: usize) -> Self::Iter {
+        StridedChunks::new(self.data, &self.shape, &self.strides, chunk_size)
+    }
+
+    #[inline]
+    fn as_view_unchecked(
+        &'b self,
+        shape: Vec<usize>,
+        strides: Vec<usize>,
+        num_elements: usize,
+        opt_chunk_size: usize,
+    ) -> Self::View {
+        SliceLayout::from_slice_unchecked(self.data, shape, strides, num_elements, opt_chunk_size)
+    }
+}
+
+impl<'a, 'b, T> LayoutMut<'b, T> for SliceLayoutMut<'a, T>
+where
+    T: 'b,
+{
+    type IterMut = StridedChunksMut<'b, T>;
+
+    #[inline]
+    fn chunks_mut(&'b mut self, chunk_size: usize) -> Self::IterMut {
+        assert!(
+            chunk_size <= self.opt_chunk_size,
+            "Chunks of {} elements would overlap in a view whose contiguous chunks have {}.",
+            chunk_size,
+            self.opt_chunk_size
+        );
+        StridedChunksMut::new(self.data, &self.shape, &self.strides, chunk_size)
+    }
+}
+
+impl<'a, T> Deref for SliceLayoutMut<'a, T> {
+    type Target = [T];
+    fn deref(&self) -> &Self::Target {
+        self.data
+    }
+}
+
+impl<'a, T> DerefMut for SliceLayoutMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.data
+    }
+}
@@ -0,0 +1,268 @@
+//! `sort` contains the operations that order the elements of a tensor:
+//! `sort` and `argsort` along a certain axis, and `unique` over
+//! all elements.
+//!
+//! Sorts are stable: equal elements keep their relative order.
+//! Elements that are not comparable with themselves, such as NaNs,
+//! are considered greater than every other element and equal to each other.
+//!
+//! The input is read in logical order, so views of any transpose policy
+//! are supported. Lanes along the sorted axis are sorted in parallel.
+//! Results are contiguous and use the allocation policy of `self`.
+
+use super::allocation_policy::{DynamicAllocationPolicy, StaticAllocationPolicy};
+use super::layout::{Layout, LayoutMut};
+use super::shape::{At, Dyn, ReductionOptChunckSize, Shape1D};
+use super::tensor::Tensor;
+use super::transpose_policy::Contiguous;
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use typenum::Unsigned;
+
+/// Direction in which elements are sorted.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    T: Send + Sync + Copy + PartialOrd,
+    L: for<'a> Layout<'a, T>,
+{
+    /// Writes in `out` the elements of `data` permuted by `permutations`
+    /// mapped through `f` lane by lane.
+    fn scatter_unchecked<U, Lout, F>(
+        data: &[T],
+        permutations: &[Vec<usize>],
+        chunk_size: usize,
+        axis_len: usize,
+        out: &mut Tensor<U, S, Contiguous, Lout, P>,
+        f: F,
+    ) where
+        U: Send + Sync,
+        Lout: for<'a> LayoutMut<'a, U>,
+        F: Fn(&[T], usize) -> U + Send + Sync,
+    {
+        if chunk_size == 0 || axis_len == 0 {
+            return;
+        }
+        out.par_chunks_mut(chunk_size * axis_len)
+            .zip(data.par_chunks(chunk_size * axis_len))
+            .zip(permutations.par_chunks(chunk_size))
+            .for_each(|((block_out, block_in), lanes)| {
+                for (offset, lane) in lanes.iter().enumerate() {
+                    for (k, index) in lane.iter().enumerate() {
+                        block_out[k * chunk_size + offset] =
+                            f(&block_in[offset..], *index * chunk_size);
+                    }
+                }
+            });
+    }
+
+    fn sort_unchecked<Lout>(
+        &self,
+        chunk_size: usize,
+        axis_len: usize,
+        order: SortOrder,
+        out: &mut Tensor<T, S, Contiguous, Lout, P>,
+    ) where
+        Lout: for<'a> LayoutMut<'a, T>,
+    {
+        let data = self.logical_vec();
+        let permutations = permutations(&data, chunk_size, axis_len, order);
+        Self::scatter_unchecked(
+            &data,
+            &permutations,
+            chunk_size,
+            axis_len,
+            out,
+            |lane, i| lane[i],
+        );
+    }
+
+    fn argsort_unchecked<Lout>(
+        &self,
+        chunk_size: usize,
+        axis_len: usize,
+        order: SortOrder,
+        out: &mut Tensor<usize, S, Contiguous, Lout, P>,
+    ) where
+        Lout: for<'a> LayoutMut<'a, usize>,
+    {
+        let data = self.logical_vec();
+        let permutations = permutations(&data, chunk_size, axis_len, order);
+        Self::scatter_unchecked(&data, &permutations, chunk_size, axis_len, out, |_, i| {
+            i / chunk_size
+        });
+    }
+
+    /// Sorts the lanes of `self` along the axis `Ax`.
+    pub fn sort<Ax>(&self, order: SortOrder) -> Tensor<T, S, Contiguous, P::Layout, P>
+    where
+        S: ReductionOptChunckSize<T, Ax> + At<Ax>,
+        P: StaticAllocationPolicy<T, S>,
+    {
+        let chunk_size = <<S as ReductionOptChunckSize<T, Ax>>::Output as Unsigned>::USIZE;
+        let axis_len = <<S as At<Ax>>::Output as Unsigned>::USIZE;
+
+        let mut out: Tensor<T, S, Contiguous, P::Layout, P> = Tensor::default();
+        self.sort_unchecked(chunk_size, axis_len, order, &mut out);
+
+        out
+    }
+
+    /// Sorts the lanes of `self` along the axis `Ax`
+    /// with a shape known at runtime.
+    pub fn sort_dynamic<Ax>(&self, order: SortOrder) -> Tensor<T, S, Contiguous, P::Layout, P>
+    where
+        P: DynamicAllocationPolicy<T>,
+        Ax: Unsigned,
+    {
+        let shape = self.shape();
+        let chunk_size = shape.iter().skip(Ax::USIZE + 1).product();
+        let axis_len = shape[Ax::USIZE];
+
+        let mut out: Tensor<T, S, Contiguous, P::Layout, P> = Tensor::alloc(shape);
+        self.sort_unchecked(chunk_size, axis_len, order, &mut out);
+
+        out
+    }
+
+    /// Returns the positions along the axis `Ax` that sort the lanes of `self`.
+    pub fn argsort<Ax>(
+        &self,
+        order: SortOrder,
+    ) -> Tensor<usize, S, Contiguous, <P as StaticAllocationPolicy<usize, S>>::Layout, P>
+    where
+        S: ReductionOptChunckSize<T, Ax> + At<Ax>,
+        P: StaticAllocationPolicy<usize, S>,
+    {
+        let chunk_size = <<S as ReductionOptChunckSize<T, Ax>>::Output as Unsigned>::USIZE;
+        let axis_len = <<S as At<Ax>>::Output as Unsigned>::USIZE;
+
+        let mut out: Tensor<usize, S, Contiguous, _, P> = Tensor::default();
+        self.argsort_unchecked(chunk_size, axis_len, order, &mut out);
+
+        out
+    }
+
+    /// Returns the positions along the axis `Ax` that sort the lanes of `self`
+    /// with a shape known at runtime.
+    pub fn argsort_dynamic<Ax>(
+        &self,
+        order: SortOrder,
+    ) -> Tensor<usize, S, Contiguous, <P as DynamicAllocationPolicy<usize>>::Layout, P>
+    where
+        P: DynamicAllocationPolicy<usize>,
+        Ax: Unsigned,
+    {
+        let shape = self.shape();
+        let chunk_size = shape.iter().skip(Ax::USIZE + 1).product();
+        let axis_len = shape[Ax::USIZE];
+
+        let mut out: Tensor<usize, S, Contiguous, _, P> = Tensor::alloc(shape);
+        self.argsort_unchecked(chunk_size, axis_len, order, &mut out);
+
+        out
+    }
+
+    /// Returns the distinct elements of `self` in ascending order
+    /// along with the number of occurrences of each of them.
+    #[allow(clippy::type_complexity)]
+    pub fn unique(
+        &self,
+    ) -> (
+        Tensor<T, Shape1D<Dyn>, Contiguous, <P as DynamicAllocationPolicy<T>>::Layout, P>,
+        Tensor<usize, Shape1D<Dyn>, Contiguous, <P as DynamicAllocationPolicy<usize>>::Layout, P>,
+    )
+    where
+        P: DynamicAllocationPolicy<T> + DynamicAllocationPolicy<usize>,
+    {
+        let mut data = self.logical_vec();
+        data.par_sort_by(total_cmp);
+
+        let mut values: Vec<T> = Vec::new();
+        let mut counts: Vec<usize> = Vec::new();
+        for x in data {
+            match values.last() {
+                Some(last) if total_cmp(last, &x) == Ordering::Equal => {
+                    *counts.last_mut().unwrap() += 1
+                }
+                _ => {
+                    values.push(x);
+                    counts.push(1);
+                }
+            }
+        }
+
+        let mut values_out: Tensor<
+            T,
+            Shape1D<Dyn>,
+            Contiguous,
+            <P as DynamicAllocationPolicy<T>>::Layout,
+            P,
+        > = Tensor::alloc(vec![values.len()]);
+        values_out.copy_from_slice(&values);
+        let mut counts_out: Tensor<
+            usize,
+            Shape1D<Dyn>,
+            Contiguous,
+            <P as DynamicAllocationPolicy<usize>>::Layout,
+            P,
+        > = Tensor::alloc(vec![counts.len()]);
+        counts_out.copy_from_slice(&counts);
+
+        (values_out, counts_out)
+    }
+}
+
+/// Total order on `T` where elements that are not comparable
+/// with themselves come last.
+fn total_cmp<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    match a.partial_cmp(b) {
+        Some(ordering) => ordering,
+        None => is_unordered(a).cmp(&is_unordered(b)),
+    }
+}
+
+#[inline]
+fn is_unordered<T: PartialOrd>(x: &T) -> bool {
+    x.partial_cmp(x).is_none()
+}
+
+/// Computes in parallel the stable permutation that sorts each lane
+/// of the contiguous `data`. Lanes are returned block by block, and
+/// permutations hold positions along the sorted axis.
+fn permutations<T>(
+    data: &[T],
+    chunk_size: usize,
+    axis_len: usize,
+    order: SortOrder,
+) -> Vec<Vec<usize>>
+where
+    T: Sync + PartialOrd,
+{
+    if chunk_size == 0 || axis_len == 0 {
+        return Vec::new();
+    }
+
+    (0..data.len() / axis_len)
+        .into_par_iter()
+        .map(|lane| {
+            let start = (lane / chunk_size) * chunk_size * axis_len + lane % chunk_size;
+            let mut permutation: Vec<usize> = (0..axis_len).collect();
+            permutation.sort_by(|a, b| {
+                let ordering =
+                    total_cmp(&data[start + a * chunk_size], &data[start + b * chunk_size]);
+                match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            });
+
+            permutation
+        })
+        .collect()
+}
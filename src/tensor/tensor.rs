@@ -1,12 +1,17 @@
-use super::layout::{Alloc, DynamicFill, Layout, StaticFill};
+use super::layout::{Alloc, DynamicFill, Layout, LayoutMut, StaticFill};
 use super::shape::{
-    intrinsic_strides_in_place, Broadcast, Same, SameNumElements, Shape, StaticShape, StridedShape,
-    StridedShapeDyn, Transpose, TRUE,
+    intrinsic_strides_in_place, Broadcast, Narrow, Same, SameNumElements, Shape, StaticShape,
+    StridedShape, StridedShapeDyn, Transpose, TRUE,
 };
 use super::slice_layout::SliceLayout;
+use super::slice_layout_mut::SliceLayoutMut;
 use super::transpose_policy::{Contiguous, Strided, TransposePolicy};
 use std::marker::PhantomData;
-use std::ops::{Deref, DerefMut};
+use std::ops::{
+    Deref, DerefMut, Index, IndexMut, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo,
+    RangeToInclusive,
+};
+use typenum::Unsigned;
 
 /// The central struct of the `tensor` module.
 ///
@@ -94,13 +99,8 @@ impl<'a, T, S, C, P> Tensor<T, S, C, SliceLayout<'a, T>, P> {
             S::runtime_compat(&shape),
             "`shape` is not compatible with specified type-level shape."
         );
-        let mut num_elements = 1;
-        let mut strides = Vec::with_capacity(shape.len());
-
-        for dim in shape.iter() {
-            strides.push(num_elements);
-            num_elements *= dim;
-        }
+        let num_elements = shape.iter().product();
+        let strides = intrinsic_strides_in_place(shape.clone());
 
         Tensor {
             layout: SliceLayout::from_slice_unchecked(
@@ -115,6 +115,31 @@ impl<'a, T, S, C, P> Tensor<T, S, C, SliceLayout<'a, T>, P> {
     }
 }
 
+impl<'a, T, S, C, P> Tensor<T, S, C, SliceLayoutMut<'a, T>, P> {
+    pub fn from_slice_mut(slice: &'a mut [T]) -> Self
+    where
+        S: StaticShape,
+    {
+        assert_eq!(
+            S::NUM_ELEMENTS,
+            slice.len(),
+            "`slice` must have exactly {} elements to be be compatible with specified type-level shape. Got {}.",
+            S::NUM_ELEMENTS,
+            slice.len(),
+        );
+        Tensor {
+            layout: SliceLayoutMut::from_slice_unchecked(
+                slice,
+                S::to_vec(),
+                S::strides(),
+                S::NUM_ELEMENTS,
+                S::NUM_ELEMENTS,
+            ),
+            _phantoms: PhantomData,
+        }
+    }
+}
+
 impl<T, S, C, L, P> Tensor<T, S, C, L, P> {
     pub fn broadcast<Z>(&self) -> Tensor<T, Z, Strided, <L as Layout<'_, T>>::View, P>
     where
@@ -124,24 +149,14 @@ impl<T, S, C, L, P> Tensor<T, S, C, L, P> {
         L: for<'a> Layout<'a, T>,
     {
         let shape = Z::to_vec();
-        let internal_strides = S::strides();
-        let mut external_strides = self.strides();
-        external_strides
-            .iter_mut()
-            .zip(internal_strides.iter())
-            .zip(S::to_vec())
-            .for_each(|((x, y), z)| *x = if z == 1 { 0 } else { *x / *y });
-        let mut strides = Z::strides();
-        let len = strides.len();
-        strides
-            .iter_mut()
-            .take(len - external_strides.len())
-            .for_each(|x| *x = 0);
+        // Broadcast axes and new leading axes do not move in storage.
+        let mut strides = vec![0; shape.len()];
         strides
             .iter_mut()
             .rev()
-            .zip(external_strides.iter().rev())
-            .for_each(|(x, y)| *x *= *y);
+            .zip(self.strides().into_iter().rev())
+            .zip(S::to_vec().into_iter().rev())
+            .for_each(|((x, y), z)| *x = if z == 1 { 0 } else { y });
 
         let opt_chunk_size = match strides
             .iter()
@@ -151,7 +166,8 @@ impl<T, S, C, L, P> Tensor<T, S, C, L, P> {
         {
             Some((_, y)) => y,
             None => Z::NUM_ELEMENTS,
-        };
+        }
+        .min(self.opt_chunk_size());
         Tensor {
             layout: self.as_view_unchecked(shape, strides, Z::NUM_ELEMENTS, opt_chunk_size),
             _phantoms: PhantomData,
@@ -178,36 +194,27 @@ impl<T, S, C, L, P> Tensor<T, S, C, L, P> {
             current_shape,
             shape,
         );
-        let internal_strides = intrinsic_strides_in_place(current_shape);
-        let mut external_strides = self.strides();
-        external_strides
-            .iter_mut()
-            .zip(internal_strides.iter())
-            .zip(self.shape())
-            .for_each(|((x, y), z)| *x = if z == 1 { 0 } else { *x / *y });
-        let mut strides = intrinsic_strides_in_place(shape.clone());
-        let len = strides.len();
-        strides
-            .iter_mut()
-            .take(len - external_strides.len())
-            .for_each(|x| *x = 0);
+        // Broadcast axes and new leading axes do not move in storage.
+        let mut strides = vec![0; shape.len()];
         strides
             .iter_mut()
             .rev()
-            .zip(external_strides.iter().rev())
-            .for_each(|(x, y)| *x *= *y);
+            .zip(self.strides().into_iter().rev())
+            .zip(current_shape.into_iter().rev())
+            .for_each(|((x, y), z)| *x = if z == 1 { 0 } else { y });
 
         let num_elements = shape.iter().product();
 
         let opt_chunk_size = match strides
             .iter()
             .rev()
-            .zip(strides.clone().into_iter().rev())
+            .zip(intrinsic_strides_in_place(shape.clone()).into_iter().rev())
             .find(|(x, _)| **x == 0)
         {
             Some((_, y)) => y,
             None => num_elements,
-        };
+        }
+        .min(self.opt_chunk_size());
         Tensor {
             layout: self.as_view_unchecked(shape, strides, num_elements, opt_chunk_size),
             _phantoms: PhantomData,
@@ -361,6 +368,254 @@ impl<T, S, C, L, P> Tensor<T, S, C, L, P> {
             _phantoms: PhantomData,
         }
     }
+
+    /// Returns a copy of the elements of `self` in logical order.
+    pub(crate) fn logical_vec(&self) -> Vec<T>
+    where
+        T: Copy,
+        L: for<'a> Layout<'a, T>,
+    {
+        let mut data = Vec::with_capacity(self.shape().iter().product());
+        for chunk in self.chunks(self.opt_chunk_size()) {
+            data.extend_from_slice(chunk);
+        }
+
+        data
+    }
+}
+
+/// Mutable views borrow the storage of `self` so that in-place
+/// operations on the view write into the corresponding sub-region.
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    L: for<'a> Layout<'a, T> + DerefMut<Target = [T]>,
+{
+    pub fn as_view_mut(&mut self) -> Tensor<T, S, C, SliceLayoutMut<'_, T>, P> {
+        let (shape, strides) = (self.shape(), self.strides());
+        let (num_elements, opt_chunk_size) = (self.num_elements(), self.opt_chunk_size());
+
+        Tensor {
+            layout: SliceLayoutMut::from_slice_unchecked(
+                &mut self.layout,
+                shape,
+                strides,
+                num_elements,
+                opt_chunk_size,
+            ),
+            _phantoms: PhantomData,
+        }
+    }
+
+    pub fn transpose_mut(
+        &mut self,
+    ) -> Tensor<T, <S as Transpose>::Output, C::Transposed, SliceLayoutMut<'_, T>, P>
+    where
+        S: Transpose,
+        C: TransposePolicy,
+    {
+        let shape = self.shape().into_iter().rev().collect();
+        let strides = self.strides().into_iter().rev().collect();
+        let num_elements = self.num_elements();
+
+        Tensor {
+            layout: SliceLayoutMut::from_slice_unchecked(
+                &mut self.layout,
+                shape,
+                strides,
+                num_elements,
+                1,
+            ),
+            _phantoms: PhantomData,
+        }
+    }
+
+    /// Outputs a mutable view on the `N` elements starting at `start`
+    /// along the axis at (0-starting) index `Ax`.
+    pub fn narrow_mut<Ax, N>(
+        &mut self,
+        start: usize,
+    ) -> Tensor<T, <S as Narrow<Ax, N>>::Output, Strided, SliceLayoutMut<'_, T>, P>
+    where
+        Ax: Unsigned,
+        N: Unsigned,
+        S: StaticShape + Narrow<Ax, N>,
+        <S as Narrow<Ax, N>>::Output: StaticShape,
+    {
+        let (axis, len) = (Ax::USIZE, N::USIZE);
+        let strides = self.strides();
+        let mut shape = self.shape();
+        assert!(
+            axis < shape.len() && start + len <= shape[axis],
+            "Cannot narrow axis {} of shape {:?} to {} elements starting at {}.",
+            axis,
+            shape,
+            len,
+            start
+        );
+        shape[axis] = len;
+
+        // The largest contiguous chunks are made of the trailing
+        // axes whose strides are the intrinsic ones.
+        let mut opt_chunk_size = 1;
+        for (dim, stride) in shape.iter().zip(strides.iter()).rev() {
+            if *stride != opt_chunk_size {
+                break;
+            }
+            opt_chunk_size *= dim;
+        }
+
+        Tensor {
+            layout: SliceLayoutMut::from_slice_unchecked(
+                &mut self.layout[start * strides[axis]..],
+                shape,
+                strides,
+                <S as Narrow<Ax, N>>::Output::NUM_ELEMENTS,
+                opt_chunk_size,
+            ),
+            _phantoms: PhantomData,
+        }
+    }
+}
+
+/// Compile-time check that an index of length `N` has the rank of `S`.
+struct RankCheck<S, const N: usize>(PhantomData<S>);
+
+impl<S: Shape, const N: usize> RankCheck<S, N> {
+    const OK: () = assert!(N == S::LEN, "Index length must match the number of axes.");
+}
+
+// Indexing with the usual slice indices keeps
+// addressing the underlying storage directly.
+macro_rules! impl_slice_index {
+    ($($index:ty => $output:ty),*) => {
+        $(
+            impl<T, S, C, L, P> Index<$index> for Tensor<T, S, C, L, P>
+            where
+                L: Deref<Target = [T]>,
+            {
+                type Output = $output;
+
+                fn index(&self, index: $index) -> &$output {
+                    &self.layout[index]
+                }
+            }
+
+            impl<T, S, C, L, P> IndexMut<$index> for Tensor<T, S, C, L, P>
+            where
+                L: DerefMut<Target = [T]>,
+            {
+                fn index_mut(&mut self, index: $index) -> &mut $output {
+                    &mut self.layout[index]
+                }
+            }
+        )*
+    };
+}
+
+impl_slice_index!(
+    usize => T,
+    Range<usize> => [T],
+    RangeFrom<usize> => [T],
+    RangeFull => [T],
+    RangeInclusive<usize> => [T],
+    RangeTo<usize> => [T],
+    RangeToInclusive<usize> => [T]
+);
+
+/// Elements are accessed in logical order: unlike the underlying slice
+/// that `Tensor` dereferences to, indices and iterators take strides into
+/// account so they are correct on transposed, broadcast or strided views.
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    L: for<'a> Layout<'a, T>,
+{
+    /// Outputs the position in storage of the element at `index`
+    /// or `None` if `index` is out of bounds or of a wrong length.
+    fn storage_index(&self, index: &[usize]) -> Option<usize> {
+        let shape = self.shape();
+        if index.len() != shape.len() || index.iter().zip(shape.iter()).any(|(i, d)| i >= d) {
+            return None;
+        }
+
+        Some(index.iter().zip(self.strides()).map(|(i, s)| i * s).sum())
+    }
+
+    /// Returns a reference to the element at `index`
+    /// or `None` if it is out of bounds.
+    pub fn get(&self, index: &[usize]) -> Option<&T> {
+        self.storage_index(index).map(|i| &self.layout[i])
+    }
+
+    /// Returns a mutable reference to the element at `index`
+    /// or `None` if it is out of bounds.
+    pub fn get_mut(&mut self, index: &[usize]) -> Option<&mut T>
+    where
+        L: for<'a> LayoutMut<'a, T>,
+    {
+        match self.storage_index(index) {
+            Some(i) => Some(&mut self.layout[i]),
+            None => None,
+        }
+    }
+
+    /// Iterates over the elements of `self` in logical order.
+    pub fn iter_logical(&self) -> impl Iterator<Item = &T> {
+        self.chunks(self.opt_chunk_size()).flatten()
+    }
+
+    /// Iterates over the elements of `self` in logical
+    /// order along with their multidimensional index.
+    pub fn indexed_iter(&self) -> impl Iterator<Item = (Vec<usize>, &T)> {
+        let strides = intrinsic_strides_in_place(self.shape());
+
+        self.iter_logical().enumerate().map(move |(mut i, x)| {
+            let index = strides
+                .iter()
+                .map(|stride| {
+                    let coordinate = i / stride;
+                    i %= stride;
+                    coordinate
+                })
+                .collect();
+
+            (index, x)
+        })
+    }
+}
+
+impl<T, S, C, L, P, const N: usize> Index<[usize; N]> for Tensor<T, S, C, L, P>
+where
+    S: Shape,
+    L: for<'a> Layout<'a, T>,
+{
+    type Output = T;
+
+    fn index(&self, index: [usize; N]) -> &T {
+        let _: () = RankCheck::<S, N>::OK;
+        match self.get(&index) {
+            Some(x) => x,
+            None => panic!(
+                "Index {:?} is out of bounds for shape {:?}.",
+                index,
+                self.shape()
+            ),
+        }
+    }
+}
+
+impl<T, S, C, L, P, const N: usize> IndexMut<[usize; N]> for Tensor<T, S, C, L, P>
+where
+    S: Shape,
+    L: for<'a> Layout<'a, T> + for<'a> LayoutMut<'a, T>,
+{
+    fn index_mut(&mut self, index: [usize; N]) -> &mut T {
+        let _: () = RankCheck::<S, N>::OK;
+        let shape = self.shape();
+        match self.get_mut(&index) {
+            Some(x) => x,
+            None => panic!("Index {:?} is out of bounds for shape {:?}.", index, shape),
+        }
+    }
 }
 
 impl<T, S, L, P> Tensor<T, S, Contiguous, L, P> {
@@ -393,7 +648,7 @@ impl<T, S, L, P> Tensor<T, S, Contiguous, L, P> {
         let current_shape = self.shape();
         assert_eq!(
             num_elements,
-            current_shape.iter().product(),
+            current_shape.iter().product::<usize>(),
             "Cannot reshape Tensor of shape {:?} to {:?}. Differing number of elements.",
             current_shape,
             shape,
@@ -0,0 +1,178 @@
+//! `tensordot` contains products of tensors that generalize the vector and
+//! matrix dot products of `linear_algebra`:
+//! * `outer`, the outer product of two vectors,
+//! * `tensordot`, the contraction of arbitrary pairs of axes of two tensors,
+//!   the contracted axes being given as type-level collections of indices,
+//! * `kron`, the Kronecker product of two matrices.
+//!
+//! All of them are batched matrix products computed with `contract_dot`
+//! from `einsum`, backed by `dgemm` and `sgemm` with the `blas` feature.
+//! Operands are read in logical order so views of any transpose policy are
+//! supported. Results are contiguous and use the allocation policy of `self`.
+
+use super::allocation_policy::StaticAllocationPolicy;
+use super::einsum::{contract_dot, Contraction, Operand};
+use super::layout::Layout;
+use super::shape::{Axes, Kron, Shape1D, Shape2D, StaticShape, TensorDot};
+use super::tensor::Tensor;
+use super::transpose_policy::Contiguous;
+use typenum::Unsigned;
+
+impl<T, N, C, L, P> Tensor<T, Shape1D<N>, C, L, P>
+where
+    T: Contraction,
+    L: for<'a> Layout<'a, T>,
+{
+    /// Computes the outer product of vectors `self` and `other`.
+    #[allow(clippy::type_complexity)]
+    pub fn outer<M, Crhs, Lrhs, Prhs>(
+        &self,
+        other: &Tensor<T, Shape1D<M>, Crhs, Lrhs, Prhs>,
+    ) -> Tensor<
+        T,
+        Shape2D<N, M>,
+        Contiguous,
+        <P as StaticAllocationPolicy<T, Shape2D<N, M>>>::Layout,
+        P,
+    >
+    where
+        N: Unsigned,
+        M: Unsigned,
+        Lrhs: for<'a> Layout<'a, T>,
+        P: StaticAllocationPolicy<T, Shape2D<N, M>>,
+    {
+        let mut out: Tensor<T, Shape2D<N, M>, Contiguous, P::Layout, P> = Tensor::default();
+        contract_dot(
+            [&[0], &[1]],
+            [&Operand::new(self), &Operand::new(other)],
+            &[0, 1],
+            &mut out,
+        );
+
+        out
+    }
+}
+
+impl<T, S, C, L, P> Tensor<T, S, C, L, P>
+where
+    T: Contraction,
+    S: StaticShape,
+    L: for<'a> Layout<'a, T>,
+{
+    /// Contracts the axes `AxesA` of `self` with the respective axes `AxesB`
+    /// of `other`. The axes of the result are the remaining axes of `self`
+    /// followed by the remaining axes of `other`.
+    ///
+    /// Axes are type-level collections of (0-starting) indices such as
+    /// `Shape2D<U1, U2>`, checked at runtime to be distinct and in range.
+    #[allow(clippy::type_complexity)]
+    pub fn tensordot<AxesA, AxesB, Srhs, Crhs, Lrhs, Prhs>(
+        &self,
+        other: &Tensor<T, Srhs, Crhs, Lrhs, Prhs>,
+    ) -> Tensor<
+        T,
+        <S as TensorDot<Srhs, AxesA, AxesB>>::Output,
+        Contiguous,
+        <P as StaticAllocationPolicy<T, <S as TensorDot<Srhs, AxesA, AxesB>>::Output>>::Layout,
+        P,
+    >
+    where
+        S: TensorDot<Srhs, AxesA, AxesB>,
+        AxesA: Axes,
+        AxesB: Axes,
+        Srhs: StaticShape,
+        Lrhs: for<'a> Layout<'a, T>,
+        P: StaticAllocationPolicy<T, <S as TensorDot<Srhs, AxesA, AxesB>>::Output>,
+    {
+        let (axes_a, axes_b) = (AxesA::axes(), AxesB::axes());
+        let check = |axes: &[usize], rank: usize| {
+            for (i, axis) in axes.iter().enumerate() {
+                assert!(
+                    *axis < rank && !axes[..i].contains(axis),
+                    "Contracted axes {:?} must be distinct and less than {}.",
+                    axes,
+                    rank
+                );
+            }
+        };
+        check(&axes_a, S::LEN);
+        check(&axes_b, Srhs::LEN);
+
+        // Contracted axes of `other` share the labels of the
+        // respective axes of `self`, other axes have their own.
+        let labels_a: Vec<u8> = (0..S::LEN as u8).collect();
+        let labels_b: Vec<u8> = (0..Srhs::LEN)
+            .map(|axis| match axes_b.iter().position(|a| *a == axis) {
+                Some(i) => axes_a[i] as u8,
+                None => (S::LEN + axis) as u8,
+            })
+            .collect();
+        let out_labels: Vec<u8> = labels_a
+            .iter()
+            .filter(|l| !axes_a.contains(&(**l as usize)))
+            .chain(labels_b.iter().filter(|l| **l as usize >= S::LEN))
+            .copied()
+            .collect();
+
+        let mut out: Tensor<
+            T,
+            <S as TensorDot<Srhs, AxesA, AxesB>>::Output,
+            Contiguous,
+            <P as StaticAllocationPolicy<T, <S as TensorDot<Srhs, AxesA, AxesB>>::Output>>::Layout,
+            P,
+        > = Tensor::default();
+        contract_dot(
+            [&labels_a, &labels_b],
+            [&Operand::new(self), &Operand::new(other)],
+            &out_labels,
+            &mut out,
+        );
+
+        out
+    }
+}
+
+impl<T, M, N, C, L, P> Tensor<T, Shape2D<M, N>, C, L, P>
+where
+    T: Contraction,
+    L: for<'a> Layout<'a, T>,
+{
+    /// Computes the Kronecker product of matrices `self` and `other`.
+    #[allow(clippy::type_complexity)]
+    pub fn kron<Mrhs, Nrhs, Crhs, Lrhs, Prhs>(
+        &self,
+        other: &Tensor<T, Shape2D<Mrhs, Nrhs>, Crhs, Lrhs, Prhs>,
+    ) -> Tensor<
+        T,
+        <Shape2D<M, N> as Kron<Shape2D<Mrhs, Nrhs>>>::Output,
+        Contiguous,
+        <P as StaticAllocationPolicy<T, <Shape2D<M, N> as Kron<Shape2D<Mrhs, Nrhs>>>::Output>>::Layout,
+        P,
+    >
+    where
+        Shape2D<M, N>: Kron<Shape2D<Mrhs, Nrhs>>,
+        Lrhs: for<'a> Layout<'a, T>,
+        P: StaticAllocationPolicy<T, <Shape2D<M, N> as Kron<Shape2D<Mrhs, Nrhs>>>::Output>,
+    {
+        // The result of shape (M * Mrhs, N * Nrhs) has the
+        // layout of the outer product of shape (M, Mrhs, N, Nrhs).
+        let mut out: Tensor<
+            T,
+            <Shape2D<M, N> as Kron<Shape2D<Mrhs, Nrhs>>>::Output,
+            Contiguous,
+            <P as StaticAllocationPolicy<
+                T,
+                <Shape2D<M, N> as Kron<Shape2D<Mrhs, Nrhs>>>::Output,
+            >>::Layout,
+            P,
+        > = Tensor::default();
+        contract_dot(
+            [&[0, 1], &[2, 3]],
+            [&Operand::new(self), &Operand::new(other)],
+            &[0, 2, 1, 3],
+            &mut out,
+        );
+
+        out
+    }
+}
@@ -8,8 +8,18 @@
 //! Policies are simply zero-sized structs that implement `TransposePolicy`.
 //! If they allow BLAS operations, they also implement `BLASPolicy`.
 
-extern crate cblas;
-use cblas::Transpose;
+#[cfg(feature = "blas")]
+pub use cblas::Transpose;
+
+/// Transposition flag of BLAS routines, mirrors `cblas::Transpose`
+/// when the `blas` feature is disabled.
+#[cfg(not(feature = "blas"))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Transpose {
+    None,
+    Ordinary,
+    Conjugate,
+}
 
 /// Policy used with contiguous tensors directly compatible with BLAS.
 #[derive(Debug, PartialEq, Clone)]